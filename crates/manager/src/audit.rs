@@ -0,0 +1,85 @@
+// src/audit.rs
+
+use std::fmt;
+
+use sea_orm::*;
+use serde::Serialize;
+
+use crate::entities::audit_event;
+
+/// Who asked for a change, a Discord user, the principal of an API token or whoever ran the `migrate` binary.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Actor<'a> {
+    Discord(&'a str),
+    Api(&'a str),
+    Cli(&'a str),
+}
+
+impl fmt::Display for Actor<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Actor::Discord(name) => write!(f, "discord:{name}"),
+            Actor::Api(principal) => write!(f, "api:{principal}"),
+            Actor::Cli(user) => write!(f, "cli:{user}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    Create,
+    Update,
+    /// An outpost moved onto a problem.
+    Assign,
+    Delete,
+}
+
+impl Action {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Action::Create => "create",
+            Action::Update => "update",
+            Action::Assign => "assign",
+            Action::Delete => "delete",
+        }
+    }
+}
+
+/// The corporation whose rows an actor is changing, each change is recorded in the transaction making it.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Trail<'a> {
+    pub corporation_id: i32,
+    pub actor: Actor<'a>,
+}
+
+impl Trail<'_> {
+    pub(crate) async fn record<C: ConnectionTrait, M: Serialize>(
+        &self,
+        db: &C,
+        action: Action,
+        entity: &'static str,
+        entity_id: i32,
+        before: Option<&M>,
+        after: Option<&M>
+    ) -> Result<(), DbErr> {
+        audit_event::ActiveModel {
+            corporation_id: ActiveValue::Set(self.corporation_id),
+            actor: ActiveValue::Set(self.actor.to_string()),
+            action: ActiveValue::Set(action.as_str().to_owned()),
+            entity: ActiveValue::Set(entity.to_owned()),
+            entity_id: ActiveValue::Set(entity_id),
+            before: ActiveValue::Set(snapshot(before)?),
+            after: ActiveValue::Set(snapshot(after)?),
+            ..Default::default()
+        }
+        .insert(db)
+        .await?;
+        Ok(())
+    }
+}
+
+fn snapshot<M: Serialize>(model: Option<&M>) -> Result<Option<serde_json::Value>, DbErr> {
+    model
+        .map(|model| serde_json::to_value(model).map_err(|e| DbErr::Json(e.to_string())))
+        .transpose()
+}
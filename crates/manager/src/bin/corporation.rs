@@ -0,0 +1,83 @@
+// src/bin/corporation.rs
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+
+use manager::audit::Actor;
+use manager::database::DatabaseManager;
+use manager::entities::prelude::Corporation;
+use manager::environment::EnvironmentManager;
+use manager::export::{self, Export, OnCollision};
+
+#[derive(Parser)]
+struct Cli {
+    /// Name of the configuration
+    #[arg(short, long, default_value = "local")]
+    config: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Print the corporation's planning state as JSON
+    Export { corporation: String },
+    /// Load an export, into the corporation it was taken from unless another is named
+    Import {
+        file: PathBuf,
+        /// Corporation receiving the export, it must exist
+        #[arg(long)]
+        into: Option<String>,
+        /// Import rows whose name is taken as `<name> (2)` instead of merging them
+        #[arg(long)]
+        rename: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Cli::parse();
+    let config = EnvironmentManager::load_config(&args.config)
+        .await
+        .expect("Failed to load configuration");
+    let db = DatabaseManager::connect(&config)
+        .await
+        .expect("Failed to connect to database");
+
+    match args.command {
+        Command::Export { corporation } => {
+            let corporation = Corporation::find_by_name(&corporation, &db)
+                .await
+                .expect("Failed to find corporation")
+                .expect("No corporation has this name");
+            let export = export::export_corporation(&db, corporation.id)
+                .await
+                .expect("Failed to export corporation");
+            println!("{}", serde_json::to_string_pretty(&export).expect("Failed to write export"));
+        }
+        Command::Import { file, into, rename } => {
+            let json = std::fs::read_to_string(&file).expect("Failed to read export");
+            let export: Export = serde_json::from_str(&json).expect("Failed to parse export");
+            let corporation = match into {
+                Some(name) => Corporation::find_by_name(&name, &db)
+                    .await
+                    .expect("Failed to find corporation")
+                    .expect("No corporation has this name"),
+                None => export::corporation_for_import(&db, &export)
+                    .await
+                    .expect("Failed to add corporation"),
+            };
+            let on_collision = if rename { OnCollision::Rename } else { OnCollision::Merge };
+            let user = std::env::var("USER").unwrap_or_else(|_| "unknown".to_owned());
+            let summary = export::import_corporation(&db, Actor::Cli(&user), corporation.id, &export, on_collision)
+                .await
+                .expect("Failed to import corporation");
+            println!(
+                "Imported into {}: {} created, {} merged, {} renamed",
+                corporation.name, summary.created, summary.merged, summary.renamed
+            );
+        }
+    }
+}
@@ -0,0 +1,75 @@
+// src/bin/migrate.rs
+
+use clap::{Parser, Subcommand};
+use sea_orm_migration::MigrationStatus;
+
+use manager::database::{DatabaseManager, MigratorTrait};
+use manager::environment::EnvironmentManager;
+use manager::migrator::Migrator;
+
+#[derive(Parser)]
+struct Cli {
+    /// Name of the configuration
+    #[arg(short, long, default_value = "local")]
+    config: String,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// List every migration and whether it was applied
+    Status,
+    /// Apply the next N pending migrations, all of them by default
+    Up { steps: Option<u32> },
+    /// Roll back the last N applied migrations
+    Down {
+        #[arg(default_value_t = 1)]
+        steps: u32,
+    },
+    /// Drop every table and apply all migrations from scratch
+    Fresh {
+        /// Confirm that every row may be lost
+        #[arg(long)]
+        yes: bool,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Cli::parse();
+    let config = EnvironmentManager::load_config(&args.config)
+        .await
+        .expect("Failed to load configuration");
+    let db = DatabaseManager::connect(&config)
+        .await
+        .expect("Failed to connect to database");
+
+    let result = match args.command {
+        Command::Status => {
+            let migrations = Migrator::get_migration_with_status(&db)
+                .await
+                .expect("Failed to read migrations");
+            for migration in migrations {
+                let status = match migration.status() {
+                    MigrationStatus::Applied => "applied",
+                    MigrationStatus::Pending => "pending",
+                };
+                println!("{status:<8} {}", migration.name());
+            }
+            Ok(())
+        }
+        Command::Up { steps } => Migrator::up(&db, steps).await,
+        Command::Down { steps } => Migrator::down(&db, Some(steps)).await,
+        Command::Fresh { yes: false } => {
+            eprintln!("fresh drops every table and its rows, pass --yes to go ahead");
+            std::process::exit(1);
+        }
+        Command::Fresh { yes: true } => Migrator::fresh(&db).await,
+    };
+    if let Err(error) = result {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+}
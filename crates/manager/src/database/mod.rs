@@ -14,19 +14,67 @@ use crate::environment::Configuration;
 
 pub struct DatabaseManager;
 
+/// What a service does with migrations that were not applied yet when it starts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrationPolicy {
+    /// Apply them before serving.
+    #[default]
+    Apply,
+    /// Refuse to start until they are applied with the `migrate` binary.
+    Require,
+}
+
+impl MigrationPolicy {
+    /// `APP_MIGRATIONS=require` refuses to start with pending migrations, anything else applies them.
+    pub fn from_env() -> Self {
+        match std::env::var("APP_MIGRATIONS").as_deref() {
+            Ok("require") => MigrationPolicy::Require,
+            _ => MigrationPolicy::Apply,
+        }
+    }
+}
+
 impl DatabaseManager {
-    pub async fn revision(config: &Configuration) -> Result<DatabaseConnection, DbErr> {
+    /// Open the configured database, creating it when missing, without touching its schema.
+    pub async fn connect(config: &Configuration) -> Result<DatabaseConnection, DbErr> {
         let db = Database::connect(&config.url).await?;
-        let db = match db.get_database_backend() {
-            DbBackend::MySql => mysql::Session::open(&config).await?,
-            DbBackend::Postgres => postgres::Session::open(&config).await?,
-            DbBackend::Sqlite => db,
-        };
+        match db.get_database_backend() {
+            DbBackend::MySql => mysql::Session::open(config).await,
+            DbBackend::Postgres => postgres::Session::open(config).await,
+            DbBackend::Sqlite => Ok(db),
+        }
+    }
+
+    pub async fn revision(config: &Configuration) -> Result<DatabaseConnection, DbErr> {
+        let db = Self::connect(config).await?;
 
         Migrator::up(&db, None).await?;
 
         Ok(db)
     }
+
+    /// Open the configured database for a service, applying or requiring migrations as the policy says.
+    pub async fn start(config: &Configuration, policy: MigrationPolicy) -> Result<DatabaseConnection, DbErr> {
+        if policy == MigrationPolicy::Apply {
+            return Self::revision(config).await;
+        }
+        let db = Self::connect(config).await?;
+        let pending = Self::pending(&db).await?;
+        if !pending.is_empty() {
+            return Err(DbErr::Migration(format!(
+                "{} pending migrations, run `migrate up` first: {}",
+                pending.len(),
+                pending.join(", ")
+            )));
+        }
+        Ok(db)
+    }
+
+    /// Names of the migrations not applied to the database yet, oldest first.
+    pub async fn pending(db: &DatabaseConnection) -> Result<Vec<String>, DbErr> {
+        let pending = Migrator::get_pending_migrations(db).await?;
+        Ok(pending.iter().map(|migration| migration.name().to_owned()).collect())
+    }
 }
 
 #[async_trait::async_trait]
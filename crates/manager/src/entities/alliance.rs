@@ -1,18 +1,32 @@
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+use crate::Error;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "alliance")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
+    #[sea_orm(unique)]
     pub name: String,
     pub active: bool,
+    /// Corporation allowed to rename, close and admit corporations to the alliance.
+    pub executor_id: Option<i32>,
 }
 
 impl Model {
     pub fn deactivate(&mut self) {
         self.active = false;
     }
+
+    pub fn restore(&mut self) {
+        self.active = true;
+    }
+
+    pub fn is_executor(&self, corporation_id: i32) -> bool {
+        self.executor_id == Some(corporation_id)
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -30,11 +44,20 @@ impl Related<super::corporation::Entity> for Entity {
 impl ActiveModelBehavior for ActiveModel {}
 
 impl Entity {
-    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, sea_orm::DbErr> {
+    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
         Entity::find()
             .filter(Column::Name.eq(name))
             .one(db)
             .await
             .map_err(|e| e.into())
     }
-}
\ No newline at end of file
+
+    pub async fn find_active_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::Name.eq(name))
+            .filter(Column::Active.eq(true))
+            .one(db)
+            .await
+            .map_err(|e| e.into())
+    }
+}
@@ -0,0 +1,51 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Serialize;
+
+use crate::Error;
+
+/// Arrays an outpost has anchored on a resource of a planet today.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "array_placement")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub outpost_id: i32,
+    pub planet_id: i64,
+    pub resource_type_id: i64,
+    pub count: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::outpost::Entity",
+        from = "Column::OutpostId",
+        to = "super::outpost::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Outpost,
+}
+
+impl Related<super::outpost::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Outpost.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// The layout of the outposts, by outpost then planet.
+    pub async fn find_by_outposts(outpost_ids: Vec<i32>, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::OutpostId.is_in(outpost_ids))
+            .order_by_asc(Column::OutpostId)
+            .order_by_asc(Column::PlanetId)
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+}
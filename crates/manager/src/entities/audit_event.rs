@@ -0,0 +1,83 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use sea_orm::QuerySelect;
+use serde::Serialize;
+
+use crate::Error;
+
+/// A change made through the service layer, with the row as it was before and after.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "audit_event")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub corporation_id: i32,
+    pub actor: String,
+    pub action: String,
+    pub entity: String,
+    pub entity_id: i32,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub before: Option<Json>,
+    #[sea_orm(column_type = "JsonBinary", nullable)]
+    pub after: Option<Json>,
+    pub created_at: DateTimeUtc,
+}
+
+impl Model {
+    /// Fields whose value differs between the before and after snapshots, every field of a created or deleted row.
+    pub fn changed_fields(&self) -> Vec<String> {
+        let empty = serde_json::Map::new();
+        let before = self.before.as_ref().and_then(Json::as_object).unwrap_or(&empty);
+        let after = self.after.as_ref().and_then(Json::as_object).unwrap_or(&empty);
+        let mut fields: Vec<String> = before
+            .keys()
+            .chain(after.keys().filter(|key| !before.contains_key(*key)))
+            .filter(|key| before.get(*key) != after.get(*key))
+            .cloned()
+            .collect();
+        fields.sort();
+        fields
+    }
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::corporation::Entity",
+        from = "Column::CorporationId",
+        to = "super::corporation::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Corporation,
+}
+
+impl Related<super::corporation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Corporation.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// The corporation's latest events first, optionally only those about one entity.
+    pub async fn find_by_corporation(
+        corporation_id: i32,
+        entity: Option<&str>,
+        limit: u64,
+        db: &DatabaseConnection
+    ) -> Result<Vec<Model>, Error> {
+        let mut select = Entity::find().filter(Column::CorporationId.eq(corporation_id));
+        if let Some(entity) = entity {
+            select = select.filter(Column::Entity.eq(entity));
+        }
+        select
+            .order_by_desc(Column::CreatedAt)
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+}
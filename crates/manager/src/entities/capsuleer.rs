@@ -1,6 +1,11 @@
 use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+use crate::lookup::only;
+use crate::Error;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "capsuleer")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -15,6 +20,10 @@ impl Model {
     pub fn deactivate(&mut self) {
         self.active = false;
     }
+
+    pub fn restore(&mut self) {
+        self.active = true;
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -60,11 +69,52 @@ impl Related<super::corporation::Entity> for Entity {
 impl ActiveModelBehavior for ActiveModel {}
 
 impl Entity {
-    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, sea_orm::DbErr> {
+    /// Any capsuleer with the name, ambiguous once several owners use it.
+    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        only(Entity::find().filter(Column::Name.eq(name)), "capsuleer", name, db).await
+    }
+
+    pub async fn find_by_corporation(corporation_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    pub async fn find_active_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        let select = Entity::find()
+            .filter(Column::Name.eq(name))
+            .filter(Column::Active.eq(true));
+        only(select, "capsuleer", name, db).await
+    }
+
+    pub async fn find_active_by_corporation(corporation_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
         Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .filter(Column::Active.eq(true))
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    /// The corporation's capsuleer with the name, the active one when a retired capsuleer shares it.
+    pub async fn find_by_corporation_and_name(corporation_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
             .filter(Column::Name.eq(name))
+            .order_by_desc(Column::Active)
             .one(db)
             .await
             .map_err(|e| e.into())
     }
-}
\ No newline at end of file
+
+    /// The corporation's retired capsuleer with the name, ambiguous when several were retired under it.
+    pub async fn find_retired_by_corporation_and_name(corporation_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        let select = Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .filter(Column::Name.eq(name))
+            .filter(Column::Active.eq(false));
+        only(select, "capsuleer", name, db).await
+    }
+}
@@ -1,10 +1,14 @@
 use sea_orm::entity::prelude::*;
+use serde::Serialize;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+use crate::Error;
+
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "corporation")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
+    #[sea_orm(unique)]
     pub name: String,
     pub active: bool,
     pub alliance_id: i32,
@@ -14,6 +18,10 @@ impl Model {
     pub fn deactivate(&mut self) {
         self.active = false;
     }
+
+    pub fn restore(&mut self) {
+        self.active = true;
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -53,11 +61,37 @@ impl Related<super::alliance::Entity> for Entity {
 impl ActiveModelBehavior for ActiveModel {}
 
 impl Entity {
-    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, sea_orm::DbErr> {
+    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
         Entity::find()
             .filter(Column::Name.eq(name))
             .one(db)
             .await
             .map_err(|e| e.into())
     }
-}
\ No newline at end of file
+
+    pub async fn find_by_alliance(alliance_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::AllianceId.eq(alliance_id))
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    pub async fn find_active_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::Name.eq(name))
+            .filter(Column::Active.eq(true))
+            .one(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    pub async fn find_active_by_alliance(alliance_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::AllianceId.eq(alliance_id))
+            .filter(Column::Active.eq(true))
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+}
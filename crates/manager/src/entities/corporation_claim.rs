@@ -0,0 +1,36 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+/// A one-time token proving a caller speaks for the corporation, redeemed to bind a Discord guild to it.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "corporation_claim")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub corporation_id: i32,
+    /// Kept out of the audit trail, whoever can read it could redeem it.
+    #[sea_orm(unique)]
+    #[serde(skip)]
+    pub token: String,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::corporation::Entity",
+        from = "Column::CorporationId",
+        to = "super::corporation::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Corporation,
+}
+
+impl Related<super::corporation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Corporation.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
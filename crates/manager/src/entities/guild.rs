@@ -0,0 +1,60 @@
+use sea_orm::entity::prelude::*;
+use serde::Serialize;
+
+use crate::Error;
+
+/// A Discord guild bound to the corporation and alliance its commands act for.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "guild")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    #[sea_orm(unique)]
+    pub guild_id: i64,
+    pub corporation_id: i32,
+    pub alliance_id: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::corporation::Entity",
+        from = "Column::CorporationId",
+        to = "super::corporation::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Corporation,
+    #[sea_orm(
+        belongs_to = "super::alliance::Entity",
+        from = "Column::AllianceId",
+        to = "super::alliance::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Alliance,
+}
+
+impl Related<super::corporation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Corporation.def()
+    }
+}
+
+impl Related<super::alliance::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Alliance.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    pub async fn find_by_guild_id(guild_id: i64, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::GuildId.eq(guild_id))
+            .one(db)
+            .await
+            .map_err(|e| e.into())
+    }
+}
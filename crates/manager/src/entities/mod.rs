@@ -3,9 +3,17 @@
 pub mod prelude;
 
 pub mod alliance;
+pub mod array_placement;
+pub mod audit_event;
 pub mod capsuleer;
 pub mod corporation;
+pub mod corporation_claim;
+pub mod guild;
 pub mod member;
 pub mod outpost;
+pub mod outpost_problem;
+pub mod price_book;
+pub mod price_book_entry;
+pub mod price_snapshot;
 pub mod problem;
 pub mod skill;
@@ -1,8 +1,10 @@
 use sea_orm::entity::prelude::*;
-use sea_orm::DeleteResult;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize, PartialEq, DeriveEntityModel, Eq)]
+use crate::lookup::only;
+use crate::Error;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "outpost")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -12,7 +14,8 @@ pub struct Model {
     pub planets: i32,
     pub arrays: i32,
     pub capsuleer_id: i32,
-    pub problem_id: Option<i32>
+    pub problem_id: Option<i32>,
+    pub active: bool,
 }
 
 impl Model {
@@ -21,6 +24,15 @@ impl Model {
         self.planets = 0;
         self.arrays = 0;
     }
+
+    pub fn deactivate(&mut self) {
+        self.active = false;
+        self.problem_id = None;
+    }
+
+    pub fn restore(&mut self) {
+        self.active = true;
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -59,27 +71,89 @@ impl Related<super::problem::Entity> for Entity {
 impl ActiveModelBehavior for ActiveModel {}
 
 impl Entity {
-    pub async fn delete_by_name(name: &str, db: &DatabaseConnection) -> Result<DeleteResult, sea_orm::DbErr> {
-        Entity::delete_many()
+    /// Any outpost with the name, ambiguous once several owners use it.
+    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        only(Entity::find().filter(Column::Name.eq(name)), "outpost", name, db).await
+    }
+
+    pub async fn find_by_capsuleer(capsuleer_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::CapsuleerId.eq(capsuleer_id))
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    pub async fn find_active_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        let select = Entity::find()
             .filter(Column::Name.eq(name))
-            .exec(db)
+            .filter(Column::Active.eq(true));
+        only(select, "outpost", name, db).await
+    }
+
+    pub async fn find_active_by_capsuleer(capsuleer_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::CapsuleerId.eq(capsuleer_id))
+            .filter(Column::Active.eq(true))
+            .all(db)
             .await
             .map_err(|e| e.into())
     }
-    
-    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, sea_orm::DbErr> {
+
+    pub async fn find_by_capsuleer_and_name(capsuleer_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
         Entity::find()
+            .filter(Column::CapsuleerId.eq(capsuleer_id))
             .filter(Column::Name.eq(name))
             .one(db)
             .await
             .map_err(|e| e.into())
     }
 
-    pub async fn find_by_capsuleer(capsuleer_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, sea_orm::DbErr> {
+    pub async fn find_active_by_corporation(corporation_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
         Entity::find()
-            .filter(Column::CapsuleerId.eq(capsuleer_id))
+            .inner_join(super::capsuleer::Entity)
+            .filter(super::capsuleer::Column::CorporationId.eq(corporation_id))
+            .filter(Column::Active.eq(true))
             .all(db)
             .await
             .map_err(|e| e.into())
     }
-}
\ No newline at end of file
+
+    /// The corporation's active outposts with the problems each may serve, the assigned one first.
+    pub async fn find_active_with_problems(corporation_id: i32, db: &DatabaseConnection) -> Result<Vec<(Model, Vec<i32>)>, Error> {
+        let links = super::outpost_problem::Entity::find_by_corporation(corporation_id, db).await?;
+        let outposts = Entity::find_active_by_corporation(corporation_id, db).await?;
+        Ok(outposts
+            .into_iter()
+            .map(|outpost| {
+                let mut problems: Vec<i32> = outpost.problem_id.into_iter().collect();
+                for link in links.iter().filter(|link| link.outpost_id == outpost.id) {
+                    if !problems.contains(&link.problem_id) {
+                        problems.push(link.problem_id);
+                    }
+                }
+                (outpost, problems)
+            })
+            .collect())
+    }
+
+    /// The corporation's active outpost with the name, ambiguous when two of its capsuleers use it.
+    pub async fn find_active_by_corporation_and_name(corporation_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        let select = Entity::find()
+            .inner_join(super::capsuleer::Entity)
+            .filter(super::capsuleer::Column::CorporationId.eq(corporation_id))
+            .filter(Column::Name.eq(name))
+            .filter(Column::Active.eq(true));
+        only(select, "outpost", name, db).await
+    }
+
+    /// The corporation's deleted outpost with the name, ambiguous when several were deleted under it.
+    pub async fn find_retired_by_corporation_and_name(corporation_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        let select = Entity::find()
+            .inner_join(super::capsuleer::Entity)
+            .filter(super::capsuleer::Column::CorporationId.eq(corporation_id))
+            .filter(Column::Name.eq(name))
+            .filter(Column::Active.eq(false));
+        only(select, "outpost", name, db).await
+    }
+}
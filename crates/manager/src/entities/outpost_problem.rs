@@ -0,0 +1,62 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::{JoinType, QuerySelect};
+use serde::Serialize;
+
+use crate::Error;
+
+/// A further problem an outpost may serve besides the one it is assigned to.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "outpost_problem")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub outpost_id: i32,
+    pub problem_id: i32,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::outpost::Entity",
+        from = "Column::OutpostId",
+        to = "super::outpost::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Outpost,
+    #[sea_orm(
+        belongs_to = "super::problem::Entity",
+        from = "Column::ProblemId",
+        to = "super::problem::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Problem,
+}
+
+impl Related<super::outpost::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Outpost.def()
+    }
+}
+
+impl Related<super::problem::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Problem.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// Every link of the corporation's outposts to its active problems.
+    pub async fn find_by_corporation(corporation_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .join(JoinType::InnerJoin, Relation::Problem.def())
+            .filter(super::problem::Column::CorporationId.eq(corporation_id))
+            .filter(super::problem::Column::Active.eq(true))
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+}
@@ -3,8 +3,12 @@
 // pub use super::kraken::Entity as Kraken;
 // pub use super::doctrine::Entity as Doctrine;
 pub use super::alliance::Entity as Alliance;
+pub use super::array_placement::Entity as ArrayPlacement;
+pub use super::audit_event::Entity as AuditEvent;
 // pub use super::neighbor::Entity as Neighbor;
 pub use super::corporation::Entity as Corporation;
+pub use super::corporation_claim::Entity as CorporationClaim;
+pub use super::guild::Entity as Guild;
 // pub use super::dues::Entity as Dues;
 // pub use super::parts::Entity as Parts;
 // pub use super::debris::Entity as Debris;
@@ -16,5 +20,9 @@ pub use super::member::Entity as Member;
 // pub use super::ship::Entity as Ship;
 // pub use super::claim::Entity as Claim;
 pub use super::outpost::Entity as Outpost;
+pub use super::outpost_problem::Entity as OutpostProblem;
+pub use super::price_book::Entity as PriceBook;
+pub use super::price_book_entry::Entity as PriceBookEntry;
+pub use super::price_snapshot::Entity as PriceSnapshot;
 pub use super::capsuleer::Entity as Capsuleer;
 pub use super::skill::Entity as Skill;
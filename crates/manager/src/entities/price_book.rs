@@ -0,0 +1,84 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Serialize;
+
+use crate::Error;
+
+/// One version of a corporation's buyback rates, every edit publishes the next version.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "price_book")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub corporation_id: i32,
+    pub version: i32,
+    /// Who published the version, as the audit trail names them.
+    pub actor: String,
+    pub created_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::corporation::Entity",
+        from = "Column::CorporationId",
+        to = "super::corporation::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Corporation,
+    #[sea_orm(has_many = "super::price_book_entry::Entity")]
+    PriceBookEntry,
+}
+
+impl Related<super::corporation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Corporation.def()
+    }
+}
+
+impl Related<super::price_book_entry::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PriceBookEntry.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// The corporation's current prices, `None` before any were set.
+    pub async fn find_latest(corporation_id: i32, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .order_by_desc(Column::Version)
+            .one(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    pub async fn find_by_corporation_and_version(corporation_id: i32, version: i32, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .filter(Column::Version.eq(version))
+            .one(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    /// A version of the corporation's prices with its entries, the latest when no version is given.
+    pub async fn find_with_entries(
+        corporation_id: i32,
+        version: Option<i32>,
+        db: &DatabaseConnection
+    ) -> Result<Option<(Model, Vec<super::price_book_entry::Model>)>, Error> {
+        let book = match version {
+            Some(version) => Entity::find_by_corporation_and_version(corporation_id, version, db).await?,
+            None => Entity::find_latest(corporation_id, db).await?,
+        };
+        let Some(book) = book else {
+            return Ok(None);
+        };
+        let entries = super::price_book_entry::Entity::find_by_price_book(book.id, db).await?;
+        Ok(Some((book, entries)))
+    }
+}
@@ -0,0 +1,48 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use serde::Serialize;
+
+use crate::Error;
+
+/// What the corporation pays for a unit of the resource in one version of its price book.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Serialize)]
+#[sea_orm(table_name = "price_book_entry")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub price_book_id: i32,
+    pub resource_type_id: i64,
+    /// ISK per unit.
+    pub price: f64,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::price_book::Entity",
+        from = "Column::PriceBookId",
+        to = "super::price_book::Column::Id",
+        on_update = "NoAction",
+        on_delete = "Cascade"
+    )]
+    PriceBook,
+}
+
+impl Related<super::price_book::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::PriceBook.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    pub async fn find_by_price_book(price_book_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::PriceBookId.eq(price_book_id))
+            .order_by_asc(Column::ResourceTypeId)
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+}
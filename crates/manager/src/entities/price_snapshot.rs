@@ -0,0 +1,74 @@
+use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use sea_orm::QuerySelect;
+use serde::Serialize;
+
+use crate::Error;
+
+/// ISK per unit of each resource as a price source quoted them, keyed by resource type id.
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
+#[sea_orm(table_name = "price_snapshot")]
+pub struct Model {
+    #[sea_orm(primary_key)]
+    pub id: i32,
+    pub corporation_id: i32,
+    /// The pasted list, price file or price book the prices came from.
+    pub source: String,
+    #[sea_orm(column_type = "JsonBinary")]
+    pub prices: Json,
+    pub taken_at: DateTimeUtc,
+}
+
+#[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
+pub enum Relation {
+    #[sea_orm(
+        belongs_to = "super::corporation::Entity",
+        from = "Column::CorporationId",
+        to = "super::corporation::Column::Id",
+        on_update = "NoAction",
+        on_delete = "NoAction"
+    )]
+    Corporation,
+}
+
+impl Related<super::corporation::Entity> for Entity {
+    fn to() -> RelationDef {
+        Relation::Corporation.def()
+    }
+}
+
+impl ActiveModelBehavior for ActiveModel {}
+
+impl Entity {
+    /// The corporation's latest snapshots first.
+    pub async fn find_by_corporation(
+        corporation_id: i32,
+        limit: u64,
+        db: &DatabaseConnection
+    ) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .order_by_desc(Column::TakenAt)
+            .order_by_desc(Column::Id)
+            .limit(limit)
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    /// The corporation's prices as they were at the moment, the latest snapshot taken up to then.
+    pub async fn find_at(
+        corporation_id: i32,
+        at: DateTimeUtc,
+        db: &DatabaseConnection
+    ) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .filter(Column::TakenAt.lte(at))
+            .order_by_desc(Column::TakenAt)
+            .order_by_desc(Column::Id)
+            .one(db)
+            .await
+            .map_err(|e| e.into())
+    }
+}
@@ -1,24 +1,44 @@
 use sea_orm::entity::prelude::*;
+use sea_orm::QueryOrder;
+use serde::{Serialize, Serializer};
 
+use crate::lookup::only;
+use crate::Error;
 use crate::outpost;
 
-#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq)]
+#[derive(Clone, Debug, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "problem")]
 pub struct Model {
     #[sea_orm(primary_key)]
     pub id: i32,
     pub name: String,
+    #[serde(serialize_with = "as_text")]
     pub constraint: Vec<u8>,
     pub active: bool,
     pub member_id: i32,
     pub corporation_id: i32,
-    pub alliance_id: Option<i32>
+    pub alliance_id: Option<i32>,
+    /// Value the materials at the corporation's price book instead of the list's own valuations.
+    pub corp_prices: bool,
+    /// Problems are served in ascending priority when they share outposts, 1 first.
+    pub priority: i32,
+    /// When the materials are needed by, open ended when unset.
+    pub deadline: Option<DateTimeUtc>,
+}
+
+/// The material list is pasted text, keep it readable wherever the model is serialized.
+fn as_text<S: Serializer>(constraint: &[u8], serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&String::from_utf8_lossy(constraint))
 }
 
 impl Model {
     pub fn deactivate(&mut self) {
         self.active = false;
     }
+
+    pub fn restore(&mut self) {
+        self.active = true;
+    }
 }
 
 #[derive(Copy, Clone, Debug, EnumIter, DeriveRelation)]
@@ -78,20 +98,53 @@ impl Related<super::alliance::Entity> for Entity {
 impl ActiveModelBehavior for ActiveModel {}
 
 impl Entity {
-    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, sea_orm::DbErr> {
+    /// Any problem with the name, ambiguous once several owners use it.
+    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        only(Entity::find().filter(Column::Name.eq(name)), "problem", name, db).await
+    }
+
+    pub async fn find_outposts_by_name(name: &str, db: &DatabaseConnection) -> Result<Vec<(Model, Option<outpost::Model>)>, Error> {
         Entity::find()
             .filter(Column::Name.eq(name))
-            .one(db)
+            .find_also_related(outpost::Entity)
+            .all(db)
             .await
             .map_err(|e| e.into())
     }
 
-    pub async fn find_outposts_by_name(name: &str, db: &DatabaseConnection) -> Result<Vec<(Model, Option<outpost::Model>)>, sea_orm::DbErr> {
-        Entity::find()
+    pub async fn find_active_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        let select = Entity::find()
             .filter(Column::Name.eq(name))
-            .find_also_related(outpost::Entity)
+            .filter(Column::Active.eq(true));
+        only(select, "problem", name, db).await
+    }
+
+    pub async fn find_active_by_corporation(corporation_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .filter(Column::Active.eq(true))
             .all(db)
             .await
             .map_err(|e| e.into())
     }
-}
\ No newline at end of file
+
+    /// The corporation's problem with the name, the active one when a retired problem shares it.
+    pub async fn find_by_corporation_and_name(corporation_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .filter(Column::Name.eq(name))
+            .order_by_desc(Column::Active)
+            .one(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    /// The corporation's retired problem with the name, ambiguous when several were retired under it.
+    pub async fn find_retired_by_corporation_and_name(corporation_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        let select = Entity::find()
+            .filter(Column::CorporationId.eq(corporation_id))
+            .filter(Column::Name.eq(name))
+            .filter(Column::Active.eq(false));
+        only(select, "problem", name, db).await
+    }
+}
@@ -1,8 +1,11 @@
 
 use sea_orm::entity::prelude::*;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, Deserialize, PartialEq, DeriveEntityModel, Eq)]
+use crate::lookup::only;
+use crate::Error;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, DeriveEntityModel, Eq, Serialize)]
 #[sea_orm(table_name = "skill")]
 pub struct Model {
     #[sea_orm(primary_key)]
@@ -44,11 +47,25 @@ impl Related<super::capsuleer::Entity> for Entity {
 impl ActiveModelBehavior for ActiveModel {}
 
 impl Entity {
-    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, sea_orm::DbErr> {
+    /// Any skill with the name, ambiguous once several owners use it.
+    pub async fn find_by_name(name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        only(Entity::find().filter(Column::Name.eq(name)), "skill", name, db).await
+    }
+
+    pub async fn find_by_capsuleer(capsuleer_id: i32, db: &DatabaseConnection) -> Result<Vec<Model>, Error> {
         Entity::find()
+            .filter(Column::CapsuleerId.eq(capsuleer_id))
+            .all(db)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    pub async fn find_by_capsuleer_and_name(capsuleer_id: i32, name: &str, db: &DatabaseConnection) -> Result<Option<Model>, Error> {
+        Entity::find()
+            .filter(Column::CapsuleerId.eq(capsuleer_id))
             .filter(Column::Name.eq(name))
             .one(db)
             .await
             .map_err(|e| e.into())
     }
-}
\ No newline at end of file
+}
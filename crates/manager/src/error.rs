@@ -0,0 +1,82 @@
+// src/error.rs
+
+use std::fmt;
+
+use sea_orm::{DbErr, SqlErr};
+
+/// Why a manager operation failed, with the entity and key it was working on when the database can tell.
+#[derive(Debug)]
+pub enum Error {
+    /// No row of the entity has the key.
+    NotFound { entity: &'static str, key: String },
+    /// The key is already taken for the owner.
+    Duplicate { entity: &'static str, key: String },
+    /// More than one row has the name, the lookup needs the owner to tell them apart.
+    Ambiguous { entity: &'static str, key: String },
+    /// The row exists but was deactivated.
+    Inactive { entity: &'static str, key: String },
+    /// The input is well formed but this version of eve-anchor cannot use it.
+    Unsupported { entity: &'static str, key: String },
+    /// The row belongs to someone the caller has not proven to speak for.
+    Forbidden { entity: &'static str, key: String },
+    /// The database could not be reached.
+    Connection(DbErr),
+    Database(DbErr),
+}
+
+impl Error {
+    /// Classify a database error raised while working on the entity's row with the key.
+    pub fn classify(error: DbErr, entity: &'static str, key: impl fmt::Display) -> Self {
+        if let Some(SqlErr::UniqueConstraintViolation(_)) = error.sql_err() {
+            return Error::Duplicate { entity, key: key.to_string() };
+        }
+        match error {
+            DbErr::RecordNotFound(_) | DbErr::RecordNotUpdated => Error::NotFound { entity, key: key.to_string() },
+            error => error.into(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotFound { entity, key } => write!(f, "{entity} {key} was not found"),
+            Error::Duplicate { entity, key } => write!(f, "{entity} {key} already exists"),
+            Error::Ambiguous { entity, key } => write!(f, "more than one {entity} is named {key}"),
+            Error::Inactive { entity, key } => write!(f, "{entity} {key} is inactive"),
+            Error::Unsupported { entity, key } => write!(f, "{entity} {key} is not supported"),
+            Error::Forbidden { entity, key } => write!(f, "{entity} {key} belongs to someone else"),
+            Error::Connection(error) => write!(f, "the database could not be reached: {error}"),
+            Error::Database(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Connection(error) | Error::Database(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<DbErr> for Error {
+    fn from(error: DbErr) -> Self {
+        match error {
+            DbErr::Conn(_) | DbErr::ConnectionAcquire(_) => Error::Connection(error),
+            error => Error::Database(error),
+        }
+    }
+}
+
+/// Attach the entity and key to a database result.
+pub(crate) trait Context<T> {
+    fn context(self, entity: &'static str, key: impl fmt::Display) -> Result<T, Error>;
+}
+
+impl<T> Context<T> for Result<T, DbErr> {
+    fn context(self, entity: &'static str, key: impl fmt::Display) -> Result<T, Error> {
+        self.map_err(|error| Error::classify(error, entity, key))
+    }
+}
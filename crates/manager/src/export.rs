@@ -0,0 +1,557 @@
+// src/export.rs
+
+use std::collections::{BTreeMap, HashMap};
+
+use sea_orm::*;
+use sea_orm::prelude::DateTimeUtc;
+use serde::{Deserialize, Serialize};
+
+use crate::audit::{Action, Actor, Trail};
+use crate::entities::*;
+use crate::entities::prelude::*;
+use crate::error::Context;
+use crate::Error;
+
+/// Version written by `export_corporation`, bumped whenever the layout of `Export` changes.
+/// Version 1 files, written before price books and array placements were exported, import without them.
+pub const EXPORT_VERSION: u32 = 2;
+
+/// A corporation's planning state, ids are the ones of the deployment it was exported from.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Export {
+    pub version: u32,
+    pub alliance: String,
+    pub corporation: String,
+    pub members: Vec<MemberExport>,
+    pub capsuleers: Vec<CapsuleerExport>,
+    pub skills: Vec<SkillExport>,
+    pub problems: Vec<ProblemExport>,
+    pub outposts: Vec<OutpostExport>,
+    /// Every version of the corporation's price book, oldest first.
+    #[serde(default)]
+    pub price_books: Vec<PriceBookExport>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemberExport {
+    pub id: i32,
+    pub name: String,
+    pub active: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CapsuleerExport {
+    pub id: i32,
+    pub name: String,
+    pub active: bool,
+    pub member_id: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillExport {
+    pub name: String,
+    pub basic: i32,
+    pub advanced: i32,
+    pub expert: i32,
+    pub capsuleer_id: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProblemExport {
+    pub id: i32,
+    pub name: String,
+    /// The material list as it was typed.
+    pub constraint: String,
+    pub active: bool,
+    pub member_id: i32,
+    /// Shared with the alliance.
+    pub shared: bool,
+    /// Valued at the corporation's price book, absent from exports made before price books.
+    #[serde(default)]
+    pub corp_prices: bool,
+    #[serde(default = "first")]
+    pub priority: i32,
+    #[serde(default)]
+    pub deadline: Option<DateTimeUtc>,
+}
+
+fn first() -> i32 {
+    1
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutpostExport {
+    pub id: i32,
+    pub name: String,
+    pub system: String,
+    pub planets: i32,
+    pub arrays: i32,
+    pub active: bool,
+    pub capsuleer_id: i32,
+    /// Only set when the problem belongs to the exported corporation.
+    pub problem_id: Option<i32>,
+    /// Further problems the outpost may serve.
+    #[serde(default)]
+    pub linked_problem_ids: Vec<i32>,
+    /// Arrays anchored on the outpost's planets today.
+    #[serde(default)]
+    pub placements: Vec<PlacementExport>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlacementExport {
+    pub planet_id: i64,
+    pub resource_type_id: i64,
+    pub count: i32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PriceBookExport {
+    pub version: i32,
+    pub actor: String,
+    pub created_at: DateTimeUtc,
+    /// ISK per unit, by resource type id.
+    pub prices: BTreeMap<i64, f64>,
+}
+
+/// What an import does with a row whose name is already taken in the corporation.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnCollision {
+    /// Keep the existing row and attach the imported children to it.
+    #[default]
+    Merge,
+    /// Import the row as `<name> (2)`, `<name> (3)`, ...
+    Rename,
+}
+
+/// Rows an import created, merged into existing ones or created under a new name.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ImportSummary {
+    pub created: usize,
+    pub merged: usize,
+    pub renamed: usize,
+}
+
+/// Everything the corporation plans with, inactive rows included.
+pub async fn export_corporation(db: &DatabaseConnection, corporation_id: i32) -> Result<Export, Error> {
+    let corporation = Corporation::find_by_id(corporation_id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound { entity: "corporation", key: corporation_id.to_string() })?;
+    let alliance = Alliance::find_by_id(corporation.alliance_id)
+        .one(db)
+        .await?
+        .ok_or(Error::NotFound { entity: "alliance", key: corporation.alliance_id.to_string() })?;
+    let members = Member::find()
+        .filter(member::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(member::Column::Id)
+        .all(db)
+        .await?;
+    let capsuleers = Capsuleer::find()
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(capsuleer::Column::Id)
+        .all(db)
+        .await?;
+    let skills = Skill::find()
+        .inner_join(Capsuleer)
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(skill::Column::Id)
+        .all(db)
+        .await?;
+    let problems = Problem::find()
+        .filter(problem::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(problem::Column::Id)
+        .all(db)
+        .await?;
+    let outposts = Outpost::find()
+        .inner_join(Capsuleer)
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(outpost::Column::Id)
+        .all(db)
+        .await?;
+    let links = OutpostProblem::find_by_corporation(corporation_id, db).await?;
+    let placements = ArrayPlacement::find()
+        .inner_join(Outpost)
+        .join(JoinType::InnerJoin, outpost::Relation::Capsuleer.def())
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(array_placement::Column::OutpostId)
+        .order_by_asc(array_placement::Column::PlanetId)
+        .all(db)
+        .await?;
+    let price_books = PriceBook::find()
+        .filter(price_book::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(price_book::Column::Version)
+        .find_with_related(PriceBookEntry)
+        .all(db)
+        .await?;
+
+    Ok(Export {
+        version: EXPORT_VERSION,
+        alliance: alliance.name,
+        corporation: corporation.name,
+        members: members.into_iter().map(|member| MemberExport {
+            id: member.id,
+            name: member.name,
+            active: member.active,
+        }).collect(),
+        capsuleers: capsuleers.into_iter().map(|capsuleer| CapsuleerExport {
+            id: capsuleer.id,
+            name: capsuleer.name,
+            active: capsuleer.active,
+            member_id: capsuleer.member_id,
+        }).collect(),
+        skills: skills.into_iter().map(|skill| SkillExport {
+            name: skill.name,
+            basic: skill.basic,
+            advanced: skill.advanced,
+            expert: skill.expert,
+            capsuleer_id: skill.capsuleer_id,
+        }).collect(),
+        outposts: outposts.into_iter().map(|outpost| OutpostExport {
+            id: outpost.id,
+            name: outpost.name,
+            system: outpost.system,
+            planets: outpost.planets,
+            arrays: outpost.arrays,
+            active: outpost.active,
+            capsuleer_id: outpost.capsuleer_id,
+            problem_id: outpost.problem_id.filter(|id| problems.iter().any(|problem| problem.id == *id)),
+            linked_problem_ids: links
+                .iter()
+                .filter(|link| link.outpost_id == outpost.id)
+                .map(|link| link.problem_id)
+                .collect(),
+            placements: placements
+                .iter()
+                .filter(|placement| placement.outpost_id == outpost.id)
+                .map(|placement| PlacementExport {
+                    planet_id: placement.planet_id,
+                    resource_type_id: placement.resource_type_id,
+                    count: placement.count,
+                })
+                .collect(),
+        }).collect(),
+        problems: problems.into_iter().map(|problem| ProblemExport {
+            id: problem.id,
+            name: problem.name,
+            constraint: String::from_utf8_lossy(&problem.constraint).into_owned(),
+            active: problem.active,
+            member_id: problem.member_id,
+            shared: problem.alliance_id.is_some(),
+            corp_prices: problem.corp_prices,
+            priority: problem.priority,
+            deadline: problem.deadline,
+        }).collect(),
+        price_books: price_books.into_iter().map(|(book, entries)| PriceBookExport {
+            version: book.version,
+            actor: book.actor,
+            created_at: book.created_at,
+            prices: entries.into_iter().map(|entry| (entry.resource_type_id, entry.price)).collect(),
+        }).collect(),
+    })
+}
+
+/// The corporation named in the export, added with its alliance when this deployment has neither.
+pub async fn corporation_for_import(db: &DatabaseConnection, export: &Export) -> Result<corporation::Model, Error> {
+    if let Some(corporation) = Corporation::find_by_name(&export.corporation, db).await? {
+        return Ok(corporation);
+    }
+    let txn = db.begin().await?;
+    let alliance = match Alliance::find().filter(alliance::Column::Name.eq(&export.alliance)).one(&txn).await? {
+        Some(alliance) => alliance,
+        None => alliance::ActiveModel {
+            name: ActiveValue::Set(export.alliance.clone()),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("alliance", &export.alliance)?,
+    };
+    let corporation = corporation::ActiveModel {
+        name: ActiveValue::Set(export.corporation.clone()),
+        alliance_id: ActiveValue::Set(alliance.id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("corporation", &export.corporation)?;
+    crate::claim_executor(&txn, alliance.id, corporation.id).await?;
+    txn.commit().await?;
+    Ok(corporation)
+}
+
+/// Recreate an export in the corporation under new ids, all or nothing.
+/// The price books are only taken over by a corporation without prices of its own.
+/// Systems are not checked here, the callers know the ones in Eve.
+pub async fn import_corporation(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    export: &Export,
+    on_collision: OnCollision
+) -> Result<ImportSummary, Error> {
+    if export.version == 0 || export.version > EXPORT_VERSION {
+        return Err(Error::Unsupported { entity: "export", key: format!("version {}", export.version) });
+    }
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let corporation = Corporation::find_by_id(corporation_id)
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "corporation", key: corporation_id.to_string() })?;
+    let mut summary = ImportSummary::default();
+
+    // Retired rows come first, so an active namesake is the one merged into.
+    let mut taken: HashMap<String, i32> = Member::find()
+        .filter(member::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(member::Column::Active)
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|member| (member.name, member.id))
+        .collect();
+    let mut members = HashMap::new();
+    for member in &export.members {
+        let name = match settle(&member.name, &taken, on_collision, &mut summary) {
+            Settled::Existing(id) => {
+                members.insert(member.id, id);
+                continue;
+            }
+            Settled::Free(name) => name,
+        };
+        let model = member::ActiveModel {
+            name: ActiveValue::Set(name.clone()),
+            active: ActiveValue::Set(member.active),
+            corporation_id: ActiveValue::Set(corporation_id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("member", &name)?;
+        trail.record(&txn, Action::Create, "member", model.id, None, Some(&model)).await?;
+        taken.insert(name, model.id);
+        members.insert(member.id, model.id);
+    }
+
+    let mut taken: HashMap<String, i32> = Problem::find()
+        .filter(problem::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(problem::Column::Active)
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|problem| (problem.name, problem.id))
+        .collect();
+    let mut problems = HashMap::new();
+    for problem in &export.problems {
+        let name = match settle(&problem.name, &taken, on_collision, &mut summary) {
+            Settled::Existing(id) => {
+                problems.insert(problem.id, id);
+                continue;
+            }
+            Settled::Free(name) => name,
+        };
+        let model = problem::ActiveModel {
+            name: ActiveValue::Set(name.clone()),
+            constraint: ActiveValue::Set(problem.constraint.clone().into_bytes()),
+            active: ActiveValue::Set(problem.active),
+            member_id: ActiveValue::Set(remap(&members, "member", problem.member_id)?),
+            corporation_id: ActiveValue::Set(corporation_id),
+            alliance_id: ActiveValue::Set(problem.shared.then_some(corporation.alliance_id)),
+            corp_prices: ActiveValue::Set(problem.corp_prices),
+            priority: ActiveValue::Set(problem.priority),
+            deadline: ActiveValue::Set(problem.deadline),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("problem", &name)?;
+        trail.record(&txn, Action::Create, "problem", model.id, None, Some(&model)).await?;
+        taken.insert(name, model.id);
+        problems.insert(problem.id, model.id);
+    }
+
+    let mut taken: HashMap<String, i32> = Capsuleer::find()
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .order_by_asc(capsuleer::Column::Active)
+        .all(&txn)
+        .await?
+        .into_iter()
+        .map(|capsuleer| (capsuleer.name, capsuleer.id))
+        .collect();
+    let mut capsuleers = HashMap::new();
+    for capsuleer in &export.capsuleers {
+        let name = match settle(&capsuleer.name, &taken, on_collision, &mut summary) {
+            Settled::Existing(id) => {
+                capsuleers.insert(capsuleer.id, id);
+                continue;
+            }
+            Settled::Free(name) => name,
+        };
+        let model = capsuleer::ActiveModel {
+            name: ActiveValue::Set(name.clone()),
+            active: ActiveValue::Set(capsuleer.active),
+            member_id: ActiveValue::Set(remap(&members, "member", capsuleer.member_id)?),
+            corporation_id: ActiveValue::Set(corporation_id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("capsuleer", &name)?;
+        trail.record(&txn, Action::Create, "capsuleer", model.id, None, Some(&model)).await?;
+        taken.insert(name, model.id);
+        capsuleers.insert(capsuleer.id, model.id);
+    }
+
+    for skill in &export.skills {
+        let capsuleer_id = remap(&capsuleers, "capsuleer", skill.capsuleer_id)?;
+        let existing = Skill::find()
+            .filter(skill::Column::CapsuleerId.eq(capsuleer_id))
+            .filter(skill::Column::Name.eq(&skill.name))
+            .one(&txn)
+            .await?;
+        if existing.is_some() {
+            summary.merged += 1;
+            continue;
+        }
+        let model = skill::ActiveModel {
+            name: ActiveValue::Set(skill.name.clone()),
+            basic: ActiveValue::Set(skill.basic),
+            advanced: ActiveValue::Set(skill.advanced),
+            expert: ActiveValue::Set(skill.expert),
+            capsuleer_id: ActiveValue::Set(capsuleer_id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("skill", &skill.name)?;
+        trail.record(&txn, Action::Create, "skill", model.id, None, Some(&model)).await?;
+        summary.created += 1;
+    }
+
+    for outpost in &export.outposts {
+        let capsuleer_id = remap(&capsuleers, "capsuleer", outpost.capsuleer_id)?;
+        let taken: HashMap<String, i32> = Outpost::find()
+            .filter(outpost::Column::CapsuleerId.eq(capsuleer_id))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|outpost| (outpost.name, outpost.id))
+            .collect();
+        let name = match settle(&outpost.name, &taken, on_collision, &mut summary) {
+            Settled::Existing(_) => continue,
+            Settled::Free(name) => name,
+        };
+        let problem_id = outpost.problem_id.map(|id| remap(&problems, "problem", id)).transpose()?;
+        let model = outpost::ActiveModel {
+            name: ActiveValue::Set(name.clone()),
+            system: ActiveValue::Set(outpost.system.clone()),
+            planets: ActiveValue::Set(outpost.planets),
+            arrays: ActiveValue::Set(outpost.arrays),
+            active: ActiveValue::Set(outpost.active),
+            capsuleer_id: ActiveValue::Set(capsuleer_id),
+            problem_id: ActiveValue::Set(problem_id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("outpost", &name)?;
+        trail.record(&txn, Action::Create, "outpost", model.id, None, Some(&model)).await?;
+        for linked_problem_id in &outpost.linked_problem_ids {
+            let link = outpost_problem::ActiveModel {
+                outpost_id: ActiveValue::Set(model.id),
+                problem_id: ActiveValue::Set(remap(&problems, "problem", *linked_problem_id)?),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .context("outpost_problem", &name)?;
+            trail.record(&txn, Action::Assign, "outpost_problem", link.id, None, Some(&link)).await?;
+        }
+        for placement in &outpost.placements {
+            let placement = array_placement::ActiveModel {
+                outpost_id: ActiveValue::Set(model.id),
+                planet_id: ActiveValue::Set(placement.planet_id),
+                resource_type_id: ActiveValue::Set(placement.resource_type_id),
+                count: ActiveValue::Set(placement.count),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .context("array_placement", &name)?;
+            trail.record(&txn, Action::Create, "array_placement", placement.id, None, Some(&placement)).await?;
+        }
+    }
+
+    let priced = PriceBook::find()
+        .filter(price_book::Column::CorporationId.eq(corporation_id))
+        .count(&txn)
+        .await?
+        > 0;
+    for book in &export.price_books {
+        if priced {
+            summary.merged += 1;
+            continue;
+        }
+        let model = price_book::ActiveModel {
+            corporation_id: ActiveValue::Set(corporation_id),
+            version: ActiveValue::Set(book.version),
+            actor: ActiveValue::Set(book.actor.clone()),
+            created_at: ActiveValue::Set(book.created_at),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("price_book", book.version)?;
+        for (resource_type_id, price) in &book.prices {
+            price_book_entry::ActiveModel {
+                price_book_id: ActiveValue::Set(model.id),
+                resource_type_id: ActiveValue::Set(*resource_type_id),
+                price: ActiveValue::Set(*price),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .context("price_book_entry", resource_type_id)?;
+        }
+        trail.record(&txn, Action::Create, "price_book", model.id, None, Some(&book.prices)).await?;
+        summary.created += 1;
+    }
+
+    txn.commit().await?;
+    Ok(summary)
+}
+
+enum Settled {
+    /// Merge into the row with this id.
+    Existing(i32),
+    /// Create the row under this name.
+    Free(String),
+}
+
+/// Pick the row to merge into or the name to create, counting the outcome.
+fn settle(name: &str, taken: &HashMap<String, i32>, on_collision: OnCollision, summary: &mut ImportSummary) -> Settled {
+    let Some(&id) = taken.get(name) else {
+        summary.created += 1;
+        return Settled::Free(name.to_owned());
+    };
+    match on_collision {
+        OnCollision::Merge => {
+            summary.merged += 1;
+            Settled::Existing(id)
+        }
+        OnCollision::Rename => {
+            summary.renamed += 1;
+            let free = (2..)
+                .map(|n| format!("{name} ({n})"))
+                .find(|candidate| !taken.contains_key(candidate))
+                .expect("a free name");
+            Settled::Free(free)
+        }
+    }
+}
+
+/// The id in this deployment of a row the export refers to.
+fn remap(ids: &HashMap<i32, i32>, entity: &'static str, id: i32) -> Result<i32, Error> {
+    ids.get(&id).copied().ok_or(Error::NotFound { entity, key: id.to_string() })
+}
@@ -1,53 +1,91 @@
 // src/lib.rs
 
+pub mod audit;
 pub mod database;
+pub mod error;
 pub mod entities;
 pub mod environment;
+pub mod export;
+mod lookup;
 pub mod migrator;
+pub mod service;
 
 
 use sea_orm::*;
+use sea_orm::prelude::{DateTimeUtc, Json};
 use entities::*;
 use entities::prelude::*;
 use migrator::sea_orm::InsertResult;
 
+pub use error::Error;
+use error::Context;
+
 
 
 pub async fn new_alliance(
     db: &DatabaseConnection,
     name: &str
-) -> Result<InsertResult<alliance::ActiveModel>, DbErr> {
+) -> Result<InsertResult<alliance::ActiveModel>, Error> {
     let alliance = alliance::ActiveModel {
         name: ActiveValue::Set(name.to_owned()),
         ..Default::default()
     };
-    Alliance::insert(alliance).exec(db).await
+    Alliance::insert(alliance).exec(db).await.context("alliance", name)
 }
 
 pub async fn new_corporation(
     db: &DatabaseConnection,
     name: &str,
     alliance_id: i32
-) -> Result<InsertResult<corporation::ActiveModel>, DbErr> {
+) -> Result<InsertResult<corporation::ActiveModel>, Error> {
     let corporation = corporation::ActiveModel {
         name: ActiveValue::Set(name.to_owned()),
         alliance_id: ActiveValue::Set(alliance_id),
         ..Default::default()
     };
-    Corporation::insert(corporation).exec(db).await
+    let corporation = Corporation::insert(corporation).exec(db).await.context("corporation", name)?;
+    claim_executor(db, alliance_id, corporation.last_insert_id).await?;
+    Ok(corporation)
+}
+
+/// Moves a corporation into another alliance, handing over the old alliance if it ran it.
+pub(crate) async fn join_alliance<C: ConnectionTrait>(db: &C, corporation: corporation::Model, alliance_id: i32) -> Result<corporation::Model, DbErr> {
+    let corporation_id = corporation.id;
+    let mut corporation: corporation::ActiveModel = corporation.into();
+    corporation.alliance_id = ActiveValue::Set(alliance_id);
+    let corporation = corporation.update(db).await?;
+    Alliance::update_many()
+        .col_expr(alliance::Column::ExecutorId, sea_query::Expr::value(Option::<i32>::None))
+        .filter(alliance::Column::ExecutorId.eq(corporation_id))
+        .filter(alliance::Column::Id.ne(alliance_id))
+        .exec(db)
+        .await?;
+    claim_executor(db, alliance_id, corporation_id).await?;
+    Ok(corporation)
+}
+
+/// The first corporation in an alliance without an executor runs it.
+pub(crate) async fn claim_executor<C: ConnectionTrait>(db: &C, alliance_id: i32, corporation_id: i32) -> Result<(), DbErr> {
+    Alliance::update_many()
+        .col_expr(alliance::Column::ExecutorId, sea_query::Expr::value(corporation_id))
+        .filter(alliance::Column::Id.eq(alliance_id))
+        .filter(alliance::Column::ExecutorId.is_null())
+        .exec(db)
+        .await?;
+    Ok(())
 }
 
 pub async fn new_member(
     db: &DatabaseConnection,
     name: &str,
     corporation_id: i32
-) -> Result<InsertResult<member::ActiveModel>, DbErr> {
+) -> Result<InsertResult<member::ActiveModel>, Error> {
     let member = member::ActiveModel {
         name: ActiveValue::Set(name.to_owned()),
         corporation_id: ActiveValue::Set(corporation_id),
         ..Default::default()
     };
-    Member::insert(member).exec(db).await
+    Member::insert(member).exec(db).await.context("member", name)
 }
 
 pub async fn new_capsuleer(
@@ -55,14 +93,14 @@ pub async fn new_capsuleer(
     name: &str,
     member_id: i32,
     corporation_id: i32
-) -> Result<InsertResult<capsuleer::ActiveModel>, DbErr> {
+) -> Result<InsertResult<capsuleer::ActiveModel>, Error> {
     let capsuleer = capsuleer::ActiveModel {
         name: ActiveValue::Set(name.to_owned()),
         member_id: ActiveValue::Set(member_id),
         corporation_id: ActiveValue::Set(corporation_id),
         ..Default::default()
     };
-    Capsuleer::insert(capsuleer).exec(db).await
+    Capsuleer::insert(capsuleer).exec(db).await.context("capsuleer", name)
 }
 
 pub async fn new_skill(
@@ -72,7 +110,7 @@ pub async fn new_skill(
     advanced: i32,
     expert: i32,
     capsuleer_id: i32
-) -> Result<InsertResult<skill::ActiveModel>, DbErr> {
+) -> Result<InsertResult<skill::ActiveModel>, Error> {
     let skill = skill::ActiveModel {
         name: ActiveValue::Set(name.to_owned()),
         basic: ActiveValue::Set(basic),
@@ -81,7 +119,7 @@ pub async fn new_skill(
         capsuleer_id: ActiveValue::Set(capsuleer_id),
         ..Default::default()
     };
-    Skill::insert(skill).exec(db).await
+    Skill::insert(skill).exec(db).await.context("skill", name)
 }
 
 pub async fn new_problem(
@@ -91,7 +129,7 @@ pub async fn new_problem(
     member_id: i32,
     corporation_id: i32,
     alliance_id: Option<i32>
-) -> Result<InsertResult<problem::ActiveModel>, DbErr> {
+) -> Result<InsertResult<problem::ActiveModel>, Error> {
     let problem = problem::ActiveModel {
         name: ActiveValue::Set(name.to_owned()),
         constraint: ActiveValue::Set(constraint),
@@ -100,7 +138,7 @@ pub async fn new_problem(
         alliance_id: ActiveValue::Set(alliance_id),
         ..Default::default()
     };
-    Problem::insert(problem).exec(db).await
+    Problem::insert(problem).exec(db).await.context("problem", name)
 }
 
 pub async fn new_outpost(
@@ -111,7 +149,7 @@ pub async fn new_outpost(
     arrays: i32,
     capsuleer_id: i32,
     problem_id: Option<i32>
-) -> Result<InsertResult<outpost::ActiveModel>, DbErr> {
+) -> Result<InsertResult<outpost::ActiveModel>, Error> {
     let outpost = outpost::ActiveModel {
         name: ActiveValue::Set(name.to_owned()),
         system: ActiveValue::Set(system.to_owned()),
@@ -121,5 +159,110 @@ pub async fn new_outpost(
         problem_id: ActiveValue::Set(problem_id),
         ..Default::default()
     };
-    Outpost::insert(outpost).exec(db).await
-}
\ No newline at end of file
+    Outpost::insert(outpost).exec(db).await.context("outpost", name)
+}
+
+/// Keep the prices a source quoted at the moment, so a later solve can be run against them again.
+pub async fn new_price_snapshot(
+    db: &DatabaseConnection,
+    corporation_id: i32,
+    source: &str,
+    prices: Json,
+    taken_at: DateTimeUtc
+) -> Result<price_snapshot::Model, Error> {
+    let snapshot = price_snapshot::ActiveModel {
+        corporation_id: ActiveValue::Set(corporation_id),
+        source: ActiveValue::Set(source.to_owned()),
+        prices: ActiveValue::Set(prices),
+        taken_at: ActiveValue::Set(taken_at),
+        ..Default::default()
+    };
+    snapshot.insert(db).await.context("price_snapshot", source)
+}
+
+async fn set_corporations_active(txn: &DatabaseTransaction, condition: Condition, active: bool) -> Result<(), DbErr> {
+    Corporation::update_many()
+        .col_expr(corporation::Column::Active, sea_query::Expr::value(active))
+        .filter(condition.clone())
+        .exec(txn)
+        .await?;
+    let corporations = sea_query::Query::select()
+        .column(corporation::Column::Id)
+        .from(corporation::Entity)
+        .cond_where(condition)
+        .to_owned();
+    set_members_active(txn, Condition::all().add(member::Column::CorporationId.in_subquery(corporations.clone())), active).await?;
+    set_problems_active(txn, Condition::all().add(problem::Column::CorporationId.in_subquery(corporations)), active).await
+}
+
+async fn set_members_active(txn: &DatabaseTransaction, condition: Condition, active: bool) -> Result<(), DbErr> {
+    Member::update_many()
+        .col_expr(member::Column::Active, sea_query::Expr::value(active))
+        .filter(condition.clone())
+        .exec(txn)
+        .await?;
+    let members = sea_query::Query::select()
+        .column(member::Column::Id)
+        .from(member::Entity)
+        .cond_where(condition)
+        .to_owned();
+    set_capsuleers_active(txn, Condition::all().add(capsuleer::Column::MemberId.in_subquery(members)), active).await
+}
+
+async fn set_capsuleers_active(txn: &DatabaseTransaction, condition: Condition, active: bool) -> Result<(), DbErr> {
+    Capsuleer::update_many()
+        .col_expr(capsuleer::Column::Active, sea_query::Expr::value(active))
+        .filter(condition.clone())
+        .exec(txn)
+        .await?;
+    let capsuleers = sea_query::Query::select()
+        .column(capsuleer::Column::Id)
+        .from(capsuleer::Entity)
+        .cond_where(condition)
+        .to_owned();
+    let mut outposts = Outpost::update_many()
+        .col_expr(outpost::Column::Active, sea_query::Expr::value(active))
+        .filter(outpost::Column::CapsuleerId.in_subquery(capsuleers.clone()));
+    if !active {
+        outposts = outposts.col_expr(outpost::Column::ProblemId, sea_query::Expr::value(Option::<i32>::None));
+    }
+    outposts.exec(txn).await?;
+    if !active {
+        let outposts = sea_query::Query::select()
+            .column(outpost::Column::Id)
+            .from(outpost::Entity)
+            .and_where(outpost::Column::CapsuleerId.in_subquery(capsuleers))
+            .to_owned();
+        OutpostProblem::delete_many()
+            .filter(outpost_problem::Column::OutpostId.in_subquery(outposts))
+            .exec(txn)
+            .await?;
+    }
+    Ok(())
+}
+
+async fn set_problems_active(txn: &DatabaseTransaction, condition: Condition, active: bool) -> Result<(), DbErr> {
+    Problem::update_many()
+        .col_expr(problem::Column::Active, sea_query::Expr::value(active))
+        .filter(condition.clone())
+        .exec(txn)
+        .await?;
+    // Released outposts and their links are not restored, they may have joined another problem since.
+    if !active {
+        let problems = sea_query::Query::select()
+            .column(problem::Column::Id)
+            .from(problem::Entity)
+            .cond_where(condition)
+            .to_owned();
+        Outpost::update_many()
+            .col_expr(outpost::Column::ProblemId, sea_query::Expr::value(Option::<i32>::None))
+            .filter(outpost::Column::ProblemId.in_subquery(problems.clone()))
+            .exec(txn)
+            .await?;
+        OutpostProblem::delete_many()
+            .filter(outpost_problem::Column::ProblemId.in_subquery(problems))
+            .exec(txn)
+            .await?;
+    }
+    Ok(())
+}
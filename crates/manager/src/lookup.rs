@@ -0,0 +1,19 @@
+// src/lookup.rs
+
+use sea_orm::*;
+
+use crate::Error;
+
+/// The only row the query selects, an error when it selects several.
+pub(crate) async fn only<E: EntityTrait>(
+    select: Select<E>,
+    entity: &'static str,
+    name: &str,
+    db: &DatabaseConnection
+) -> Result<Option<E::Model>, Error> {
+    let mut models = select.limit(2).all(db).await?;
+    match models.len() {
+        0 | 1 => Ok(models.pop()),
+        _ => Err(Error::Ambiguous { entity, key: name.to_owned() }),
+    }
+}
@@ -0,0 +1,64 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250109_000001_create_alliance_table::Alliance;
+use super::m20250109_000002_create_corporation_table::Corporation;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250120_000001_create_guild_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(Guild::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(Guild::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(Guild::GuildId).big_integer().not_null().unique_key())
+                    .col(ColumnDef::new(Guild::CorporationId).integer().not_null())
+                    .col(ColumnDef::new(Guild::AllianceId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-corporation-guild_id")
+                            .from(Guild::Table, Guild::CorporationId)
+                            .to(Corporation::Table, Corporation::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-alliance-guild_id")
+                            .from(Guild::Table, Guild::AllianceId)
+                            .to(Alliance::Table, Alliance::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(Guild::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum Guild {
+    Table,
+    Id,
+    GuildId,
+    CorporationId,
+    AllianceId,
+}
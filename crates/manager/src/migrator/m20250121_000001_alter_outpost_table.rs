@@ -0,0 +1,42 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250121_000001_alter_outpost_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Outpost::Table)
+                    .add_column(ColumnDef::new(Outpost::Active).boolean().not_null().default(true))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Outpost::Table)
+                    .drop_column(Outpost::Active)
+                    .to_owned()
+            )
+            .await
+    }
+}
+
+
+
+#[derive(DeriveIden)]
+pub enum Outpost {
+    Table,
+    Active,
+}
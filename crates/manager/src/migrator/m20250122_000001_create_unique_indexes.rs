@@ -0,0 +1,155 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250122_000001_create_unique_indexes"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, table, columns, active) in indexes() {
+            dedupe(manager, &table, &columns, active.as_ref()).await?;
+            let mut index = Index::create().name(name).table(table).unique().to_owned();
+            for column in columns {
+                index.col(column);
+            }
+            if let Some(active) = active {
+                index.and_where(Expr::col(active).eq(true));
+            }
+            manager.create_index(index).await?;
+        }
+        Ok(())
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        for (name, table, _, _) in indexes() {
+            manager
+                .drop_index(Index::drop().name(name).table(table).to_owned())
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+/// Names are unique within their owner, alliances and corporations across eve-anchor.
+/// Retired members, capsuleers and problems and deleted outposts keep their names, so only active ones have to be unique.
+fn indexes() -> Vec<(&'static str, DynIden, Vec<DynIden>, Option<DynIden>)> {
+    vec![
+        ("idx-alliance-name", Alliance::Table.into_iden(), vec![Alliance::Name.into_iden()], None),
+        ("idx-corporation-name", Corporation::Table.into_iden(), vec![Corporation::Name.into_iden()], None),
+        (
+            "idx-member-corporation_id-name",
+            Member::Table.into_iden(),
+            vec![Member::CorporationId.into_iden(), Member::Name.into_iden()],
+            Some(Member::Active.into_iden()),
+        ),
+        (
+            "idx-capsuleer-corporation_id-name",
+            Capsuleer::Table.into_iden(),
+            vec![Capsuleer::CorporationId.into_iden(), Capsuleer::Name.into_iden()],
+            Some(Capsuleer::Active.into_iden()),
+        ),
+        (
+            "idx-problem-corporation_id-name",
+            Problem::Table.into_iden(),
+            vec![Problem::CorporationId.into_iden(), Problem::Name.into_iden()],
+            Some(Problem::Active.into_iden()),
+        ),
+        (
+            "idx-outpost-capsuleer_id-name",
+            Outpost::Table.into_iden(),
+            vec![Outpost::CapsuleerId.into_iden(), Outpost::Name.into_iden()],
+            Some(Outpost::Active.into_iden()),
+        ),
+        (
+            "idx-skill-capsuleer_id-name",
+            Skill::Table.into_iden(),
+            vec![Skill::CapsuleerId.into_iden(), Skill::Name.into_iden()],
+            None,
+        ),
+    ]
+}
+
+/// Rows registered before names were unique may share one, every copy but the oldest gets its id appended
+/// so the index can be built without losing the rows or what refers to them.
+async fn dedupe(manager: &SchemaManager<'_>, table: &DynIden, columns: &[DynIden], active: Option<&DynIden>) -> Result<(), DbErr> {
+    let older = Alias::new("older");
+    let id = Alias::new("id");
+    let name = columns.last().expect("an index has a name column");
+    let mut duplicate = Query::select()
+        .expr(Expr::val(1))
+        .from_as(table.clone(), older.clone())
+        .and_where(Expr::col((older.clone(), id.clone())).lt(Expr::col((table.clone(), id.clone()))))
+        .to_owned();
+    for column in columns {
+        duplicate.and_where(Expr::col((older.clone(), column.clone())).equals((table.clone(), column.clone())));
+    }
+    let mut update = Query::update()
+        .table(table.clone())
+        .value(
+            name.clone(),
+            Func::cust(Alias::new("CONCAT")).args([Expr::col(name.clone()).into(), Expr::val(" #").into(), Expr::col(id.clone()).into()]),
+        )
+        .to_owned();
+    if let Some(active) = active {
+        duplicate.and_where(Expr::col((older, active.clone())).eq(true));
+        update.and_where(Expr::col((table.clone(), active.clone())).eq(true));
+    }
+    manager.exec_stmt(update.and_where(Expr::exists(duplicate)).to_owned()).await
+}
+
+
+#[derive(DeriveIden)]
+pub enum Alliance {
+    Table,
+    Name,
+}
+
+#[derive(DeriveIden)]
+pub enum Corporation {
+    Table,
+    Name,
+}
+
+#[derive(DeriveIden)]
+pub enum Member {
+    Table,
+    Name,
+    CorporationId,
+    Active,
+}
+
+#[derive(DeriveIden)]
+pub enum Capsuleer {
+    Table,
+    Name,
+    CorporationId,
+    Active,
+}
+
+#[derive(DeriveIden)]
+pub enum Problem {
+    Table,
+    Name,
+    CorporationId,
+    Active,
+}
+
+#[derive(DeriveIden)]
+pub enum Outpost {
+    Table,
+    Name,
+    CapsuleerId,
+    Active,
+}
+
+#[derive(DeriveIden)]
+pub enum Skill {
+    Table,
+    Name,
+    CapsuleerId,
+}
@@ -0,0 +1,82 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250109_000002_create_corporation_table::Corporation;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250123_000001_create_audit_event_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(AuditEvent::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(AuditEvent::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(AuditEvent::CorporationId).integer().not_null())
+                    .col(ColumnDef::new(AuditEvent::Actor).string().not_null())
+                    .col(ColumnDef::new(AuditEvent::Action).string().not_null())
+                    .col(ColumnDef::new(AuditEvent::Entity).string().not_null())
+                    .col(ColumnDef::new(AuditEvent::EntityId).integer().not_null())
+                    .col(ColumnDef::new(AuditEvent::Before).json_binary())
+                    .col(ColumnDef::new(AuditEvent::After).json_binary())
+                    .col(
+                        ColumnDef::new(AuditEvent::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-corporation-audit_event_id")
+                            .from(AuditEvent::Table, AuditEvent::CorporationId)
+                            .to(Corporation::Table, Corporation::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-audit_event-corporation_id-created_at")
+                    .table(AuditEvent::Table)
+                    .col(AuditEvent::CorporationId)
+                    .col(AuditEvent::CreatedAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(AuditEvent::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum AuditEvent {
+    Table,
+    Id,
+    CorporationId,
+    Actor,
+    Action,
+    Entity,
+    EntityId,
+    Before,
+    After,
+    CreatedAt,
+}
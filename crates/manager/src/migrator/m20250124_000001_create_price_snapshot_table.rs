@@ -0,0 +1,74 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250109_000002_create_corporation_table::Corporation;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250124_000001_create_price_snapshot_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceSnapshot::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PriceSnapshot::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PriceSnapshot::CorporationId).integer().not_null())
+                    .col(ColumnDef::new(PriceSnapshot::Source).string().not_null())
+                    .col(ColumnDef::new(PriceSnapshot::Prices).json_binary().not_null())
+                    .col(
+                        ColumnDef::new(PriceSnapshot::TakenAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-corporation-price_snapshot_id")
+                            .from(PriceSnapshot::Table, PriceSnapshot::CorporationId)
+                            .to(Corporation::Table, Corporation::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-price_snapshot-corporation_id-taken_at")
+                    .table(PriceSnapshot::Table)
+                    .col(PriceSnapshot::CorporationId)
+                    .col(PriceSnapshot::TakenAt)
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PriceSnapshot::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum PriceSnapshot {
+    Table,
+    Id,
+    CorporationId,
+    Source,
+    Prices,
+    TakenAt,
+}
@@ -0,0 +1,75 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250109_000002_create_corporation_table::Corporation;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250125_000001_create_price_book_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceBook::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PriceBook::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PriceBook::CorporationId).integer().not_null())
+                    .col(ColumnDef::new(PriceBook::Version).integer().not_null())
+                    .col(ColumnDef::new(PriceBook::Actor).string().not_null())
+                    .col(
+                        ColumnDef::new(PriceBook::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-corporation-price_book_id")
+                            .from(PriceBook::Table, PriceBook::CorporationId)
+                            .to(Corporation::Table, Corporation::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-price_book-corporation_id-version")
+                    .table(PriceBook::Table)
+                    .col(PriceBook::CorporationId)
+                    .col(PriceBook::Version)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PriceBook::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum PriceBook {
+    Table,
+    Id,
+    CorporationId,
+    Version,
+    Actor,
+    CreatedAt,
+}
@@ -0,0 +1,69 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250125_000001_create_price_book_table::PriceBook;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250125_000002_create_price_book_entry_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(PriceBookEntry::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(PriceBookEntry::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(PriceBookEntry::PriceBookId).integer().not_null())
+                    .col(ColumnDef::new(PriceBookEntry::ResourceTypeId).big_integer().not_null())
+                    .col(ColumnDef::new(PriceBookEntry::Price).double().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-price_book-price_book_entry_id")
+                            .from(PriceBookEntry::Table, PriceBookEntry::PriceBookId)
+                            .to(PriceBook::Table, PriceBook::Id)
+                            .on_delete(ForeignKeyAction::Cascade),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-price_book_entry-price_book_id-resource_type_id")
+                    .table(PriceBookEntry::Table)
+                    .col(PriceBookEntry::PriceBookId)
+                    .col(PriceBookEntry::ResourceTypeId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(PriceBookEntry::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum PriceBookEntry {
+    Table,
+    Id,
+    PriceBookId,
+    ResourceTypeId,
+    Price,
+}
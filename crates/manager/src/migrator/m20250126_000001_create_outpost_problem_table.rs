@@ -0,0 +1,73 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250110_000001_create_problem_table::Problem;
+use super::m20250110_000002_create_outpost_table::Outpost;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250126_000001_create_outpost_problem_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(OutpostProblem::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(OutpostProblem::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(OutpostProblem::OutpostId).integer().not_null())
+                    .col(ColumnDef::new(OutpostProblem::ProblemId).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-outpost-outpost_problem_id")
+                            .from(OutpostProblem::Table, OutpostProblem::OutpostId)
+                            .to(Outpost::Table, Outpost::Id),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-problem-outpost_problem_id")
+                            .from(OutpostProblem::Table, OutpostProblem::ProblemId)
+                            .to(Problem::Table, Problem::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-outpost_problem-outpost_id-problem_id")
+                    .table(OutpostProblem::Table)
+                    .col(OutpostProblem::OutpostId)
+                    .col(OutpostProblem::ProblemId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(OutpostProblem::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum OutpostProblem {
+    Table,
+    Id,
+    OutpostId,
+    ProblemId,
+}
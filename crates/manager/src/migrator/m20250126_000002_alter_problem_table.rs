@@ -0,0 +1,45 @@
+use sea_orm_migration::prelude::*;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250126_000002_alter_problem_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Problem::Table)
+                    .add_column(ColumnDef::new(Problem::Priority).integer().not_null().default(1))
+                    .add_column(ColumnDef::new(Problem::Deadline).timestamp_with_time_zone())
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Problem::Table)
+                    .drop_column(Problem::Priority)
+                    .drop_column(Problem::Deadline)
+                    .to_owned()
+            )
+            .await
+    }
+}
+
+
+
+#[derive(DeriveIden)]
+pub enum Problem {
+    Table,
+    Priority,
+    Deadline,
+}
@@ -0,0 +1,71 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250110_000002_create_outpost_table::Outpost;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250127_000001_create_array_placement_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(ArrayPlacement::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(ArrayPlacement::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(ArrayPlacement::OutpostId).integer().not_null())
+                    .col(ColumnDef::new(ArrayPlacement::PlanetId).big_integer().not_null())
+                    .col(ColumnDef::new(ArrayPlacement::ResourceTypeId).big_integer().not_null())
+                    .col(ColumnDef::new(ArrayPlacement::Count).integer().not_null())
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-outpost-array_placement_id")
+                            .from(ArrayPlacement::Table, ArrayPlacement::OutpostId)
+                            .to(Outpost::Table, Outpost::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+        manager
+            .create_index(
+                Index::create()
+                    .name("idx-array_placement-outpost_id-planet_id-resource_type_id")
+                    .table(ArrayPlacement::Table)
+                    .col(ArrayPlacement::OutpostId)
+                    .col(ArrayPlacement::PlanetId)
+                    .col(ArrayPlacement::ResourceTypeId)
+                    .unique()
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(ArrayPlacement::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum ArrayPlacement {
+    Table,
+    Id,
+    OutpostId,
+    PlanetId,
+    ResourceTypeId,
+    Count,
+}
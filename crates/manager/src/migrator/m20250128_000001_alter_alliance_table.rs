@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250109_000002_create_corporation_table::Corporation;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250128_000001_alter_alliance_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alliance::Table)
+                    .add_column(ColumnDef::new(Alliance::ExecutorId).integer())
+                    .add_foreign_key(
+                        TableForeignKey::new()
+                            .name("fk-alliance-executor_id")
+                            .from_tbl(Alliance::Table)
+                            .from_col(Alliance::ExecutorId)
+                            .to_tbl(Corporation::Table)
+                            .to_col(Corporation::Id),
+                    )
+                    .to_owned(),
+            )
+            .await?;
+
+        // Alliances founded before executors existed are run by their oldest corporation.
+        let oldest = Query::select()
+            .expr(Expr::col((Corporation::Table, Corporation::Id)).min())
+            .from(Corporation::Table)
+            .and_where(Expr::col((Corporation::Table, Corporation::AllianceId)).equals((Alliance::Table, Alliance::Id)))
+            .to_owned();
+        manager
+            .exec_stmt(
+                Query::update()
+                    .table(Alliance::Table)
+                    .value(Alliance::ExecutorId, SimpleExpr::SubQuery(None, Box::new(oldest.into_sub_query_statement())))
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .alter_table(
+                Table::alter()
+                    .table(Alliance::Table)
+                    .drop_foreign_key(Alias::new("fk-alliance-executor_id"))
+                    .drop_column(Alliance::ExecutorId)
+                    .to_owned()
+            )
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum Alliance {
+    Table,
+    Id,
+    ExecutorId,
+}
@@ -0,0 +1,68 @@
+use sea_orm_migration::prelude::*;
+
+use super::m20250109_000002_create_corporation_table::Corporation;
+
+pub struct Migration;
+
+impl MigrationName for Migration {
+    fn name(&self) -> &str {
+        "m20250128_000002_create_corporation_claim_table"
+    }
+}
+
+#[async_trait::async_trait]
+impl MigrationTrait for Migration {
+    async fn up(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .create_table(
+                Table::create()
+                    .table(CorporationClaim::Table)
+                    .if_not_exists()
+                    .col(
+                        ColumnDef::new(CorporationClaim::Id)
+                            .integer()
+                            .not_null()
+                            .auto_increment()
+                            .primary_key(),
+                    )
+                    .col(ColumnDef::new(CorporationClaim::CorporationId).integer().not_null())
+                    .col(
+                        ColumnDef::new(CorporationClaim::Token)
+                            .string()
+                            .not_null()
+                            .unique_key()
+                            .default(Expr::cust("gen_random_uuid()::text")),
+                    )
+                    .col(
+                        ColumnDef::new(CorporationClaim::CreatedAt)
+                            .timestamp_with_time_zone()
+                            .not_null()
+                            .default(Expr::current_timestamp()),
+                    )
+                    .foreign_key(
+                        ForeignKey::create()
+                            .name("fk-corporation-corporation_claim_id")
+                            .from(CorporationClaim::Table, CorporationClaim::CorporationId)
+                            .to(Corporation::Table, Corporation::Id),
+                    )
+                    .to_owned(),
+            )
+            .await
+    }
+
+    async fn down(&self, manager: &SchemaManager) -> Result<(), DbErr> {
+        manager
+            .drop_table(Table::drop().table(CorporationClaim::Table).to_owned())
+            .await
+    }
+}
+
+
+#[derive(DeriveIden)]
+pub enum CorporationClaim {
+    Table,
+    Id,
+    CorporationId,
+    Token,
+    CreatedAt,
+}
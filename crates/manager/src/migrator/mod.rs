@@ -10,6 +10,19 @@ mod m20250109_000004_alter_capsuleer_table;
 mod m20250110_000001_create_problem_table;
 mod m20250110_000002_create_outpost_table;
 mod m20250114_000001_alter_problem_table;
+mod m20250120_000001_create_guild_table;
+mod m20250121_000001_alter_outpost_table;
+mod m20250122_000001_create_unique_indexes;
+mod m20250123_000001_create_audit_event_table;
+mod m20250124_000001_create_price_snapshot_table;
+mod m20250125_000001_create_price_book_table;
+mod m20250125_000002_create_price_book_entry_table;
+mod m20250125_000003_alter_problem_table;
+mod m20250126_000001_create_outpost_problem_table;
+mod m20250126_000002_alter_problem_table;
+mod m20250127_000001_create_array_placement_table;
+mod m20250128_000001_alter_alliance_table;
+mod m20250128_000002_create_corporation_claim_table;
 
 pub struct Migrator;
 
@@ -27,6 +40,19 @@ impl MigratorTrait for Migrator {
             Box::new(m20250110_000001_create_problem_table::Migration),
             Box::new(m20250110_000002_create_outpost_table::Migration),
             Box::new(m20250114_000001_alter_problem_table::Migration),
+            Box::new(m20250120_000001_create_guild_table::Migration),
+            Box::new(m20250121_000001_alter_outpost_table::Migration),
+            Box::new(m20250122_000001_create_unique_indexes::Migration),
+            Box::new(m20250123_000001_create_audit_event_table::Migration),
+            Box::new(m20250124_000001_create_price_snapshot_table::Migration),
+            Box::new(m20250125_000001_create_price_book_table::Migration),
+            Box::new(m20250125_000002_create_price_book_entry_table::Migration),
+            Box::new(m20250125_000003_alter_problem_table::Migration),
+            Box::new(m20250126_000001_create_outpost_problem_table::Migration),
+            Box::new(m20250126_000002_alter_problem_table::Migration),
+            Box::new(m20250127_000001_create_array_placement_table::Migration),
+            Box::new(m20250128_000001_alter_alliance_table::Migration),
+            Box::new(m20250128_000002_create_corporation_claim_table::Migration),
         ]
     }
 }
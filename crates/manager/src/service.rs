@@ -0,0 +1,1077 @@
+// src/service.rs
+
+use std::collections::BTreeMap;
+
+use sea_orm::*;
+use sea_orm::prelude::DateTimeUtc;
+
+use crate::audit::{Action, Actor, Trail};
+use crate::entities::*;
+use crate::entities::prelude::*;
+use crate::error::Context;
+use crate::Error;
+
+/// An outpost about to be anchored.
+#[derive(Clone, Debug)]
+pub struct NewOutpost<'a> {
+    pub name: &'a str,
+    pub system: &'a str,
+    pub planets: i32,
+    pub arrays: i32,
+}
+
+/// Skill levels a capsuleer starts with.
+#[derive(Clone, Debug)]
+pub struct NewSkill<'a> {
+    pub name: &'a str,
+    pub basic: i32,
+    pub advanced: i32,
+    pub expert: i32,
+}
+
+/// Register a member of the corporation, the existing member when the name is taken.
+pub async fn register_member(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    member_name: &str
+) -> Result<member::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let member = member(&txn, trail, member_name).await?;
+    txn.commit().await?;
+    Ok(member)
+}
+
+/// Register a capsuleer for the member along with their skills, adding the member when they are new.
+pub async fn register_capsuleer_with_skills(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    member_name: &str,
+    capsuleer_name: &str,
+    skills: &[NewSkill<'_>]
+) -> Result<capsuleer::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    if find_capsuleer(&txn, corporation_id, capsuleer_name).await?.is_some_and(|capsuleer| capsuleer.active) {
+        return Err(Error::Duplicate { entity: "capsuleer", key: capsuleer_name.to_owned() });
+    }
+    let member = member(&txn, trail, member_name).await?;
+    let capsuleer = new_capsuleer(&txn, trail, member.id, capsuleer_name).await?;
+    for skill in skills {
+        let skill = skill::ActiveModel {
+            name: ActiveValue::Set(skill.name.to_owned()),
+            basic: ActiveValue::Set(skill.basic),
+            advanced: ActiveValue::Set(skill.advanced),
+            expert: ActiveValue::Set(skill.expert),
+            capsuleer_id: ActiveValue::Set(capsuleer.id),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("skill", skill.name)?;
+        trail.record(&txn, Action::Create, "skill", skill.id, None, Some(&skill)).await?;
+    }
+    txn.commit().await?;
+    Ok(capsuleer)
+}
+
+/// Register an outpost anchored by the capsuleer, adding the member and capsuleer when they are new.
+pub async fn register_outpost_for_user(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    member_name: &str,
+    capsuleer_name: &str,
+    outpost: NewOutpost<'_>
+) -> Result<outpost::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let capsuleer = match find_capsuleer(&txn, corporation_id, capsuleer_name).await? {
+        Some(capsuleer) if !capsuleer.active => {
+            return Err(Error::Inactive { entity: "capsuleer", key: capsuleer.name });
+        }
+        Some(capsuleer) => capsuleer,
+        None => {
+            let member = member(&txn, trail, member_name).await?;
+            new_capsuleer(&txn, trail, member.id, capsuleer_name).await?
+        }
+    };
+    let existing = Outpost::find()
+        .filter(outpost::Column::CapsuleerId.eq(capsuleer.id))
+        .filter(outpost::Column::Name.eq(outpost.name))
+        .filter(outpost::Column::Active.eq(true))
+        .one(&txn)
+        .await?;
+    if existing.is_some() {
+        return Err(Error::Duplicate { entity: "outpost", key: outpost.name.to_owned() });
+    }
+    let outpost = outpost::ActiveModel {
+        name: ActiveValue::Set(outpost.name.to_owned()),
+        system: ActiveValue::Set(outpost.system.to_owned()),
+        planets: ActiveValue::Set(outpost.planets),
+        arrays: ActiveValue::Set(outpost.arrays),
+        capsuleer_id: ActiveValue::Set(capsuleer.id),
+        problem_id: ActiveValue::Set(None),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("outpost", outpost.name)?;
+    trail.record(&txn, Action::Create, "outpost", outpost.id, None, Some(&outpost)).await?;
+    txn.commit().await?;
+    Ok(outpost)
+}
+
+/// Register a problem owned by the member, adding the member when they are new.
+pub async fn register_problem_for_user(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    alliance_id: Option<i32>,
+    member_name: &str,
+    problem_name: &str,
+    constraint: Vec<u8>
+) -> Result<problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let existing = Problem::find()
+        .filter(problem::Column::CorporationId.eq(corporation_id))
+        .filter(problem::Column::Name.eq(problem_name))
+        .filter(problem::Column::Active.eq(true))
+        .one(&txn)
+        .await?;
+    if existing.is_some() {
+        return Err(Error::Duplicate { entity: "problem", key: problem_name.to_owned() });
+    }
+    let member = member(&txn, trail, member_name).await?;
+    let problem = problem::ActiveModel {
+        name: ActiveValue::Set(problem_name.to_owned()),
+        constraint: ActiveValue::Set(constraint),
+        member_id: ActiveValue::Set(member.id),
+        corporation_id: ActiveValue::Set(corporation_id),
+        alliance_id: ActiveValue::Set(alliance_id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("problem", problem_name)?;
+    trail.record(&txn, Action::Create, "problem", problem.id, None, Some(&problem)).await?;
+    txn.commit().await?;
+    Ok(problem)
+}
+
+/// Move every active outpost of the member onto the problem.
+pub async fn assign_member_outposts(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    member_id: i32,
+    problem_id: i32
+) -> Result<Vec<outpost::Model>, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    find_problem(&txn, corporation_id, problem_id).await?;
+    let outposts = Outpost::find()
+        .inner_join(Capsuleer)
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .filter(capsuleer::Column::MemberId.eq(member_id))
+        .filter(outpost::Column::Active.eq(true))
+        .all(&txn)
+        .await?;
+    let mut assigned = Vec::with_capacity(outposts.len());
+    for before in outposts {
+        let mut outpost: outpost::ActiveModel = before.clone().into();
+        outpost.problem_id = ActiveValue::Set(Some(problem_id));
+        let after = outpost.update(&txn).await?;
+        trail.record(&txn, Action::Assign, "outpost", after.id, Some(&before), Some(&after)).await?;
+        assigned.push(after);
+    }
+    txn.commit().await?;
+    Ok(assigned)
+}
+
+/// Rename or resize an outpost.
+pub async fn edit_outpost(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    outpost_id: i32,
+    outpost: NewOutpost<'_>
+) -> Result<outpost::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_outpost(&txn, corporation_id, outpost_id).await?;
+    let mut model: outpost::ActiveModel = before.clone().into();
+    model.name = ActiveValue::Set(outpost.name.to_owned());
+    model.system = ActiveValue::Set(outpost.system.to_owned());
+    model.planets = ActiveValue::Set(outpost.planets);
+    model.arrays = ActiveValue::Set(outpost.arrays);
+    let after = model.update(&txn).await.context("outpost", outpost.name)?;
+    trail.record(&txn, Action::Update, "outpost", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Rename a problem or replace its material list.
+pub async fn edit_problem(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    problem_id: i32,
+    name: &str,
+    constraint: Vec<u8>
+) -> Result<problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_problem(&txn, corporation_id, problem_id).await?;
+    let mut model: problem::ActiveModel = before.clone().into();
+    model.name = ActiveValue::Set(name.to_owned());
+    model.constraint = ActiveValue::Set(constraint);
+    let after = model.update(&txn).await.context("problem", name)?;
+    trail.record(&txn, Action::Update, "problem", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Soft-delete an outpost, it leaves its problems and stays in the history.
+pub async fn delete_outpost(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    outpost_id: i32
+) -> Result<outpost::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_outpost(&txn, corporation_id, outpost_id).await?;
+    let mut after = before.clone();
+    after.deactivate();
+    let after = outpost::ActiveModel::from(after).reset_all().update(&txn).await?;
+    OutpostProblem::delete_many()
+        .filter(outpost_problem::Column::OutpostId.eq(outpost_id))
+        .exec(&txn)
+        .await?;
+    trail.record(&txn, Action::Delete, "outpost", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Publish the next version of the corporation's price book, the latest prices with the changes applied.
+/// A change without a price takes the resource out of the book.
+pub async fn set_prices(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    changes: &[(i64, Option<f64>)]
+) -> Result<(price_book::Model, Vec<price_book_entry::Model>), Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let latest = PriceBook::find()
+        .filter(price_book::Column::CorporationId.eq(corporation_id))
+        .order_by_desc(price_book::Column::Version)
+        .one(&txn)
+        .await?;
+    let before: BTreeMap<i64, f64> = match &latest {
+        Some(book) => PriceBookEntry::find()
+            .filter(price_book_entry::Column::PriceBookId.eq(book.id))
+            .all(&txn)
+            .await?
+            .into_iter()
+            .map(|entry| (entry.resource_type_id, entry.price))
+            .collect(),
+        None => BTreeMap::new(),
+    };
+    let mut after = before.clone();
+    for (resource_type_id, price) in changes {
+        match price {
+            Some(price) => after.insert(*resource_type_id, *price),
+            None => after.remove(resource_type_id),
+        };
+    }
+    let version = latest.as_ref().map_or(1, |book| book.version + 1);
+    let book = price_book::ActiveModel {
+        corporation_id: ActiveValue::Set(corporation_id),
+        version: ActiveValue::Set(version),
+        actor: ActiveValue::Set(actor.to_string()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("price_book", version)?;
+    let mut entries = Vec::with_capacity(after.len());
+    for (resource_type_id, price) in &after {
+        let entry = price_book_entry::ActiveModel {
+            price_book_id: ActiveValue::Set(book.id),
+            resource_type_id: ActiveValue::Set(*resource_type_id),
+            price: ActiveValue::Set(*price),
+            ..Default::default()
+        }
+        .insert(&txn)
+        .await
+        .context("price_book_entry", resource_type_id)?;
+        entries.push(entry);
+    }
+    let action = if latest.is_some() { Action::Update } else { Action::Create };
+    trail.record(&txn, action, "price_book", book.id, latest.is_some().then_some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok((book, entries))
+}
+
+/// Value a problem's materials at the corporation's price book, or at the list's own valuations again.
+pub async fn set_problem_pricing(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    problem_id: i32,
+    corp_prices: bool
+) -> Result<problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_problem(&txn, corporation_id, problem_id).await?;
+    let mut model: problem::ActiveModel = before.clone().into();
+    model.corp_prices = ActiveValue::Set(corp_prices);
+    let after = model.update(&txn).await.context("problem", &before.name)?;
+    trail.record(&txn, Action::Update, "problem", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Let an outpost serve a further problem besides the one it is assigned to.
+pub async fn link_outpost(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    outpost_id: i32,
+    problem_id: i32
+) -> Result<outpost_problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let outpost = find_outpost(&txn, corporation_id, outpost_id).await?;
+    if !outpost.active {
+        return Err(Error::Inactive { entity: "outpost", key: outpost.name });
+    }
+    let problem = find_problem(&txn, corporation_id, problem_id).await?;
+    if !problem.active {
+        return Err(Error::Inactive { entity: "problem", key: problem.name });
+    }
+    let link = outpost_problem::ActiveModel {
+        outpost_id: ActiveValue::Set(outpost_id),
+        problem_id: ActiveValue::Set(problem_id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("outpost_problem", format!("{outpost_id}-{problem_id}"))?;
+    trail.record(&txn, Action::Assign, "outpost_problem", link.id, None, Some(&link)).await?;
+    txn.commit().await?;
+    Ok(link)
+}
+
+/// Stop an outpost serving a problem it was linked to.
+pub async fn unlink_outpost(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    outpost_id: i32,
+    problem_id: i32
+) -> Result<outpost_problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    find_outpost(&txn, corporation_id, outpost_id).await?;
+    let link = OutpostProblem::find()
+        .filter(outpost_problem::Column::OutpostId.eq(outpost_id))
+        .filter(outpost_problem::Column::ProblemId.eq(problem_id))
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "outpost_problem", key: format!("{outpost_id}-{problem_id}") })?;
+    OutpostProblem::delete_by_id(link.id).exec(&txn).await?;
+    trail.record(&txn, Action::Delete, "outpost_problem", link.id, Some(&link), None).await?;
+    txn.commit().await?;
+    Ok(link)
+}
+
+/// Set how urgent a problem is when it shares outposts with others.
+pub async fn schedule_problem(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    problem_id: i32,
+    priority: i32,
+    deadline: Option<DateTimeUtc>
+) -> Result<problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_problem(&txn, corporation_id, problem_id).await?;
+    let mut model: problem::ActiveModel = before.clone().into();
+    model.priority = ActiveValue::Set(priority);
+    model.deadline = ActiveValue::Set(deadline);
+    let after = model.update(&txn).await.context("problem", &before.name)?;
+    trail.record(&txn, Action::Update, "problem", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Record how many arrays an outpost has anchored on a resource of a planet, none taking the placement out.
+/// Returns the outpost's layout afterwards.
+pub async fn place_arrays(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    outpost_id: i32,
+    planet_id: i64,
+    resource_type_id: i64,
+    count: i32
+) -> Result<Vec<array_placement::Model>, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    find_outpost(&txn, corporation_id, outpost_id).await?;
+    let before = ArrayPlacement::find()
+        .filter(array_placement::Column::OutpostId.eq(outpost_id))
+        .filter(array_placement::Column::PlanetId.eq(planet_id))
+        .filter(array_placement::Column::ResourceTypeId.eq(resource_type_id))
+        .one(&txn)
+        .await?;
+    let key = format!("{outpost_id}-{planet_id}-{resource_type_id}");
+    match (before, count) {
+        (None, 0) => {},
+        (None, count) => {
+            let placement = array_placement::ActiveModel {
+                outpost_id: ActiveValue::Set(outpost_id),
+                planet_id: ActiveValue::Set(planet_id),
+                resource_type_id: ActiveValue::Set(resource_type_id),
+                count: ActiveValue::Set(count),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .context("array_placement", &key)?;
+            trail.record(&txn, Action::Create, "array_placement", placement.id, None, Some(&placement)).await?;
+        },
+        (Some(before), 0) => {
+            ArrayPlacement::delete_by_id(before.id).exec(&txn).await?;
+            trail.record(&txn, Action::Delete, "array_placement", before.id, Some(&before), None).await?;
+        },
+        (Some(before), count) => {
+            let mut model: array_placement::ActiveModel = before.clone().into();
+            model.count = ActiveValue::Set(count);
+            let after = model.update(&txn).await.context("array_placement", &key)?;
+            trail.record(&txn, Action::Update, "array_placement", after.id, Some(&before), Some(&after)).await?;
+        },
+    }
+    let layout = ArrayPlacement::find()
+        .filter(array_placement::Column::OutpostId.eq(outpost_id))
+        .order_by_asc(array_placement::Column::PlanetId)
+        .all(&txn)
+        .await?;
+    txn.commit().await?;
+    Ok(layout)
+}
+
+/// How long a claim token can be redeemed after it was issued.
+const CLAIM_LIFETIME: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Issue a one-time token another guild can redeem to bind itself to the corporation.
+pub async fn issue_claim(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32
+) -> Result<corporation_claim::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let claim = corporation_claim::ActiveModel {
+        corporation_id: ActiveValue::Set(corporation_id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("corporation_claim", corporation_id)?;
+    trail.record(&txn, Action::Create, "corporation_claim", claim.id, None, Some(&claim)).await?;
+    txn.commit().await?;
+    Ok(claim)
+}
+
+/// Bind a Discord guild to the corporation in the alliance, founding both when neither is registered.
+/// A corporation registered before, or a new one joining an existing alliance, has to be claimed with a token
+/// issued for it or for the alliance's executor. A guild already bound to the corporation needs none.
+pub async fn setup_guild(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    guild_id: i64,
+    alliance_name: &str,
+    corporation_name: &str,
+    claim: Option<&str>
+) -> Result<guild::Model, Error> {
+    let txn = db.begin().await?;
+    let alliance = Alliance::find()
+        .filter(alliance::Column::Name.eq(alliance_name))
+        .one(&txn)
+        .await?;
+    let corporation = Corporation::find()
+        .filter(corporation::Column::Name.eq(corporation_name))
+        .one(&txn)
+        .await?;
+    let before = Guild::find()
+        .filter(guild::Column::GuildId.eq(guild_id))
+        .one(&txn)
+        .await?;
+    let (corporation, alliance) = match (corporation, alliance) {
+        (Some(corporation), Some(alliance)) if corporation.alliance_id == alliance.id => {
+            if !corporation.active {
+                return Err(Error::Inactive { entity: "corporation", key: corporation.name });
+            }
+            let bound = Guild::find()
+                .filter(guild::Column::CorporationId.eq(corporation.id))
+                .filter(guild::Column::GuildId.ne(guild_id))
+                .one(&txn)
+                .await?;
+            if bound.is_some() {
+                return Err(Error::Forbidden { entity: "corporation", key: corporation.name });
+            }
+            if before.as_ref().map(|guild| guild.corporation_id) != Some(corporation.id) {
+                redeem_claim(&txn, corporation.id, "corporation", corporation_name, claim).await?;
+            }
+            (corporation, alliance)
+        }
+        (Some(_), _) => {
+            return Err(Error::NotFound { entity: "corporation", key: format!("{corporation_name} in {alliance_name}") });
+        }
+        (None, alliance) => {
+            if let Some(alliance) = &alliance {
+                let executor_id = alliance.executor_id
+                    .ok_or(Error::Forbidden { entity: "alliance", key: alliance.name.clone() })?;
+                redeem_claim(&txn, executor_id, "alliance", alliance_name, claim).await?;
+            }
+            let founded = alliance.is_none();
+            let alliance = match alliance {
+                Some(alliance) => alliance,
+                None => alliance::ActiveModel {
+                    name: ActiveValue::Set(alliance_name.to_owned()),
+                    ..Default::default()
+                }
+                .insert(&txn)
+                .await
+                .context("alliance", alliance_name)?,
+            };
+            let corporation = corporation::ActiveModel {
+                name: ActiveValue::Set(corporation_name.to_owned()),
+                alliance_id: ActiveValue::Set(alliance.id),
+                ..Default::default()
+            }
+            .insert(&txn)
+            .await
+            .context("corporation", corporation_name)?;
+            crate::claim_executor(&txn, alliance.id, corporation.id).await?;
+            let trail = Trail { corporation_id: corporation.id, actor };
+            if founded {
+                trail.record(&txn, Action::Create, "alliance", alliance.id, None, Some(&alliance)).await?;
+            }
+            trail.record(&txn, Action::Create, "corporation", corporation.id, None, Some(&corporation)).await?;
+            (corporation, alliance)
+        }
+    };
+    let trail = Trail { corporation_id: corporation.id, actor };
+    let guild = bind(&txn, trail, guild_id, before, alliance.id).await?;
+    txn.commit().await?;
+    Ok(guild)
+}
+
+/// Bind a Discord guild to the corporation and alliance as they are, whoever held it before.
+pub async fn bind_guild(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    guild_id: i64,
+    corporation_id: i32,
+    alliance_id: i32
+) -> Result<guild::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = Guild::find()
+        .filter(guild::Column::GuildId.eq(guild_id))
+        .one(&txn)
+        .await?;
+    let guild = bind(&txn, trail, guild_id, before, alliance_id).await?;
+    txn.commit().await?;
+    Ok(guild)
+}
+
+/// Found an alliance run by the corporation, which leaves its old alliance for it along with its guilds.
+pub async fn found_alliance(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    name: &str
+) -> Result<alliance::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = Corporation::find_by_id(corporation_id)
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "corporation", key: corporation_id.to_string() })?;
+    let alliance = alliance::ActiveModel {
+        name: ActiveValue::Set(name.to_owned()),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("alliance", name)?;
+    let after = crate::join_alliance(&txn, before.clone(), alliance.id).await?;
+    let alliance = Alliance::find_by_id(alliance.id)
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "alliance", key: name.to_owned() })?;
+    trail.record(&txn, Action::Create, "alliance", alliance.id, None, Some(&alliance)).await?;
+    trail.record(&txn, Action::Update, "corporation", after.id, Some(&before), Some(&after)).await?;
+    let guilds = Guild::find()
+        .filter(guild::Column::CorporationId.eq(corporation_id))
+        .all(&txn)
+        .await?;
+    for guild in guilds {
+        bind(&txn, trail, guild.guild_id, Some(guild), alliance.id).await?;
+    }
+    txn.commit().await?;
+    Ok(alliance)
+}
+
+/// Rename the alliance the corporation runs.
+pub async fn rename_alliance(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    alliance_id: i32,
+    name: &str
+) -> Result<alliance::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_executed_alliance(&txn, corporation_id, alliance_id).await?;
+    let mut alliance: alliance::ActiveModel = before.clone().into();
+    alliance.name = ActiveValue::Set(name.to_owned());
+    let after = alliance.update(&txn).await.context("alliance", name)?;
+    trail.record(&txn, Action::Update, "alliance", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Deactivate the alliance the corporation runs, its corporations are left as they are.
+pub async fn retire_alliance(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    alliance_id: i32
+) -> Result<alliance::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_executed_alliance(&txn, corporation_id, alliance_id).await?;
+    let mut alliance = before.clone();
+    alliance.deactivate();
+    let after = alliance::ActiveModel::from(alliance).reset_all().update(&txn).await?;
+    trail.record(&txn, Action::Update, "alliance", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Register a corporation in the alliance the corporation runs.
+pub async fn found_corporation(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    alliance_id: i32,
+    name: &str
+) -> Result<corporation::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    find_executed_alliance(&txn, corporation_id, alliance_id).await?;
+    let corporation = corporation::ActiveModel {
+        name: ActiveValue::Set(name.to_owned()),
+        alliance_id: ActiveValue::Set(alliance_id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("corporation", name)?;
+    trail.record(&txn, Action::Create, "corporation", corporation.id, None, Some(&corporation)).await?;
+    txn.commit().await?;
+    Ok(corporation)
+}
+
+/// Rename the corporation.
+pub async fn rename_corporation(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    name: &str
+) -> Result<corporation::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = Corporation::find_by_id(corporation_id)
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "corporation", key: corporation_id.to_string() })?;
+    let mut corporation: corporation::ActiveModel = before.clone().into();
+    corporation.name = ActiveValue::Set(name.to_owned());
+    let after = corporation.update(&txn).await.context("corporation", name)?;
+    trail.record(&txn, Action::Update, "corporation", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Deactivate the corporation with its members, capsuleers, outposts and problems.
+pub async fn retire_corporation(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32
+) -> Result<corporation::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = Corporation::find_by_id(corporation_id)
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "corporation", key: corporation_id.to_string() })?;
+    crate::set_corporations_active(&txn, Condition::all().add(corporation::Column::Id.eq(corporation_id)), false).await?;
+    let mut after = before.clone();
+    after.deactivate();
+    trail.record(&txn, Action::Update, "corporation", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Move a member of the corporation and their capsuleers to another corporation, recorded for both.
+/// Problems stay with the old corporation, so the member's outposts leave the problems they served.
+pub async fn transfer_member(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    member_id: i32,
+    to_corporation_id: i32
+) -> Result<member::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = Member::find_by_id(member_id)
+        .filter(member::Column::CorporationId.eq(corporation_id))
+        .one(&txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "member", key: member_id.to_string() })?;
+    let mut member: member::ActiveModel = before.clone().into();
+    member.corporation_id = ActiveValue::Set(to_corporation_id);
+    // The new corporation may already have a member with the name.
+    let after = member.update(&txn).await.context("member", &before.name)?;
+    Capsuleer::update_many()
+        .col_expr(capsuleer::Column::CorporationId, sea_query::Expr::value(to_corporation_id))
+        .filter(capsuleer::Column::MemberId.eq(member_id))
+        .exec(&txn)
+        .await?;
+    let capsuleers = sea_query::Query::select()
+        .column(capsuleer::Column::Id)
+        .from(capsuleer::Entity)
+        .and_where(capsuleer::Column::MemberId.eq(member_id))
+        .to_owned();
+    let outposts = sea_query::Query::select()
+        .column(outpost::Column::Id)
+        .from(outpost::Entity)
+        .and_where(outpost::Column::CapsuleerId.in_subquery(capsuleers.clone()))
+        .to_owned();
+    Outpost::update_many()
+        .col_expr(outpost::Column::ProblemId, sea_query::Expr::value(Option::<i32>::None))
+        .filter(outpost::Column::CapsuleerId.in_subquery(capsuleers))
+        .exec(&txn)
+        .await?;
+    OutpostProblem::delete_many()
+        .filter(outpost_problem::Column::OutpostId.in_subquery(outposts))
+        .exec(&txn)
+        .await?;
+    trail.record(&txn, Action::Update, "member", after.id, Some(&before), Some(&after)).await?;
+    Trail { corporation_id: to_corporation_id, actor }
+        .record(&txn, Action::Update, "member", after.id, Some(&before), Some(&after))
+        .await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Add a skill the capsuleer of the corporation trained.
+pub async fn register_skill(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    capsuleer_id: i32,
+    skill: NewSkill<'_>
+) -> Result<skill::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    find_capsuleer_by_id(&txn, corporation_id, capsuleer_id).await?;
+    let skill = skill::ActiveModel {
+        name: ActiveValue::Set(skill.name.to_owned()),
+        basic: ActiveValue::Set(skill.basic),
+        advanced: ActiveValue::Set(skill.advanced),
+        expert: ActiveValue::Set(skill.expert),
+        capsuleer_id: ActiveValue::Set(capsuleer_id),
+        ..Default::default()
+    }
+    .insert(&txn)
+    .await
+    .context("skill", skill.name)?;
+    trail.record(&txn, Action::Create, "skill", skill.id, None, Some(&skill)).await?;
+    txn.commit().await?;
+    Ok(skill)
+}
+
+/// Set a capsuleer's skill levels, adding the skill when the capsuleer has not trained it yet.
+pub async fn set_skill(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    capsuleer_id: i32,
+    skill: NewSkill<'_>
+) -> Result<skill::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    find_capsuleer_by_id(&txn, corporation_id, capsuleer_id).await?;
+    let before = Skill::find()
+        .filter(skill::Column::CapsuleerId.eq(capsuleer_id))
+        .filter(skill::Column::Name.eq(skill.name))
+        .one(&txn)
+        .await?;
+    let mut model: skill::ActiveModel = match &before {
+        Some(skill) => skill.clone().into(),
+        None => skill::ActiveModel {
+            name: ActiveValue::Set(skill.name.to_owned()),
+            capsuleer_id: ActiveValue::Set(capsuleer_id),
+            ..Default::default()
+        },
+    };
+    model.basic = ActiveValue::Set(skill.basic);
+    model.advanced = ActiveValue::Set(skill.advanced);
+    model.expert = ActiveValue::Set(skill.expert);
+    let after = model.save(&txn).await.context("skill", skill.name)?.try_into_model()?;
+    let action = if before.is_some() { Action::Update } else { Action::Create };
+    trail.record(&txn, action, "skill", after.id, before.as_ref(), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Deactivate a capsuleer of the corporation and their outposts, skills and arrays are kept for when they return.
+pub async fn retire_capsuleer(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    capsuleer_id: i32
+) -> Result<capsuleer::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_capsuleer_by_id(&txn, corporation_id, capsuleer_id).await?;
+    crate::set_capsuleers_active(&txn, Condition::all().add(capsuleer::Column::Id.eq(capsuleer_id)), false).await?;
+    let mut after = before.clone();
+    after.deactivate();
+    trail.record(&txn, Action::Update, "capsuleer", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Reactivate a retired capsuleer of the corporation and their outposts, which come back without a problem.
+pub async fn restore_capsuleer(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    capsuleer_id: i32
+) -> Result<capsuleer::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_capsuleer_by_id(&txn, corporation_id, capsuleer_id).await?;
+    // A capsuleer or outpost registered since may have taken the name.
+    crate::set_capsuleers_active(&txn, Condition::all().add(capsuleer::Column::Id.eq(capsuleer_id)), true)
+        .await
+        .context("capsuleer", &before.name)?;
+    let mut after = before.clone();
+    after.restore();
+    trail.record(&txn, Action::Update, "capsuleer", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Soft-delete a problem of the corporation, the outposts harvesting for it are released.
+pub async fn retire_problem(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    problem_id: i32
+) -> Result<problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_problem(&txn, corporation_id, problem_id).await?;
+    crate::set_problems_active(&txn, Condition::all().add(problem::Column::Id.eq(problem_id)), false).await?;
+    let mut after = before.clone();
+    after.deactivate();
+    trail.record(&txn, Action::Delete, "problem", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Reactivate a retired problem of the corporation, the outposts it released are not reattached.
+pub async fn restore_problem(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    problem_id: i32
+) -> Result<problem::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_problem(&txn, corporation_id, problem_id).await?;
+    crate::set_problems_active(&txn, Condition::all().add(problem::Column::Id.eq(problem_id)), true)
+        .await
+        .context("problem", &before.name)?;
+    let mut after = before.clone();
+    after.restore();
+    trail.record(&txn, Action::Update, "problem", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// Reactivate a deleted outpost of the corporation, it comes back without a problem.
+pub async fn restore_outpost(
+    db: &DatabaseConnection,
+    actor: Actor<'_>,
+    corporation_id: i32,
+    outpost_id: i32
+) -> Result<outpost::Model, Error> {
+    let trail = Trail { corporation_id, actor };
+    let txn = db.begin().await?;
+    let before = find_outpost(&txn, corporation_id, outpost_id).await?;
+    let mut after = before.clone();
+    after.restore();
+    // Another active outpost of the capsuleer may have taken the name since.
+    let after = outpost::ActiveModel::from(after).reset_all().update(&txn).await.context("outpost", &before.name)?;
+    trail.record(&txn, Action::Update, "outpost", after.id, Some(&before), Some(&after)).await?;
+    txn.commit().await?;
+    Ok(after)
+}
+
+/// The corporation's member with the name, added when there is none.
+async fn member(txn: &DatabaseTransaction, trail: Trail<'_>, name: &str) -> Result<member::Model, Error> {
+    let existing = Member::find()
+        .filter(member::Column::CorporationId.eq(trail.corporation_id))
+        .filter(member::Column::Name.eq(name))
+        .order_by_desc(member::Column::Active)
+        .one(txn)
+        .await?;
+    if let Some(member) = existing {
+        return Ok(member);
+    }
+    let member = member::ActiveModel {
+        name: ActiveValue::Set(name.to_owned()),
+        corporation_id: ActiveValue::Set(trail.corporation_id),
+        ..Default::default()
+    }
+    .insert(txn)
+    .await
+    .context("member", name)?;
+    trail.record(txn, Action::Create, "member", member.id, None, Some(&member)).await?;
+    Ok(member)
+}
+
+async fn new_capsuleer(txn: &DatabaseTransaction, trail: Trail<'_>, member_id: i32, name: &str) -> Result<capsuleer::Model, Error> {
+    let capsuleer = capsuleer::ActiveModel {
+        name: ActiveValue::Set(name.to_owned()),
+        member_id: ActiveValue::Set(member_id),
+        corporation_id: ActiveValue::Set(trail.corporation_id),
+        ..Default::default()
+    }
+    .insert(txn)
+    .await
+    .context("capsuleer", name)?;
+    trail.record(txn, Action::Create, "capsuleer", capsuleer.id, None, Some(&capsuleer)).await?;
+    Ok(capsuleer)
+}
+
+/// Point the guild at the corporation in the trail and the alliance, recording whether it was bound before.
+async fn bind(
+    txn: &DatabaseTransaction,
+    trail: Trail<'_>,
+    guild_id: i64,
+    before: Option<guild::Model>,
+    alliance_id: i32
+) -> Result<guild::Model, Error> {
+    let mut model: guild::ActiveModel = match &before {
+        Some(guild) => guild.clone().into(),
+        None => guild::ActiveModel {
+            guild_id: ActiveValue::Set(guild_id),
+            ..Default::default()
+        },
+    };
+    model.corporation_id = ActiveValue::Set(trail.corporation_id);
+    model.alliance_id = ActiveValue::Set(alliance_id);
+    let after = model.save(txn).await?.try_into_model()?;
+    let action = if before.is_some() { Action::Update } else { Action::Create };
+    trail.record(txn, action, "guild", after.id, before.as_ref(), Some(&after)).await?;
+    Ok(after)
+}
+
+/// The alliance when the corporation is its executor, forbidden for any other corporation.
+async fn find_executed_alliance(txn: &DatabaseTransaction, corporation_id: i32, alliance_id: i32) -> Result<alliance::Model, Error> {
+    let alliance = Alliance::find_by_id(alliance_id)
+        .one(txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "alliance", key: alliance_id.to_string() })?;
+    if !alliance.is_executor(corporation_id) {
+        return Err(Error::Forbidden { entity: "alliance", key: alliance.name });
+    }
+    Ok(alliance)
+}
+
+/// The capsuleer when they fly for the corporation, not found for any other corporation.
+async fn find_capsuleer_by_id(txn: &DatabaseTransaction, corporation_id: i32, capsuleer_id: i32) -> Result<capsuleer::Model, Error> {
+    Capsuleer::find_by_id(capsuleer_id)
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .one(txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "capsuleer", key: capsuleer_id.to_string() })
+}
+
+/// The outpost when a capsuleer of the corporation anchored it, not found for any other corporation.
+async fn find_outpost(txn: &DatabaseTransaction, corporation_id: i32, outpost_id: i32) -> Result<outpost::Model, Error> {
+    Outpost::find_by_id(outpost_id)
+        .inner_join(Capsuleer)
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .one(txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "outpost", key: outpost_id.to_string() })
+}
+
+/// The problem when the corporation owns it, not found for any other corporation.
+async fn find_problem(txn: &DatabaseTransaction, corporation_id: i32, problem_id: i32) -> Result<problem::Model, Error> {
+    Problem::find_by_id(problem_id)
+        .filter(problem::Column::CorporationId.eq(corporation_id))
+        .one(txn)
+        .await?
+        .ok_or(Error::NotFound { entity: "problem", key: problem_id.to_string() })
+}
+
+async fn find_capsuleer(txn: &DatabaseTransaction, corporation_id: i32, name: &str) -> Result<Option<capsuleer::Model>, DbErr> {
+    Capsuleer::find()
+        .filter(capsuleer::Column::CorporationId.eq(corporation_id))
+        .filter(capsuleer::Column::Name.eq(name))
+        .order_by_desc(capsuleer::Column::Active)
+        .one(txn)
+        .await
+}
+
+/// Spend a claim issued for the corporation within its lifetime, refusing the entity it was meant to prove otherwise.
+async fn redeem_claim(
+    txn: &DatabaseTransaction,
+    corporation_id: i32,
+    entity: &'static str,
+    name: &str,
+    token: Option<&str>
+) -> Result<(), Error> {
+    let forbidden = || Error::Forbidden { entity, key: name.to_owned() };
+    let token = token.ok_or_else(forbidden)?;
+    let issued_after = DateTimeUtc::from(std::time::SystemTime::now() - CLAIM_LIFETIME);
+    let redeemed = CorporationClaim::delete_many()
+        .filter(corporation_claim::Column::CorporationId.eq(corporation_id))
+        .filter(corporation_claim::Column::Token.eq(token))
+        .filter(corporation_claim::Column::CreatedAt.gt(issued_after))
+        .exec(txn)
+        .await?;
+    if redeemed.rows_affected == 0 {
+        return Err(forbidden());
+    }
+    Ok(())
+}
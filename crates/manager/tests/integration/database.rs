@@ -4,22 +4,29 @@
 mod tests {
 
     use sea_orm::*;
+    use sea_orm::prelude::DateTimeUtc;
 
     use manager::*;
     use manager::entities::*;
     use manager::entities::prelude::*;
     use manager::environment::EnvironmentManager;
+    use manager::audit::Actor;
+    use manager::export::*;
+    use manager::service::*;
 
     use crate::DatabaseManager;
     use crate::TEST_ALLIANCE_NAME;
     use crate::TEST_CAPSULEER_NAME;
     use crate::TEST_CORPORATION_NAME;
+    use crate::TEST_GUILD_ID;
     use crate::TEST_MEMBER_NAME;
     use crate::TEST_OUTPOST_NAME;
     use crate::TEST_PROBLEM_NAME;
     use crate::TEST_SKILL_NAME;
     use crate::TEST_SYSTEM_NAME;
 
+    const TEST_ACTOR: Actor = Actor::Discord(TEST_MEMBER_NAME);
+
 
     #[tokio::test]
     async fn test_alliance() {
@@ -364,4 +371,1298 @@ mod tests {
         assert_eq!(retrieved_outposts_by_problem_name[0].1.clone().unwrap().name, TEST_OUTPOST_NAME);
         assert_eq!(retrieved_outposts_by_problem_name[1].1.clone().unwrap().name, "Test Outpost 2");
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_update_outpost_and_problem() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let saved_problem = new_problem(&db, TEST_PROBLEM_NAME, vec![], saved_member.last_insert_id, saved_corporation.last_insert_id, None)
+            .await
+            .expect("Failed to add problem to database");
+
+        let saved_outpost = new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, Some(saved_problem.last_insert_id))
+            .await
+            .expect("Failed to add outpost to database");
+
+        let edit = NewOutpost { name: "Renamed Outpost", system: "Other System", planets: 10, arrays: 30 };
+        let updated_outpost = edit_outpost(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_outpost.last_insert_id, edit)
+            .await
+            .expect("Failed to update outpost");
+
+        let updated_problem = edit_problem(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_problem.last_insert_id, "Renamed Problem", b"materials".to_vec())
+            .await
+            .expect("Failed to update problem");
+
+        assert_eq!(updated_outpost.name, "Renamed Outpost");
+        assert_eq!(updated_outpost.system, "Other System");
+        assert_eq!(updated_outpost.planets, 10);
+        assert_eq!(updated_outpost.arrays, 30);
+        assert_eq!(updated_outpost.problem_id, Some(saved_problem.last_insert_id));
+        assert_eq!(updated_problem.name, "Renamed Problem");
+        assert_eq!(updated_problem.constraint, b"materials".to_vec());
+        assert!(Problem::find_by_name(TEST_PROBLEM_NAME, &db).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_set_skill() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let added_skill = set_skill(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_capsuleer.last_insert_id, NewSkill { name: TEST_SKILL_NAME, basic: 5, advanced: 4, expert: 3 })
+            .await
+            .expect("Failed to add skill");
+
+        let trained_skill = set_skill(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_capsuleer.last_insert_id, NewSkill { name: TEST_SKILL_NAME, basic: 5, advanced: 5, expert: 4 })
+            .await
+            .expect("Failed to update skill");
+
+        assert_eq!(trained_skill.id, added_skill.id);
+        assert_eq!((trained_skill.basic, trained_skill.advanced, trained_skill.expert), (5, 5, 4));
+        assert_eq!(Skill::find_by_capsuleer(saved_capsuleer.last_insert_id, &db).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retire_capsuleer() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        new_skill(&db, TEST_SKILL_NAME, 5, 4, 3, saved_capsuleer.last_insert_id)
+            .await
+            .expect("Failed to add skill to database");
+
+        new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, None)
+            .await
+            .expect("Failed to add outpost to database");
+
+        let retired_capsuleer = retire_capsuleer(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_capsuleer.last_insert_id)
+            .await
+            .expect("Failed to retire capsuleer");
+
+        let retrieved_skill = Skill::find_by_name(TEST_SKILL_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let retrieved_outpost = Outpost::find_by_name(TEST_OUTPOST_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!retired_capsuleer.active);
+        assert!(!retrieved_outpost.active);
+        assert_eq!((retrieved_skill.basic, retrieved_skill.advanced, retrieved_skill.expert), (5, 4, 3));
+        assert_eq!((retrieved_outpost.planets, retrieved_outpost.arrays), (12, 26));
+    }
+
+    #[tokio::test]
+    async fn test_deactivate_capsuleer() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let saved_problem = new_problem(&db, TEST_PROBLEM_NAME, vec![], saved_member.last_insert_id, saved_corporation.last_insert_id, None)
+            .await
+            .expect("Failed to add problem to database");
+
+        let saved_outpost = new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, Some(saved_problem.last_insert_id))
+            .await
+            .expect("Failed to add outpost to database");
+
+        retire_capsuleer(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_capsuleer.last_insert_id)
+            .await
+            .expect("Failed to deactivate capsuleer");
+
+        let retrieved_outpost = Outpost::find_by_id(saved_outpost.last_insert_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(!retrieved_outpost.active);
+        assert_eq!(retrieved_outpost.problem_id, None);
+        assert!(Capsuleer::find_active_by_name(TEST_CAPSULEER_NAME, &db).await.unwrap().is_none());
+        assert!(Outpost::find_active_by_name(TEST_OUTPOST_NAME, &db).await.unwrap().is_none());
+        assert!(Outpost::find_by_name(TEST_OUTPOST_NAME, &db).await.unwrap().is_some());
+
+        restore_capsuleer(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_capsuleer.last_insert_id)
+            .await
+            .expect("Failed to restore capsuleer");
+
+        let restored_outpost = Outpost::find_active_by_name(TEST_OUTPOST_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert!(Capsuleer::find_active_by_name(TEST_CAPSULEER_NAME, &db).await.unwrap().is_some());
+        assert_eq!(restored_outpost.problem_id, None);
+
+        // A retired capsuleer's name is free for a new one, which is found before it.
+        retire_capsuleer(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_capsuleer.last_insert_id)
+            .await
+            .expect("Failed to deactivate capsuleer");
+        let successor = register_capsuleer_with_skills(&db, TEST_ACTOR, saved_corporation.last_insert_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, &[])
+            .await
+            .expect("Failed to register a capsuleer under a retired name");
+        let found = Capsuleer::find_by_corporation_and_name(saved_corporation.last_insert_id, TEST_CAPSULEER_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(found.id, successor.id);
+        assert!(matches!(
+            restore_capsuleer(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_capsuleer.last_insert_id).await,
+            Err(Error::Duplicate { entity: "capsuleer", .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_deactivate_corporation() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let saved_problem = new_problem(&db, TEST_PROBLEM_NAME, vec![], saved_member.last_insert_id, saved_corporation.last_insert_id, None)
+            .await
+            .expect("Failed to add problem to database");
+
+        let saved_outpost = new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, Some(saved_problem.last_insert_id))
+            .await
+            .expect("Failed to add outpost to database");
+
+        retire_corporation(&db, TEST_ACTOR, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to deactivate corporation");
+
+        assert!(Corporation::find_active_by_name(TEST_CORPORATION_NAME, &db).await.unwrap().is_none());
+        assert!(Member::find_active_by_corporation(saved_corporation.last_insert_id, &db).await.unwrap().is_empty());
+        assert!(Capsuleer::find_active_by_corporation(saved_corporation.last_insert_id, &db).await.unwrap().is_empty());
+        assert!(Problem::find_active_by_corporation(saved_corporation.last_insert_id, &db).await.unwrap().is_empty());
+        assert!(Outpost::find_active_by_capsuleer(saved_capsuleer.last_insert_id, &db).await.unwrap().is_empty());
+        assert!(Alliance::find_active_by_name(TEST_ALLIANCE_NAME, &db).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_deactivate_problem_and_outpost() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let saved_problem = new_problem(&db, TEST_PROBLEM_NAME, vec![], saved_member.last_insert_id, saved_corporation.last_insert_id, None)
+            .await
+            .expect("Failed to add problem to database");
+
+        let saved_outpost = new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, Some(saved_problem.last_insert_id))
+            .await
+            .expect("Failed to add outpost to database");
+
+        retire_problem(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_problem.last_insert_id)
+            .await
+            .expect("Failed to deactivate problem");
+
+        let released_outpost = Outpost::find_active_by_name(TEST_OUTPOST_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(released_outpost.problem_id, None);
+        assert!(Problem::find_active_by_name(TEST_PROBLEM_NAME, &db).await.unwrap().is_none());
+
+        let restored_problem = restore_problem(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_problem.last_insert_id)
+            .await
+            .expect("Failed to restore problem");
+        assert!(restored_problem.active);
+        assert!(Problem::find_active_by_name(TEST_PROBLEM_NAME, &db).await.unwrap().is_some());
+
+        let deactivated_outpost = delete_outpost(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_outpost.last_insert_id)
+            .await
+            .expect("Failed to deactivate outpost");
+        assert!(!deactivated_outpost.active);
+
+        let restored_outpost = restore_outpost(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_outpost.last_insert_id)
+            .await
+            .expect("Failed to restore outpost");
+        assert!(restored_outpost.active);
+
+        let events = AuditEvent::find_by_corporation(saved_corporation.last_insert_id, Some("problem"), 10, &db).await.unwrap();
+        assert_eq!(events.iter().map(|event| event.action.as_str()).collect::<Vec<_>>(), vec!["update", "delete"]);
+    }
+
+    #[tokio::test]
+    async fn test_scoped_names() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let other_corporation = new_corporation(&db, "Other Corporation", saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let other_member = new_member(&db, TEST_MEMBER_NAME, other_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member with the same name to another corporation");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let second_capsuleer = new_capsuleer(&db, "Second Capsuleer", saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let other_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, other_member.last_insert_id, other_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer with the same name to another corporation");
+
+        new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, None)
+            .await
+            .expect("Failed to add outpost to database");
+
+        new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, other_capsuleer.last_insert_id, None)
+            .await
+            .expect("Failed to add outpost with the same name to another capsuleer");
+
+        assert!(matches!(
+            new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, None).await,
+            Err(Error::Duplicate { entity: "outpost", .. })
+        ));
+        assert!(matches!(
+            new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id).await,
+            Err(Error::Duplicate { entity: "corporation", .. })
+        ));
+        match new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id).await {
+            Err(Error::Duplicate { entity, key }) => assert_eq!((entity, key.as_str()), ("member", TEST_MEMBER_NAME)),
+            other => panic!("expected a duplicate member, got {other:?}"),
+        }
+        assert!(matches!(
+            edit_outpost(&db, TEST_ACTOR, saved_corporation.last_insert_id, -1, NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 }).await,
+            Err(Error::NotFound { entity: "outpost", .. })
+        ));
+
+        assert!(matches!(
+            Capsuleer::find_by_name(TEST_CAPSULEER_NAME, &db).await,
+            Err(Error::Ambiguous { entity: "capsuleer", .. })
+        ));
+        assert!(matches!(Outpost::find_by_name(TEST_OUTPOST_NAME, &db).await, Err(Error::Ambiguous { .. })));
+
+        let scoped_capsuleer = Capsuleer::find_by_corporation_and_name(other_corporation.last_insert_id, TEST_CAPSULEER_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        let scoped_outpost = Outpost::find_active_by_corporation_and_name(saved_corporation.last_insert_id, TEST_OUTPOST_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(scoped_capsuleer.id, other_capsuleer.last_insert_id);
+        assert_eq!(scoped_outpost.capsuleer_id, saved_capsuleer.last_insert_id);
+
+        new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, second_capsuleer.last_insert_id, None)
+            .await
+            .expect("Failed to add outpost with the same name to a second capsuleer");
+
+        assert!(matches!(
+            Outpost::find_active_by_corporation_and_name(saved_corporation.last_insert_id, TEST_OUTPOST_NAME, &db).await,
+            Err(Error::Ambiguous { entity: "outpost", .. })
+        ));
+        assert!(Outpost::find_by_capsuleer_and_name(second_capsuleer.last_insert_id, TEST_OUTPOST_NAME, &db).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_register_capsuleer_with_skills() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+
+        let skills = [NewSkill { name: TEST_SKILL_NAME, basic: 5, advanced: 4, expert: 3 }];
+        let capsuleer = register_capsuleer_with_skills(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, &skills)
+            .await
+            .expect("Failed to register capsuleer");
+
+        let member = Member::find_by_corporation_and_name(corporation_id, TEST_MEMBER_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+        let saved_skills = Skill::find_by_capsuleer(capsuleer.id, &db)
+            .await
+            .unwrap();
+
+        assert_eq!(capsuleer.member_id, member.id);
+        assert_eq!(saved_skills.len(), 1);
+        assert_eq!((saved_skills[0].basic, saved_skills[0].advanced, saved_skills[0].expert), (5, 4, 3));
+
+        // A second capsuleer reuses the member rather than adding another.
+        register_capsuleer_with_skills(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, "Second Capsuleer", &skills)
+            .await
+            .expect("Failed to register a second capsuleer");
+        assert_eq!(Member::find_by_corporation(corporation_id, &db).await.unwrap().len(), 1);
+
+        assert!(matches!(
+            register_capsuleer_with_skills(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, &skills).await,
+            Err(Error::Duplicate { entity: "capsuleer", .. })
+        ));
+
+        // The skill insert fails on the unique index, so the member and capsuleer added before it roll back.
+        let repeated = [skills[0].clone(), skills[0].clone()];
+        assert!(matches!(
+            register_capsuleer_with_skills(&db, TEST_ACTOR, corporation_id, "New Member", "New Capsuleer", &repeated).await,
+            Err(Error::Duplicate { entity: "skill", .. })
+        ));
+        assert!(Member::find_by_corporation_and_name(corporation_id, "New Member", &db).await.unwrap().is_none());
+        assert!(Capsuleer::find_by_corporation_and_name(corporation_id, "New Capsuleer", &db).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_register_outpost_for_user() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+
+        let outpost = NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 };
+        let saved_outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost.clone())
+            .await
+            .expect("Failed to register outpost");
+
+        let capsuleer = Capsuleer::find_by_corporation_and_name(corporation_id, TEST_CAPSULEER_NAME, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(saved_outpost.capsuleer_id, capsuleer.id);
+        assert_eq!(saved_outpost.problem_id, None);
+        assert_eq!((saved_outpost.planets, saved_outpost.arrays), (12, 26));
+
+        let second_outpost = NewOutpost { name: "Second Outpost", ..outpost.clone() };
+        let second_outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, second_outpost)
+            .await
+            .expect("Failed to register a second outpost");
+        assert_eq!(second_outpost.capsuleer_id, capsuleer.id);
+        assert_eq!(Capsuleer::find_by_corporation(corporation_id, &db).await.unwrap().len(), 1);
+
+        assert!(matches!(
+            register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost.clone()).await,
+            Err(Error::Duplicate { entity: "outpost", .. })
+        ));
+
+        delete_outpost(&db, TEST_ACTOR, corporation_id, saved_outpost.id)
+            .await
+            .expect("Failed to delete outpost");
+        let reanchored_outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost.clone())
+            .await
+            .expect("Failed to register an outpost under the name of a deleted one");
+        assert_ne!(reanchored_outpost.id, saved_outpost.id);
+
+        retire_capsuleer(&db, TEST_ACTOR, corporation_id, capsuleer.id)
+            .await
+            .expect("Failed to retire capsuleer");
+        assert!(matches!(
+            register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost).await,
+            Err(Error::Inactive { entity: "capsuleer", .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_audit_events() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+
+        let other_corporation = new_corporation(&db, "Other Corporation", saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let problem = register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, TEST_MEMBER_NAME, TEST_PROBLEM_NAME, b"Fuel 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        let outpost = NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 };
+        let outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost)
+            .await
+            .expect("Failed to register outpost");
+        let api = Actor::Api("token");
+        let assigned = assign_member_outposts(&db, api, corporation_id, problem.member_id, problem.id)
+            .await
+            .expect("Failed to assign outposts");
+        delete_outpost(&db, TEST_ACTOR, corporation_id, outpost.id)
+            .await
+            .expect("Failed to delete outpost");
+
+        assert_eq!(assigned.len(), 1);
+        assert_eq!(assigned[0].problem_id, Some(problem.id));
+
+        let events = AuditEvent::find_by_corporation(corporation_id, None, 100, &db)
+            .await
+            .unwrap();
+        let actions: Vec<(&str, &str)> = events.iter().map(|event| (event.action.as_str(), event.entity.as_str())).collect();
+        assert_eq!(actions, vec![
+            ("delete", "outpost"),
+            ("assign", "outpost"),
+            ("create", "outpost"),
+            ("create", "capsuleer"),
+            ("create", "problem"),
+            ("create", "member"),
+        ]);
+        assert_eq!(events[1].actor, "api:token");
+        assert_eq!(events[1].changed_fields(), vec!["problem_id".to_string()]);
+        assert_eq!(events[0].changed_fields(), vec!["active".to_string(), "problem_id".to_string()]);
+        assert_eq!(events[4].after.as_ref().unwrap()["constraint"], "Fuel 1");
+
+        let outpost_events = AuditEvent::find_by_corporation(corporation_id, Some("outpost"), 2, &db)
+            .await
+            .unwrap();
+        assert_eq!(outpost_events.len(), 2);
+        assert!(outpost_events.iter().all(|event| event.entity_id == outpost.id));
+        assert_eq!(outpost_events[0].actor, format!("discord:{TEST_MEMBER_NAME}"));
+
+        // A failed registration leaves no trace.
+        assert!(register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, "Other Member", TEST_PROBLEM_NAME, b"Fuel 1".to_vec()).await.is_err());
+        assert_eq!(AuditEvent::find_by_corporation(corporation_id, None, 100, &db).await.unwrap().len(), events.len());
+        assert!(AuditEvent::find_by_corporation(other_corporation.last_insert_id, None, 100, &db).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_transfer_member() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let other_corporation = new_corporation(&db, "Other Corporation", saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_member = new_member(&db, TEST_MEMBER_NAME, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add member to database");
+
+        let saved_capsuleer = new_capsuleer(&db, TEST_CAPSULEER_NAME, saved_member.last_insert_id, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to add capsuleer to database");
+
+        let saved_problem = new_problem(&db, TEST_PROBLEM_NAME, vec![], saved_member.last_insert_id, saved_corporation.last_insert_id, None)
+            .await
+            .expect("Failed to add problem to database");
+        let other_problem = new_problem(&db, "Hull", vec![], saved_member.last_insert_id, saved_corporation.last_insert_id, None)
+            .await
+            .expect("Failed to add problem to database");
+        let saved_outpost = new_outpost(&db, TEST_OUTPOST_NAME, TEST_SYSTEM_NAME, 12, 26, saved_capsuleer.last_insert_id, Some(saved_problem.last_insert_id))
+            .await
+            .expect("Failed to add outpost to database");
+        link_outpost(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_outpost.last_insert_id, other_problem.last_insert_id)
+            .await
+            .expect("Failed to link outpost");
+
+        let transferred_member = transfer_member(&db, TEST_ACTOR, saved_corporation.last_insert_id, saved_member.last_insert_id, other_corporation.last_insert_id)
+            .await
+            .expect("Failed to transfer member");
+
+        let retrieved_capsuleer = Capsuleer::find_by_id(saved_capsuleer.last_insert_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(transferred_member.corporation_id, other_corporation.last_insert_id);
+        assert_eq!(retrieved_capsuleer.corporation_id, other_corporation.last_insert_id);
+        assert!(Member::find_by_corporation(saved_corporation.last_insert_id, &db).await.unwrap().is_empty());
+
+        let retrieved_outpost = Outpost::find_by_id(saved_outpost.last_insert_id)
+            .one(&db)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(retrieved_outpost.problem_id, None);
+        assert!(OutpostProblem::find().all(&db).await.unwrap().is_empty());
+
+        for corporation_id in [saved_corporation.last_insert_id, other_corporation.last_insert_id] {
+            let events = AuditEvent::find_by_corporation(corporation_id, Some("member"), 100, &db).await.unwrap();
+            assert_eq!(events.len(), 1);
+            assert_eq!(events[0].action, "update");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bind_guild() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let other_corporation = new_corporation(&db, "Other Corporation", saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        let saved_guild = bind_guild(&db, TEST_ACTOR, TEST_GUILD_ID, saved_corporation.last_insert_id, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to bind guild");
+
+        let rebound_guild = bind_guild(&db, TEST_ACTOR, TEST_GUILD_ID, other_corporation.last_insert_id, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to rebind guild");
+
+        let retrieved_guild = Guild::find_by_guild_id(TEST_GUILD_ID, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(rebound_guild.id, saved_guild.id);
+        assert_eq!(retrieved_guild.corporation_id, other_corporation.last_insert_id);
+        assert_eq!(retrieved_guild.alliance_id, saved_alliance.last_insert_id);
+        assert_eq!(Guild::find().count(&db).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_found_alliance_moves_guilds() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+
+        bind_guild(&db, TEST_ACTOR, TEST_GUILD_ID, saved_corporation.last_insert_id, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to bind guild");
+
+        let founded = found_alliance(&db, TEST_ACTOR, saved_corporation.last_insert_id, "Other Alliance")
+            .await
+            .expect("Failed to found alliance");
+
+        let retrieved_guild = Guild::find_by_guild_id(TEST_GUILD_ID, &db)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(retrieved_guild.alliance_id, founded.id);
+        assert_eq!(retrieved_guild.corporation_id, saved_corporation.last_insert_id);
+    }
+
+    #[tokio::test]
+    async fn test_setup_guild() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let other_guild_id = TEST_GUILD_ID + 1;
+
+        assert!(matches!(
+            setup_guild(&db, TEST_ACTOR, TEST_GUILD_ID, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME, None).await,
+            Err(Error::Forbidden { entity: "corporation", .. })
+        ));
+        assert!(matches!(
+            setup_guild(&db, TEST_ACTOR, TEST_GUILD_ID, "Other Alliance", TEST_CORPORATION_NAME, None).await,
+            Err(Error::NotFound { entity: "corporation", .. })
+        ));
+
+        let expired = issue_claim(&db, TEST_ACTOR, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to issue claim");
+        let mut backdated: corporation_claim::ActiveModel = expired.clone().into();
+        backdated.created_at = ActiveValue::Set(DateTimeUtc::from(std::time::SystemTime::now() - std::time::Duration::from_secs(2 * 60 * 60)));
+        backdated.update(&db).await.expect("Failed to backdate claim");
+        assert!(matches!(
+            setup_guild(&db, TEST_ACTOR, TEST_GUILD_ID, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME, Some(&expired.token)).await,
+            Err(Error::Forbidden { entity: "corporation", .. })
+        ));
+
+        let claim = issue_claim(&db, TEST_ACTOR, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to issue claim");
+        let saved_guild = setup_guild(&db, TEST_ACTOR, TEST_GUILD_ID, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME, Some(&claim.token))
+            .await
+            .expect("Failed to set up guild");
+        assert_eq!(saved_guild.corporation_id, saved_corporation.last_insert_id);
+        setup_guild(&db, TEST_ACTOR, TEST_GUILD_ID, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME, None)
+            .await
+            .expect("Failed to set up guild again");
+
+        let claim = issue_claim(&db, TEST_ACTOR, saved_corporation.last_insert_id)
+            .await
+            .expect("Failed to issue claim");
+        assert!(matches!(
+            setup_guild(&db, TEST_ACTOR, other_guild_id, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME, Some(&claim.token)).await,
+            Err(Error::Forbidden { entity: "corporation", .. })
+        ));
+        assert!(matches!(
+            setup_guild(&db, TEST_ACTOR, other_guild_id, TEST_ALLIANCE_NAME, "Other Corporation", None).await,
+            Err(Error::Forbidden { entity: "alliance", .. })
+        ));
+        let other_guild = setup_guild(&db, TEST_ACTOR, other_guild_id, TEST_ALLIANCE_NAME, "Other Corporation", Some(&claim.token))
+            .await
+            .expect("Failed to set up guild with the executor's claim");
+        assert_eq!(other_guild.alliance_id, saved_alliance.last_insert_id);
+        assert!(matches!(
+            setup_guild(&db, TEST_ACTOR, other_guild_id + 1, TEST_ALLIANCE_NAME, "Third Corporation", Some(&claim.token)).await,
+            Err(Error::Forbidden { entity: "alliance", .. })
+        ));
+
+        let founded_guild = setup_guild(&db, TEST_ACTOR, other_guild_id + 1, "Other Alliance", "Third Corporation", None)
+            .await
+            .expect("Failed to found alliance and corporation");
+        let founded_alliance = Alliance::find_by_id(founded_guild.alliance_id).one(&db).await.unwrap().unwrap();
+        assert!(founded_alliance.is_executor(founded_guild.corporation_id));
+        let events = AuditEvent::find_by_corporation(founded_guild.corporation_id, None, 10, &db).await.unwrap();
+        assert_eq!(events.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_export_import() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+
+        let skills = [NewSkill { name: TEST_SKILL_NAME, basic: 5, advanced: 4, expert: 3 }];
+        register_capsuleer_with_skills(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, &skills)
+            .await
+            .expect("Failed to register capsuleer");
+        let problem = register_problem_for_user(&db, TEST_ACTOR, corporation_id, Some(saved_alliance.last_insert_id), TEST_MEMBER_NAME, TEST_PROBLEM_NAME, b"Fuel 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        let outpost = NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 };
+        let outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost)
+            .await
+            .expect("Failed to register outpost");
+        assign_member_outposts(&db, TEST_ACTOR, corporation_id, problem.member_id, problem.id)
+            .await
+            .expect("Failed to assign outposts");
+        place_arrays(&db, TEST_ACTOR, corporation_id, outpost.id, 40000001, 42001000000, 4)
+            .await
+            .expect("Failed to place arrays");
+        set_prices(&db, TEST_ACTOR, corporation_id, &[(42001000000, Some(120.0))])
+            .await
+            .expect("Failed to set prices");
+
+        let export = export_corporation(&db, corporation_id)
+            .await
+            .expect("Failed to export corporation");
+        assert_eq!(export.version, EXPORT_VERSION);
+        assert_eq!(export.corporation, TEST_CORPORATION_NAME);
+        assert_eq!((export.members.len(), export.capsuleers.len(), export.skills.len()), (1, 1, 1));
+        assert_eq!(export.problems[0].constraint, "Fuel 1");
+        assert_eq!(export.outposts[0].problem_id, Some(export.problems[0].id));
+        assert_eq!(export.outposts[0].placements, vec![PlacementExport { planet_id: 40000001, resource_type_id: 42001000000, count: 4 }]);
+        assert_eq!(export.price_books.len(), 1);
+        assert_eq!(export.price_books[0].prices[&42001000000], 120.0);
+
+        let other_corporation = new_corporation(&db, "Other Corporation", saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let other_id = other_corporation.last_insert_id;
+        let summary = import_corporation(&db, Actor::Cli("admin"), other_id, &export, OnCollision::Merge)
+            .await
+            .expect("Failed to import corporation");
+        assert_eq!(summary, ImportSummary { created: 6, merged: 0, renamed: 0 });
+
+        let imported = export_corporation(&db, other_id)
+            .await
+            .expect("Failed to export corporation");
+        assert_eq!(imported.members[0].name, TEST_MEMBER_NAME);
+        assert_eq!(imported.skills[0].expert, 3);
+        assert_eq!(imported.outposts[0].problem_id, Some(imported.problems[0].id));
+        assert!(imported.problems[0].shared);
+        assert_ne!(imported.outposts[0].id, export.outposts[0].id);
+        assert_eq!(imported.outposts[0].placements, export.outposts[0].placements);
+        assert_eq!(imported.price_books, export.price_books);
+
+        let summary = import_corporation(&db, Actor::Cli("admin"), other_id, &export, OnCollision::Merge)
+            .await
+            .expect("Failed to import corporation");
+        assert_eq!(summary, ImportSummary { created: 0, merged: 6, renamed: 0 });
+
+        let summary = import_corporation(&db, Actor::Cli("admin"), other_id, &export, OnCollision::Rename)
+            .await
+            .expect("Failed to import corporation");
+        assert_eq!(summary, ImportSummary { created: 2, merged: 1, renamed: 3 });
+        let renamed = Capsuleer::find_by_corporation_and_name(other_id, &format!("{TEST_CAPSULEER_NAME} (2)"), &db)
+            .await
+            .unwrap();
+        assert!(renamed.is_some());
+
+        let future = Export { version: EXPORT_VERSION + 1, ..export.clone() };
+        let result = import_corporation(&db, Actor::Cli("admin"), other_id, &future, OnCollision::Merge).await;
+        assert!(matches!(result, Err(Error::Unsupported { entity: "export", .. })));
+
+        // Files written before price books and placements were exported still import.
+        let mut legacy = serde_json::to_value(&export).unwrap();
+        legacy["version"] = 1.into();
+        legacy.as_object_mut().unwrap().remove("price_books");
+        legacy["outposts"][0].as_object_mut().unwrap().remove("placements");
+        let legacy: Export = serde_json::from_value(legacy).expect("Failed to read a version 1 export");
+        let legacy_corporation = new_corporation(&db, "Legacy Corporation", saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let summary = import_corporation(&db, Actor::Cli("admin"), legacy_corporation.last_insert_id, &legacy, OnCollision::Merge)
+            .await
+            .expect("Failed to import a version 1 export");
+        assert_eq!(summary, ImportSummary { created: 5, merged: 0, renamed: 0 });
+
+        let moved = Export { corporation: "New Corporation".to_string(), alliance: "New Alliance".to_string(), ..export };
+        let corporation = corporation_for_import(&db, &moved)
+            .await
+            .expect("Failed to add corporation");
+        assert_eq!(corporation.name, "New Corporation");
+        assert!(Alliance::find_by_name("New Alliance", &db).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_price_snapshots() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+
+        let earlier = DateTimeUtc::from_timestamp(1_736_000_000, 0).unwrap();
+        let last_week = DateTimeUtc::from_timestamp(1_737_000_000, 0).unwrap();
+        let midweek = DateTimeUtc::from_timestamp(1_737_300_000, 0).unwrap();
+        let today = DateTimeUtc::from_timestamp(1_737_604_800, 0).unwrap();
+        new_price_snapshot(&db, corporation_id, "pasted", serde_json::json!({ "42001000031": 350.0 }), last_week)
+            .await
+            .expect("Failed to add price snapshot");
+        new_price_snapshot(&db, corporation_id, "prices.csv", serde_json::json!({ "42001000031": 410.0 }), today)
+            .await
+            .expect("Failed to add price snapshot");
+
+        let before = PriceSnapshot::find_at(corporation_id, earlier, &db).await.unwrap();
+        assert!(before.is_none());
+        let then = PriceSnapshot::find_at(corporation_id, midweek, &db).await.unwrap().unwrap();
+        assert_eq!(then.source, "pasted");
+        assert_eq!(then.prices["42001000031"], 350.0);
+        let now = PriceSnapshot::find_at(corporation_id, today, &db).await.unwrap().unwrap();
+        assert_eq!(now.source, "prices.csv");
+
+        let snapshots = PriceSnapshot::find_by_corporation(corporation_id, 10, &db).await.unwrap();
+        let sources: Vec<&str> = snapshots.iter().map(|snapshot| snapshot.source.as_str()).collect();
+        assert_eq!(sources, vec!["prices.csv", "pasted"]);
+    }
+
+    #[tokio::test]
+    async fn test_price_book_versions() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+        const NANITES: i64 = 42001000031;
+        const LIQUID_OZONE: i64 = 42002000014;
+
+        assert!(PriceBook::find_with_entries(corporation_id, None, &db).await.unwrap().is_none());
+        let (first, entries) = set_prices(&db, TEST_ACTOR, corporation_id, &[(NANITES, Some(350.)), (LIQUID_OZONE, Some(12.))])
+            .await
+            .expect("Failed to set prices");
+        assert_eq!(first.version, 1);
+        assert_eq!(entries.len(), 2);
+
+        let (second, entries) = set_prices(&db, Actor::Api("token"), corporation_id, &[(NANITES, Some(400.)), (LIQUID_OZONE, None)])
+            .await
+            .expect("Failed to set prices");
+        assert_eq!(second.version, 2);
+        assert_eq!(second.actor, "api:token");
+        assert_eq!(entries.iter().map(|entry| (entry.resource_type_id, entry.price)).collect::<Vec<_>>(), vec![(NANITES, 400.)]);
+
+        let (book, entries) = PriceBook::find_with_entries(corporation_id, Some(1), &db).await.unwrap().unwrap();
+        assert_eq!(book.id, first.id);
+        assert_eq!(entries.iter().map(|entry| (entry.resource_type_id, entry.price)).collect::<Vec<_>>(), vec![(NANITES, 350.), (LIQUID_OZONE, 12.)]);
+        let (latest, _) = PriceBook::find_with_entries(corporation_id, None, &db).await.unwrap().unwrap();
+        assert_eq!(latest.id, second.id);
+
+        let events = AuditEvent::find_by_corporation(corporation_id, Some("price_book"), 10, &db).await.unwrap();
+        assert_eq!(events[0].action, "update");
+        assert_eq!(events[0].changed_fields(), vec![NANITES.to_string(), LIQUID_OZONE.to_string()]);
+        assert_eq!(events[1].action, "create");
+
+        let problem = register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, TEST_MEMBER_NAME, TEST_PROBLEM_NAME, b"Fuel 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        assert!(!problem.corp_prices);
+        let problem = set_problem_pricing(&db, TEST_ACTOR, corporation_id, problem.id, true)
+            .await
+            .expect("Failed to set problem pricing");
+        assert!(problem.corp_prices);
+    }
+
+    #[tokio::test]
+    async fn test_outposts_serving_several_problems() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+
+        let fuel = register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, TEST_MEMBER_NAME, TEST_PROBLEM_NAME, b"Fuel 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        let hull = register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, TEST_MEMBER_NAME, "Hull", b"Hull 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        let outpost = NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 };
+        let outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost)
+            .await
+            .expect("Failed to register outpost");
+        assign_member_outposts(&db, TEST_ACTOR, corporation_id, fuel.member_id, fuel.id)
+            .await
+            .expect("Failed to assign outposts");
+
+        link_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id)
+            .await
+            .expect("Failed to link outpost");
+        assert!(matches!(
+            link_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id).await,
+            Err(Error::Duplicate { entity: "outpost_problem", .. })
+        ));
+        let serving = Outpost::find_active_with_problems(corporation_id, &db).await.unwrap();
+        assert_eq!(serving.len(), 1);
+        assert_eq!(serving[0].1, vec![fuel.id, hull.id]);
+
+        let deadline = DateTimeUtc::from_timestamp(1_738_000_000, 0).unwrap();
+        let hull = schedule_problem(&db, TEST_ACTOR, corporation_id, hull.id, 2, Some(deadline))
+            .await
+            .expect("Failed to schedule problem");
+        assert_eq!((hull.priority, hull.deadline), (2, Some(deadline)));
+
+        let export = export_corporation(&db, corporation_id).await.unwrap();
+        assert_eq!(export.outposts[0].linked_problem_ids, vec![hull.id]);
+        assert_eq!(export.problems[1].priority, 2);
+
+        unlink_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id)
+            .await
+            .expect("Failed to unlink outpost");
+        let serving = Outpost::find_active_with_problems(corporation_id, &db).await.unwrap();
+        assert_eq!(serving[0].1, vec![fuel.id]);
+        assert!(matches!(
+            unlink_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id).await,
+            Err(Error::NotFound { entity: "outpost_problem", .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_retired_problem_leaves_the_portfolio() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+
+        let fuel = register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, TEST_MEMBER_NAME, TEST_PROBLEM_NAME, b"Fuel 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        let hull = register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, TEST_MEMBER_NAME, "Hull", b"Hull 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        let outpost = NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 };
+        let outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost)
+            .await
+            .expect("Failed to register outpost");
+        assign_member_outposts(&db, TEST_ACTOR, corporation_id, fuel.member_id, fuel.id)
+            .await
+            .expect("Failed to assign outposts");
+        link_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id)
+            .await
+            .expect("Failed to link outpost");
+
+        retire_problem(&db, TEST_ACTOR, corporation_id, hull.id)
+            .await
+            .expect("Failed to retire problem");
+        let serving = Outpost::find_active_with_problems(corporation_id, &db).await.unwrap();
+        assert_eq!(serving[0].1, vec![fuel.id]);
+        assert!(OutpostProblem::find_by_corporation(corporation_id, &db).await.unwrap().is_empty());
+
+        assert!(matches!(
+            link_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id).await,
+            Err(Error::Inactive { entity: "problem", .. })
+        ));
+
+        // Links are not restored with the problem, the outpost may serve another one by now.
+        restore_problem(&db, TEST_ACTOR, corporation_id, hull.id)
+            .await
+            .expect("Failed to restore problem");
+        let serving = Outpost::find_active_with_problems(corporation_id, &db).await.unwrap();
+        assert_eq!(serving[0].1, vec![fuel.id]);
+
+        link_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id)
+            .await
+            .expect("Failed to link outpost");
+        delete_outpost(&db, TEST_ACTOR, corporation_id, outpost.id)
+            .await
+            .expect("Failed to delete outpost");
+        assert!(OutpostProblem::find_by_corporation(corporation_id, &db).await.unwrap().is_empty());
+        assert!(matches!(
+            link_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, hull.id).await,
+            Err(Error::Inactive { entity: "outpost", .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_array_placements() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+        let outpost = NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 };
+        let outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost)
+            .await
+            .expect("Failed to register outpost");
+
+        place_arrays(&db, TEST_ACTOR, corporation_id, outpost.id, 40000002, 42001000031, 20)
+            .await
+            .expect("Failed to place arrays");
+        place_arrays(&db, TEST_ACTOR, corporation_id, outpost.id, 40000001, 42001000028, 6)
+            .await
+            .expect("Failed to place arrays");
+        let layout = place_arrays(&db, TEST_ACTOR, corporation_id, outpost.id, 40000002, 42001000031, 12)
+            .await
+            .expect("Failed to move arrays");
+        let counts: Vec<(i64, i32)> = layout.iter().map(|placement| (placement.planet_id, placement.count)).collect();
+        assert_eq!(counts, vec![(40000001, 6), (40000002, 12)]);
+
+        let layout = place_arrays(&db, TEST_ACTOR, corporation_id, outpost.id, 40000001, 42001000028, 0)
+            .await
+            .expect("Failed to remove arrays");
+        assert_eq!(layout.len(), 1);
+        assert_eq!(ArrayPlacement::find_by_outposts(vec![outpost.id], &db).await.unwrap(), layout);
+
+        let events = AuditEvent::find()
+            .filter(audit_event::Column::Entity.eq("array_placement"))
+            .count(&db)
+            .await
+            .unwrap();
+        assert_eq!(events, 4);
+    }
+
+    #[tokio::test]
+    async fn test_services_scoped_to_corporation() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+
+        let saved_alliance = new_alliance(&db, TEST_ALLIANCE_NAME)
+            .await
+            .expect("Failed to add alliance to database");
+        let saved_corporation = new_corporation(&db, TEST_CORPORATION_NAME, saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let other_corporation = new_corporation(&db, "Other Corporation", saved_alliance.last_insert_id)
+            .await
+            .expect("Failed to add corporation to database");
+        let corporation_id = saved_corporation.last_insert_id;
+        let other_id = other_corporation.last_insert_id;
+
+        let outpost = NewOutpost { name: TEST_OUTPOST_NAME, system: TEST_SYSTEM_NAME, planets: 12, arrays: 26 };
+        let outpost = register_outpost_for_user(&db, TEST_ACTOR, corporation_id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost)
+            .await
+            .expect("Failed to register outpost");
+        let problem = register_problem_for_user(&db, TEST_ACTOR, corporation_id, None, TEST_MEMBER_NAME, TEST_PROBLEM_NAME, b"Fuel 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+        let other_problem = register_problem_for_user(&db, TEST_ACTOR, other_id, None, TEST_MEMBER_NAME, TEST_PROBLEM_NAME, b"Fuel 1".to_vec())
+            .await
+            .expect("Failed to register problem");
+
+        let edit = NewOutpost { name: "Taken", system: TEST_SYSTEM_NAME, planets: 1, arrays: 1 };
+        assert!(matches!(
+            edit_outpost(&db, TEST_ACTOR, other_id, outpost.id, edit).await,
+            Err(Error::NotFound { entity: "outpost", .. })
+        ));
+        assert!(matches!(
+            delete_outpost(&db, TEST_ACTOR, other_id, outpost.id).await,
+            Err(Error::NotFound { entity: "outpost", .. })
+        ));
+        assert!(matches!(
+            place_arrays(&db, TEST_ACTOR, other_id, outpost.id, 40000001, 42001000028, 6).await,
+            Err(Error::NotFound { entity: "outpost", .. })
+        ));
+        assert!(matches!(
+            edit_problem(&db, TEST_ACTOR, other_id, problem.id, "Taken", vec![]).await,
+            Err(Error::NotFound { entity: "problem", .. })
+        ));
+        assert!(matches!(
+            set_problem_pricing(&db, TEST_ACTOR, other_id, problem.id, true).await,
+            Err(Error::NotFound { entity: "problem", .. })
+        ));
+        assert!(matches!(
+            schedule_problem(&db, TEST_ACTOR, other_id, problem.id, 2, None).await,
+            Err(Error::NotFound { entity: "problem", .. })
+        ));
+        assert!(matches!(
+            link_outpost(&db, TEST_ACTOR, corporation_id, outpost.id, other_problem.id).await,
+            Err(Error::NotFound { entity: "problem", .. })
+        ));
+        assert!(matches!(
+            assign_member_outposts(&db, TEST_ACTOR, corporation_id, problem.member_id, other_problem.id).await,
+            Err(Error::NotFound { entity: "problem", .. })
+        ));
+        let assigned = assign_member_outposts(&db, TEST_ACTOR, other_id, other_problem.member_id, other_problem.id)
+            .await
+            .expect("Failed to assign outposts");
+        assert!(assigned.is_empty());
+
+        let retrieved_outpost = Outpost::find_by_id(outpost.id).one(&db).await.unwrap().unwrap();
+        assert_eq!(retrieved_outpost, outpost);
+    }
+}
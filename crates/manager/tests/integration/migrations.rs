@@ -0,0 +1,124 @@
+// tests/migrations.rs
+
+#[cfg(test)]
+mod tests {
+
+    use sea_orm::*;
+    use sea_orm_migration::MigratorTrait;
+
+    use manager::database::MigrationPolicy;
+    use manager::environment::EnvironmentManager;
+    use manager::migrator::Migrator;
+
+    use crate::DatabaseManager;
+
+    /// Columns, indexes and constraints of every table but the migration history.
+    async fn schema(db: &DatabaseConnection) -> Vec<String> {
+        let queries = [
+            "SELECT table_name || '.' || column_name || ' ' || data_type || ' ' || is_nullable || ' ' || coalesce(column_default, '') \
+             FROM information_schema.columns \
+             WHERE table_schema = 'public' AND table_name <> 'seaql_migrations'",
+            "SELECT indexname || ' ' || indexdef FROM pg_indexes \
+             WHERE schemaname = 'public' AND tablename <> 'seaql_migrations'",
+            "SELECT conname || ' ' || pg_get_constraintdef(oid) FROM pg_constraint \
+             WHERE connamespace = 'public'::regnamespace AND conrelid <> 'seaql_migrations'::regclass",
+        ];
+        let mut schema = Vec::new();
+        for query in queries {
+            let rows = db
+                .query_all(Statement::from_string(db.get_database_backend(), query))
+                .await
+                .expect("Failed to read schema");
+            for row in rows {
+                schema.push(row.try_get_by_index::<String>(0).expect("schema row"));
+            }
+        }
+        schema.sort();
+        schema
+    }
+
+    #[tokio::test]
+    async fn test_migration_round_trips() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+        let migrated = schema(&db).await;
+
+        Migrator::reset(&db).await.expect("Failed to roll back every migration");
+        assert!(schema(&db).await.is_empty());
+
+        for migration in Migrator::migrations() {
+            let before = schema(&db).await;
+            Migrator::up(&db, Some(1)).await.expect("Failed to apply migration");
+            let after = schema(&db).await;
+            assert_ne!(before, after, "{} changed nothing", migration.name());
+
+            Migrator::down(&db, Some(1)).await.expect("Failed to roll back migration");
+            assert_eq!(schema(&db).await, before, "{} did not roll back cleanly", migration.name());
+
+            Migrator::up(&db, Some(1)).await.expect("Failed to reapply migration");
+            assert_eq!(schema(&db).await, after, "{} did not reapply cleanly", migration.name());
+        }
+
+        assert_eq!(schema(&db).await, migrated);
+    }
+
+    #[tokio::test]
+    async fn test_unique_indexes_keep_duplicate_rows() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+        let before_indexes = Migrator::migrations()
+            .iter()
+            .position(|migration| migration.name() == "m20250122_000001_create_unique_indexes")
+            .expect("unique index migration");
+
+        Migrator::reset(&db).await.expect("Failed to roll back every migration");
+        Migrator::up(&db, Some(before_indexes as u32)).await.expect("Failed to apply migrations");
+        db.execute_unprepared("INSERT INTO alliance (name) VALUES ('Duplicate'), ('Duplicate')")
+            .await
+            .expect("Failed to add duplicate alliances");
+        Migrator::up(&db, None).await.expect("Failed to apply the remaining migrations");
+
+        let rows = db
+            .query_all(Statement::from_string(db.get_database_backend(), "SELECT name FROM alliance ORDER BY id"))
+            .await
+            .expect("Failed to read alliances");
+        let names: Vec<String> = rows.into_iter().map(|row| row.try_get_by_index(0).expect("alliance name")).collect();
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[0], "Duplicate");
+        assert!(names[1].starts_with("Duplicate #"));
+    }
+
+    #[tokio::test]
+    async fn test_pending_migrations() {
+        let config = EnvironmentManager::load_config("test")
+            .await
+            .expect("Failed to load configuration");
+        let db = DatabaseManager::revision(&config)
+            .await
+            .expect("Failed to connect to database");
+        let latest = Migrator::migrations().last().expect("migrations").name().to_owned();
+
+        assert!(manager::database::DatabaseManager::pending(&db).await.unwrap().is_empty());
+        manager::database::DatabaseManager::start(&config, MigrationPolicy::Require)
+            .await
+            .expect("Failed to start with every migration applied");
+
+        Migrator::down(&db, Some(1)).await.expect("Failed to roll back migration");
+        assert_eq!(manager::database::DatabaseManager::pending(&db).await.unwrap(), vec![latest.clone()]);
+        let refused = manager::database::DatabaseManager::start(&config, MigrationPolicy::Require).await;
+        assert!(matches!(refused, Err(DbErr::Migration(message)) if message.contains(&latest)));
+
+        manager::database::DatabaseManager::start(&config, MigrationPolicy::Apply)
+            .await
+            .expect("Failed to start applying migrations");
+        assert!(manager::database::DatabaseManager::pending(&db).await.unwrap().is_empty());
+    }
+}
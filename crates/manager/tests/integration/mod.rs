@@ -66,10 +66,11 @@ impl DatabaseManager {
 pub const TEST_ALLIANCE_NAME: &str = "Alliance";
 pub const TEST_CAPSULEER_NAME: &str = "Capsuleer";
 pub const TEST_CORPORATION_NAME: &str = "Corporation";
+pub const TEST_GUILD_ID: i64 = 1328392047123456789;
 pub const TEST_MEMBER_NAME: &str = "Member";
 pub const TEST_OUTPOST_NAME: &str = "Outpost";
 pub const TEST_PROBLEM_NAME: &str = "Problem";
 pub const TEST_SKILL_NAME: &str = "Planetology";
 pub const TEST_SYSTEM_NAME: &str = "System";
 
-pub mod database;
\ No newline at end of file
+pub mod database;pub mod migrations;
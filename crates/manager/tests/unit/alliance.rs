@@ -9,6 +9,7 @@ fn test_create_alliance() {
         id: 1,
         name: TEST_ALLIANCE_NAME.to_string(),
         active: true,
+        executor_id: None,
     };
     
     assert_eq!(alliance.name, TEST_ALLIANCE_NAME.to_string());
@@ -0,0 +1,36 @@
+// tests/unit/audit_event.rs
+
+use manager::entities::audit_event;
+use sea_orm::prelude::DateTimeUtc;
+use serde_json::json;
+
+fn event(before: Option<serde_json::Value>, after: Option<serde_json::Value>) -> audit_event::Model {
+    audit_event::Model {
+        id: 1,
+        corporation_id: 1,
+        actor: "discord:Member".to_string(),
+        action: "update".to_string(),
+        entity: "outpost".to_string(),
+        entity_id: 1,
+        before,
+        after,
+        created_at: DateTimeUtc::default(),
+    }
+}
+
+#[test]
+fn test_changed_fields() {
+    let event = event(
+        Some(json!({ "name": "Home", "arrays": 22, "problem_id": null })),
+        Some(json!({ "name": "Home", "arrays": 26, "problem_id": 3 })),
+    );
+
+    assert_eq!(event.changed_fields(), vec!["arrays", "problem_id"]);
+}
+
+#[test]
+fn test_created_fields() {
+    let event = event(None, Some(json!({ "name": "Home", "arrays": 26 })));
+
+    assert_eq!(event.changed_fields(), vec!["arrays", "name"]);
+}
@@ -0,0 +1,39 @@
+// tests/unit/export.rs
+
+use manager::export::*;
+use serde_json::json;
+
+use crate::TEST_CORPORATION_NAME;
+use crate::TEST_MEMBER_NAME;
+
+#[test]
+fn test_export_json() {
+    let export = Export {
+        version: EXPORT_VERSION,
+        alliance: "Alliance".to_string(),
+        corporation: TEST_CORPORATION_NAME.to_string(),
+        members: vec![MemberExport { id: 7, name: TEST_MEMBER_NAME.to_string(), active: true }],
+        capsuleers: vec![],
+        skills: vec![],
+        problems: vec![],
+        outposts: vec![],
+        price_books: vec![PriceBookExport {
+            version: 1,
+            actor: format!("discord:{TEST_MEMBER_NAME}"),
+            created_at: Default::default(),
+            prices: [(42001000000, 120.5)].into_iter().collect(),
+        }],
+    };
+    let value = serde_json::to_value(&export).unwrap();
+
+    assert_eq!(value["version"], json!(EXPORT_VERSION));
+    assert_eq!(value["members"][0]["name"], json!(TEST_MEMBER_NAME));
+    assert_eq!(value["price_books"][0]["prices"]["42001000000"], json!(120.5));
+    assert_eq!(serde_json::from_value::<Export>(value).unwrap(), export);
+}
+
+#[test]
+fn test_on_collision_json() {
+    assert_eq!(serde_json::to_value(OnCollision::Rename).unwrap(), json!("rename"));
+    assert_eq!(serde_json::from_value::<OnCollision>(json!("merge")).unwrap(), OnCollision::Merge);
+}
@@ -0,0 +1,16 @@
+// tests/unit/guild.rs
+
+use crate::TEST_GUILD_ID;
+use manager::entities::guild;
+
+#[test]
+fn test_create_guild() {
+    let guild = guild::Model {
+        id: 1,
+        guild_id: TEST_GUILD_ID,
+        corporation_id: 1,
+        alliance_id: 1,
+    };
+
+    assert_eq!(guild.guild_id, TEST_GUILD_ID);
+}
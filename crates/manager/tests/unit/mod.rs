@@ -1,8 +1,11 @@
 // tests/unit/mod.rs
 
 pub mod alliance;
+pub mod audit_event;
 pub mod capsuleer;
 pub mod corporation;
+pub mod export;
+pub mod guild;
 pub mod member; 
 pub mod outpost;
 pub mod problem;
@@ -11,6 +14,7 @@ pub mod skill;
 pub const TEST_ALLIANCE_NAME: &str = "Alliance";
 pub const TEST_CAPSULEER_NAME: &str = "Capsuleer";
 pub const TEST_CORPORATION_NAME: &str = "Corporation";
+pub const TEST_GUILD_ID: i64 = 1328392047123456789;
 pub const TEST_MEMBER_NAME: &str = "Member";
 pub const TEST_OUTPOST_NAME: &str = "Outpost";
 pub const TEST_PROBLEM_NAME: &str = "Problem";
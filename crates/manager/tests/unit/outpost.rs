@@ -14,10 +14,32 @@ fn test_create_outpost() {
         arrays: 22,
         capsuleer_id: 1,
         problem_id: None,
+        active: true,
     };
     
     assert_eq!(outpost.name, TEST_OUTPOST_NAME.to_string());
     assert_eq!(outpost.system, TEST_SYSTEM_NAME.to_string());
     assert_eq!(outpost.planets, 12);
     assert_eq!(outpost.arrays, 22);
+}
+
+#[test]
+fn test_deactivate_outpost() {
+    let mut outpost = outpost::Model {
+        id: 1,
+        name: TEST_OUTPOST_NAME.to_string(),
+        system: TEST_SYSTEM_NAME.to_string(),
+        planets: 12,
+        arrays: 22,
+        capsuleer_id: 1,
+        problem_id: Some(1),
+        active: true,
+    };
+
+    outpost.deactivate();
+    assert!(!outpost.active);
+    assert_eq!(outpost.problem_id, None);
+
+    outpost.restore();
+    assert!(outpost.active);
 }
\ No newline at end of file
@@ -13,6 +13,9 @@ fn test_create_problem() {
         member_id: 1,
         corporation_id: 1,
         alliance_id: None,
+        corp_prices: false,
+        priority: 1,
+        deadline: None,
     };
     
     assert_eq!(problem.name, TEST_PROBLEM_NAME);
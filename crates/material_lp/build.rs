@@ -1,23 +1,54 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
+/// Every dataset `data.rs` loads, each one missing is reported by the build rather than by the first command needing it.
+const DATASETS: [&str; 7] = [
+    "celestials.json",
+    "all_items_info.json",
+    "constellations_r.json",
+    "regions_r.json",
+    "systems_r.json",
+    "stargates_r.json",
+    "planet_exploit_resource.json",
+];
+
 fn main() {
     let target = PathBuf::from("target");
+    println!("cargo:rerun-if-changed=data.tar.gz");
+    println!("cargo:rerun-if-changed=data");
 
     Command::new("tar")
         .args(&["-xzvf", "data.tar.gz", "-C", target.to_str().unwrap()])
         .status()
         .expect("Failed to extract tar.gz file");
 
+    // Datasets added after the archive was cut are kept gzipped in data/ next to it.
+    fs::create_dir_all("target/data").expect("Failed to create target/data");
+    if let Ok(entries) = fs::read_dir("data") {
+        for entry in entries {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|s| s.to_str()) == Some("gz") {
+                fs::copy(&path, Path::new("target/data").join(path.file_name().unwrap()))
+                    .expect("Failed to copy dataset");
+            }
+        }
+    }
+
     for entry in fs::read_dir("target/data").unwrap() {
         let entry = entry.unwrap();
         let path = entry.path();
         if path.extension().and_then(|s| s.to_str()) == Some("gz") {
             Command::new("gunzip")
-                .arg(path.to_str().unwrap())
+                .args(["-f", path.to_str().unwrap()])
                 .status()
                 .expect("Failed to unzip file");
         }
     }
+
+    for dataset in DATASETS {
+        if !Path::new("target/data").join(dataset).exists() {
+            println!("cargo:warning=Missing dataset {dataset}, add it to data.tar.gz or gzipped to data/");
+        }
+    }
 }
@@ -8,7 +8,7 @@ macro_rules! assert_materials_available {
             let mut material_found = false;
 
             for outpost in $outposts.iter() {
-                let celestial_resources = celestial_resources_by_outpost(outpost.clone());
+                let celestial_resources = celestial_resources_by_outpost(outpost.clone()).unwrap_or_default();
                 let matching_celestial_resources: Vec<_> = celestial_resources
                 .iter()
                 .filter(|celestial_resource| celestial_resource.resource_type_id == material.resource_type_id)
@@ -2,10 +2,12 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
-use crate::resource::CelestialResource;
+use crate::Plan;
 
+/// Clones share the store, so a plan solved on another thread is cached for everyone.
+#[derive(Clone)]
 pub struct Cache {
-    store: Arc<Mutex<HashMap<String, (Instant, Result<Vec<(CelestialResource, f64)>, String>)>>>,
+    store: Arc<Mutex<HashMap<String, (Instant, Result<Plan, String>)>>>,
     ttl: Duration,
 }
 
@@ -17,7 +19,7 @@ impl Cache {
         }
     }
 
-    pub fn get(&self, key: &str) -> Option<Result<Vec<(CelestialResource, f64)>, String>> {
+    pub fn get(&self, key: &str) -> Option<Result<Plan, String>> {
         let store = self.store.lock().unwrap();
         if let Some((timestamp, value)) = store.get(key) {
             if timestamp.elapsed() < self.ttl {
@@ -27,7 +29,7 @@ impl Cache {
         None
     }
 
-    pub fn set(&self, key: String, value: Result<Vec<(CelestialResource, f64)>, String>) {
+    pub fn set(&self, key: String, value: Result<Plan, String>) {
         let mut store = self.store.lock().unwrap();
         store.insert(key, (Instant::now(), value));
     }
@@ -1,8 +1,11 @@
 mod data {
     use serde::{Deserialize, Serialize};
-    use std::collections::HashMap;
+    use std::collections::{BTreeMap, BTreeSet, HashMap};
     use lazy_static::lazy_static;
 
+    use crate::route::{distances, shortest_path};
+    use crate::search::best_match;
+
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
     pub struct Celestial {
         pub constellation_id: i64,
@@ -33,6 +36,48 @@ mod data {
             pub zhcn_name: Box<str>,
     }
 
+    impl Constellation {
+        /// Every localized name, empty where the data has no translation.
+        pub fn names(&self) -> [&str; 9] {
+            [
+                &self.en_name, &self.zh_name, &self.de_name, &self.fr_name, &self.ja_name,
+                &self.por_name, &self.ru_name, &self.spa_name, &self.zhcn_name,
+            ]
+        }
+    }
+
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Default)]
+    pub struct Region {
+            #[serde(default)]
+            pub zh_name: Box<str>,
+            #[serde(default)]
+            pub en_name: Box<str>,
+            #[serde(default)]
+            pub de_name: Box<str>,
+            #[serde(default)]
+            pub fr_name: Box<str>,
+            #[serde(default)]
+            pub ja_name: Box<str>,
+            #[serde(default)]
+            pub por_name: Box<str>,
+            #[serde(default)]
+            pub ru_name: Box<str>,
+            #[serde(default)]
+            pub spa_name: Box<str>,
+            #[serde(default)]
+            pub zhcn_name: Box<str>,
+    }
+
+    impl Region {
+        /// Every localized name, empty where the data has no translation.
+        pub fn names(&self) -> [&str; 9] {
+            [
+                &self.en_name, &self.zh_name, &self.de_name, &self.fr_name, &self.ja_name,
+                &self.por_name, &self.ru_name, &self.spa_name, &self.zhcn_name,
+            ]
+        }
+    }
+
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
     pub struct Item {
             #[serde(default)]
@@ -57,6 +102,16 @@ mod data {
             pub kr_name: Box<str>,
     }
 
+    impl Item {
+        /// Every localized name, empty where the data has no translation.
+        pub fn names(&self) -> [&str; 10] {
+            [
+                &self.en_name, &self.zh_name, &self.de_name, &self.fr_name, &self.ja_name,
+                &self.por_name, &self.ru_name, &self.spa_name, &self.zhcn_name, &self.kr_name,
+            ]
+        }
+    }
+
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
     pub struct System {
             #[serde(default)]
@@ -81,6 +136,16 @@ mod data {
             pub constellation: Box<i64>,
     }
 
+    impl System {
+        /// Every localized name, empty where the data has no translation.
+        pub fn names(&self) -> [&str; 9] {
+            [
+                &self.en_name, &self.zh_name, &self.de_name, &self.fr_name, &self.ja_name,
+                &self.por_name, &self.ru_name, &self.spa_name, &self.zhcn_name,
+            ]
+        }
+    }
+
     #[derive(Clone, Debug, Deserialize, PartialEq, Serialize, Default)]
     pub struct Resource {
         pub init_output: f64,
@@ -99,9 +164,53 @@ mod data {
     lazy_static! {
         pub static ref CELESTIALS: HashMap<i64, Celestial> = load_celestials().expect("Failed to load celestials");
         pub static ref CONSTELLATIONS: HashMap<i64, Constellation> = load_constellations().expect("Failed to load constellations.");
+        pub static ref REGIONS: HashMap<i64, Region> = load_regions().expect("Failed to load regions.");
+        /// The constellations of every region, as the celestials place them.
+        pub static ref CONSTELLATIONS_BY_REGION: BTreeMap<i64, BTreeSet<i64>> = CELESTIALS
+            .values()
+            .fold(BTreeMap::new(), |mut regions, celestial| {
+                regions.entry(celestial.region_id).or_insert_with(BTreeSet::new).insert(celestial.constellation_id);
+                regions
+            });
         pub static ref ITEMS: HashMap<i64, Item> = load_items().expect("Failed to load items.");
         pub static ref SYSTEMS: HashMap<i64, System> = load_systems().expect("Failed to load systems.");
+        /// The systems each system has a stargate to.
+        pub static ref STARGATES: HashMap<i64, Vec<i64>> = load_stargates().expect("Failed to load stargates.");
         pub static ref PLANETS: HashMap<i64, Planet> = load_planet_exploit_resource().expect("Failed to load planets");
+        /// Every resource some planet produces.
+        pub static ref RESOURCE_TYPES: BTreeSet<i64> = PLANETS
+            .values()
+            .flat_map(|planet| planet.resource_info.values().map(|resource| resource.resource_type_id))
+            .collect();
+    }
+
+    /// Filters over the resources of every planet, each one left out matches everything.
+    #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+    pub struct PlanetQuery {
+        pub resource_type_id: Option<i64>,
+        pub region_id: Option<i64>,
+        pub constellation_id: Option<i64>,
+        pub system_id: Option<i64>,
+        pub min_output: Option<f64>,
+        pub min_richness: Option<i64>,
+    }
+
+    /// A resource of a planet with the names of where it is.
+    #[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+    pub struct PlanetRow {
+        pub planet_id: i64,
+        pub region_id: i64,
+        pub region: Box<str>,
+        pub constellation_id: i64,
+        pub constellation: Box<str>,
+        pub system_id: i64,
+        pub system: Box<str>,
+        pub celestial_index: i64,
+        pub resource_type_id: i64,
+        pub resource: Box<str>,
+        pub init_output: f64,
+        pub richness_index: i64,
+        pub richness_value: i64,
     }
 
     pub fn load_celestials() -> Result<HashMap<i64, Celestial>, Box<dyn std::error::Error>> {
@@ -142,6 +251,42 @@ mod data {
         CONSTELLATIONS.get(&key)
     }
 
+    pub fn load_regions() -> Result<HashMap<i64, Region>, Box<dyn std::error::Error>> {
+        let out_dir = "./target".to_string();
+        let file_path = std::path::Path::new(&out_dir).join("data/regions_r.json");
+        let region_data = std::fs::read_to_string(file_path)?;
+        let regions: HashMap<i64, Region> = serde_json::from_str(&region_data)?;
+        Ok(regions)
+    }
+
+    pub fn get_region(key: i64) -> Option<&'static Region> {
+        REGIONS.get(&key)
+    }
+
+    pub fn find_region(name: &str) -> Option<&'static i64> {
+        for (key, region) in REGIONS.iter() {
+            if region.en_name.as_ref() == name {
+                return Some(key);
+            }
+        }
+        None
+    }
+
+    /// The constellations of a region, none when the region is unknown.
+    pub fn constellations_by_region(region_id: i64) -> Vec<i64> {
+        CONSTELLATIONS_BY_REGION
+            .get(&region_id)
+            .map(|constellations| constellations.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    pub fn region_by_constellation(constellation_id: i64) -> Option<i64> {
+        CONSTELLATIONS_BY_REGION
+            .iter()
+            .find(|(_, constellations)| constellations.contains(&constellation_id))
+            .map(|(region_id, _)| *region_id)
+    }
+
     pub fn load_planet_exploit_resource() -> Result<HashMap<i64, Planet>, Box<dyn std::error::Error>> {
         let out_dir = "./target".to_string();
         let file_path = std::path::Path::new(&out_dir).join("data/planet_exploit_resource.json");
@@ -163,6 +308,29 @@ mod data {
         Ok(systems)
     }
 
+    pub fn load_stargates() -> Result<HashMap<i64, Vec<i64>>, Box<dyn std::error::Error>> {
+        let out_dir = "./target".to_string();
+        let file_path = std::path::Path::new(&out_dir).join("data/stargates_r.json");
+        let stargate_data = std::fs::read_to_string(file_path)?;
+        let stargates: HashMap<i64, Vec<i64>> = serde_json::from_str(&stargate_data)?;
+        Ok(stargates)
+    }
+
+    /// Jumps between two systems, `None` when no stargates connect them.
+    pub fn jumps(from: i64, to: i64) -> Option<usize> {
+        shortest_path(&STARGATES, from, to).map(|path| path.len() - 1)
+    }
+
+    /// The systems of a shortest route between two systems, both ends included.
+    pub fn route(from: i64, to: i64) -> Option<Vec<i64>> {
+        shortest_path(&STARGATES, from, to)
+    }
+
+    /// Jumps from the system to every system reachable from it.
+    pub fn jumps_from(origin: i64) -> HashMap<i64, usize> {
+        distances(&STARGATES, origin)
+    }
+
     pub fn find_system(name: &str) -> Option<&'static i64> {
         for (key, system) in SYSTEMS.iter() {
             if system.en_name.as_ref() == name {
@@ -173,9 +341,9 @@ mod data {
     }
 
     pub fn find_constellation_by_system(name: &str) -> Option<&'static i64> {
-        let system_id = find_system(&name);
+        let system_id = find_system(name)?;
         for (_, celestial) in CELESTIALS.iter() {
-            if celestial.solar_system_id == *system_id.unwrap() {
+            if celestial.solar_system_id == *system_id {
                 return Some(&celestial.constellation_id);
             }
         }
@@ -205,6 +373,98 @@ mod data {
         None
     }
 
+    /// Systems whose name in any language fuzzy matches the query, paired with the name that matched.
+    pub fn search_systems(query: &str, limit: usize) -> Vec<(&'static str, &'static System)> {
+        let mut matches: Vec<(u32, &'static str, &'static System)> = SYSTEMS
+            .values()
+            .filter_map(|system| best_match(query, system.names()).map(|(score, name)| (score, name, system)))
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.en_name.cmp(&b.2.en_name)));
+        matches.into_iter().take(limit).map(|(_, name, system)| (name, system)).collect()
+    }
+
+    /// Constellations whose name in any language fuzzy matches the query, paired with the name that matched.
+    pub fn search_constellations(query: &str, limit: usize) -> Vec<(&'static str, &'static Constellation)> {
+        let mut matches: Vec<(u32, &'static str, &'static Constellation)> = CONSTELLATIONS
+            .values()
+            .filter_map(|constellation| best_match(query, constellation.names()).map(|(score, name)| (score, name, constellation)))
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.en_name.cmp(&b.2.en_name)));
+        matches.into_iter().take(limit).map(|(_, name, constellation)| (name, constellation)).collect()
+    }
+
+    /// Regions whose name in any language fuzzy matches the query, paired with the name that matched.
+    pub fn search_regions(query: &str, limit: usize) -> Vec<(&'static str, &'static Region)> {
+        let mut matches: Vec<(u32, &'static str, &'static Region)> = REGIONS
+            .values()
+            .filter_map(|region| best_match(query, region.names()).map(|(score, name)| (score, name, region)))
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.en_name.cmp(&b.2.en_name)));
+        matches.into_iter().take(limit).map(|(_, name, region)| (name, region)).collect()
+    }
+
+    /// Resources whose name in any language fuzzy matches the query, paired with the name that matched.
+    pub fn search_resources(query: &str, limit: usize) -> Vec<(&'static str, &'static Item)> {
+        let mut matches: Vec<(u32, &'static str, &'static Item)> = RESOURCE_TYPES
+            .iter()
+            .filter_map(|resource_type_id| get_item(*resource_type_id))
+            .filter_map(|item| best_match(query, item.names()).map(|(score, name)| (score, name, item)))
+            .collect();
+        matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.2.en_name.cmp(&b.2.en_name)));
+        matches.into_iter().take(limit).map(|(_, name, item)| (name, item)).collect()
+    }
+
+    /// The first `limit` planet resources matching the query, highest output first.
+    pub fn query_planets(query: &PlanetQuery, limit: usize) -> Vec<PlanetRow> {
+        let mut rows: Vec<PlanetRow> = Vec::new();
+        for planet in PLANETS.values() {
+            let Some(celestial) = get_celestial(planet.planet_id) else {
+                continue;
+            };
+            if query.region_id.is_some_and(|region_id| region_id != celestial.region_id)
+                || query.constellation_id.is_some_and(|constellation_id| constellation_id != celestial.constellation_id)
+                || query.system_id.is_some_and(|system_id| system_id != celestial.solar_system_id) {
+                continue;
+            }
+            for resource in planet.resource_info.values() {
+                if query.resource_type_id.is_some_and(|resource_type_id| resource_type_id != resource.resource_type_id)
+                    || query.min_output.is_some_and(|min_output| resource.init_output < min_output)
+                    || query.min_richness.is_some_and(|min_richness| resource.richness_index < min_richness) {
+                    continue;
+                }
+                rows.push(PlanetRow {
+                    planet_id: planet.planet_id,
+                    region_id: celestial.region_id,
+                    region: get_region(celestial.region_id)
+                        .map(|region| region.en_name.clone())
+                        .unwrap_or_default(),
+                    constellation_id: celestial.constellation_id,
+                    constellation: get_constellation(celestial.constellation_id)
+                        .map(|constellation| constellation.en_name.clone())
+                        .unwrap_or_default(),
+                    system_id: celestial.solar_system_id,
+                    system: get_system(celestial.solar_system_id)
+                        .map(|system| system.en_name.clone())
+                        .unwrap_or_default(),
+                    celestial_index: celestial.celestial_index,
+                    resource_type_id: resource.resource_type_id,
+                    resource: get_item(resource.resource_type_id)
+                        .map(|item| item.en_name.clone())
+                        .unwrap_or_default(),
+                    init_output: resource.init_output,
+                    richness_index: resource.richness_index,
+                    richness_value: resource.richness_value,
+                });
+            }
+        }
+        rows.sort_by(|a, b| b.init_output
+            .total_cmp(&a.init_output)
+            .then(a.planet_id.cmp(&b.planet_id))
+            .then(a.resource_type_id.cmp(&b.resource_type_id)));
+        rows.truncate(limit);
+        rows
+    }
+
     pub fn find_item(name: &str) -> Option<&'static i64> {
         for (key, item) in ITEMS.iter() {
             if item.en_name.as_ref() == name {
@@ -215,8 +475,10 @@ mod data {
     }
 }
 
-pub use data::{get_item, get_celestial, get_constellation, get_system};
-pub use data::{system_by_planet, find_item, find_constellation, find_system, find_constellation_by_system, slice_celestials};
-pub use data::{Item, Constellation, Celestial, Resource, System, Planet};
-pub use data::{CELESTIALS, CONSTELLATIONS, ITEMS, SYSTEMS, PLANETS};
+pub use data::{get_item, get_celestial, get_constellation, get_region, get_system};
+pub use data::{system_by_planet, find_item, find_constellation, find_region, find_system, find_constellation_by_system, slice_celestials};
+pub use data::{constellations_by_region, region_by_constellation, jumps, jumps_from, route};
+pub use data::{search_systems, search_constellations, search_regions, search_resources, query_planets};
+pub use data::{Item, Constellation, Region, Celestial, Resource, System, Planet, PlanetQuery, PlanetRow};
+pub use data::{CELESTIALS, CONSTELLATIONS, CONSTELLATIONS_BY_REGION, ITEMS, REGIONS, SYSTEMS, STARGATES, PLANETS, RESOURCE_TYPES};
 
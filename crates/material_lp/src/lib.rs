@@ -2,20 +2,130 @@ pub mod assertions;
 pub mod cache;
 pub mod data;
 pub mod objective;
+pub mod portfolio;
+pub mod price;
 pub mod problem;
 pub mod resource;
+pub mod route;
+pub mod schedule;
+pub mod scout;
+pub mod search;
+pub mod sensitivity;
+pub mod summary;
 
 use std::collections::HashMap;
-use good_lp::solvers::Solution;
+use std::hash::{DefaultHasher, Hash, Hasher};
 
 use objective::{map_objective, map_constellation};
-use problem::{ResourceHarvestProblem};
+use portfolio::{Demand, Portfolio, PortfolioPlan, Site};
+use problem::{Delivery, ResourceHarvestProblem, Shift, Stability};
 use resource::{Material, CelestialResource};
+use schedule::{Period, Reanchoring, Schedule, SchedulePlan};
+use sensitivity::{analyse, Sensitivity};
 use manager::database::DatabaseConnection;
-use manager::entities::outpost;
+use manager::entities::{array_placement, outpost};
 use manager::entities::prelude::*;
 use manager::*;
 
+/// Liquid Ozone burnt by an outpost's arrays.
+pub const FUEL_TYPE_ID: i64 = 42002000014;
+const FUEL_GJ_PER_UNIT: f64 = 13.;
+const FUEL_GJ_NEEDED: f64 = 18000.;
+
+/// Units of fuel the outposts burn over the days.
+pub fn fuel_required(days: f64, outposts: f64) -> f64 {
+    FUEL_GJ_NEEDED / FUEL_GJ_PER_UNIT * 24. * days * outposts
+}
+
+/// The demand every portfolio carries for the fuel its outposts burn, secured before any problem.
+pub const FUEL_DEMAND: &str = "Outpost fuel";
+
+/// A system the harvest is hauled to for building.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Destination {
+    pub system: String,
+    /// Planets further than this many jumps get no arrays.
+    pub max_jumps: Option<usize>,
+    /// ISK every unit loses for each jump it is hauled.
+    pub isk_per_unit_jump: f64,
+}
+
+impl Destination {
+    /// Jumps from every planet of the resources to the system, the system must exist.
+    pub fn delivery(&self, resources: &[CelestialResource]) -> Result<Delivery, String> {
+        let system_id = data::find_system(&self.system).ok_or(format!("{} is not found in Eve", self.system))?;
+        let jumps = data::jumps_from(*system_id);
+        let jumps = resources
+            .iter()
+            .filter_map(|resource| {
+                let celestial = data::get_celestial(resource.planet_id)?;
+                Some((resource.planet_id, *jumps.get(&celestial.solar_system_id)?))
+            })
+            .collect();
+        Ok(Delivery { jumps, max_jumps: self.max_jumps, isk_per_unit_jump: self.isk_per_unit_jump })
+    }
+}
+
+/// The key a plan is cached under, telling apart corporations, their outposts and material lists valued at different prices.
+pub fn plan_key(
+    corporation_id: i32,
+    outposts: &[outpost::Model],
+    materials: &[Material],
+    days: f64,
+    destination: Option<&Destination>,
+    stability: Option<&Stability>,
+    sensitivity: bool,
+) -> String {
+    let mut hasher = DefaultHasher::new();
+    let mut sites: Vec<_> = outposts
+        .iter()
+        .map(|outpost| (outpost.id, outpost.system.as_str(), outpost.planets, outpost.arrays))
+        .collect();
+    sites.sort();
+    sites.hash(&mut hasher);
+    let outposts = format!("{corporation_id}-{}:{:x}", outposts.len(), hasher.finish());
+    let mut hasher = DefaultHasher::new();
+    for material in materials {
+        (material.resource_type_id, material.quantity, material.valuation.to_bits()).hash(&mut hasher);
+    }
+    let materials = format!("{}:{:x}", materials.len(), hasher.finish());
+    let key = match destination {
+        Some(destination) => format!(
+            "{outposts}-{materials}-{days}-{}-{:?}-{}",
+            destination.system, destination.max_jumps, destination.isk_per_unit_jump,
+        ),
+        None => format!("{outposts}-{materials}-{days}"),
+    };
+    let key = match stability {
+        Some(stability) => {
+            let mut hasher = DefaultHasher::new();
+            let mut current: Vec<_> = stability.current.iter().map(|(key, arrays)| (*key, arrays.to_bits())).collect();
+            current.sort();
+            current.hash(&mut hasher);
+            format!("{key}-{}:{:x}", stability.isk_per_move, hasher.finish())
+        }
+        None => key,
+    };
+    if sensitivity { format!("{key}-sensitivity") } else { key }
+}
+
+/// The arrays the outposts have anchored today, each one anchored elsewhere costing `isk_per_move`.
+pub fn current_layout(placements: &[array_placement::Model], isk_per_move: f64) -> Stability {
+    let mut current = HashMap::new();
+    for placement in placements {
+        *current.entry((placement.planet_id, placement.resource_type_id)).or_insert(0.) += placement.count as f64;
+    }
+    Stability { current, isk_per_move }
+}
+
+/// Arrays placed on each resource with what moving the problem's constraints is worth.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Plan {
+    pub production: Vec<(CelestialResource, f64)>,
+    /// Only worked out when asked for, it solves the problem again for every constraint it prices.
+    pub sensitivity: Option<Sensitivity>,
+}
+
 pub fn outposts_per_constellation(outposts: &Vec<outpost::Model>) -> Result<Vec<(String, i32)>, anyhow::Error> {
     let mut outpost_counts: HashMap<String, i32> = HashMap::new();
     for outpost in outposts {
@@ -54,21 +164,26 @@ pub async fn create_outpost(
         .unwrap()
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn solve_for_constellation(
+    corporation_id: i32,
     outposts: Vec<outpost::Model>,
     materials: Vec<Material>,
     days: f64,
+    destination: Option<&Destination>,
+    stability: Option<&Stability>,
+    sensitivity: bool,
     cache: &cache::Cache,
-) -> Result<Vec<(CelestialResource, f64)>, String> {
-    let key = format!("{}-{}-{}", outposts.len(), materials.len(), days);
+) -> Result<Plan, String> {
+    let key = plan_key(corporation_id, &outposts, &materials, days, destination, stability, sensitivity);
     if let Some(result) = cache.get(&key) {
-        println!("Cache hit: {}", key);
+        log::debug!("Cache hit: {}", key);
         return result;
     }
-    println!("Cache miss: {}", key);
+    log::debug!("Cache miss: {}", key);
     let outpost_count = outposts.len() as f64;
     let (minimum_output, value) = map_objective(materials);
-    let (available_key, available_planet, celestial_resources) = map_constellation(outposts);
+    let (available_key, available_planet, celestial_resources) = map_constellation(outposts)?;
     let mut harvest = ResourceHarvestProblem::new(
         available_key,
         available_planet,
@@ -76,22 +191,68 @@ pub fn solve_for_constellation(
         value,
         days,
     );
-    let variables: Vec<_> = celestial_resources
-        .clone()
-        .into_iter()
-        .map(|r| harvest.add_resource(r))
-        .collect();
-    
-    harvest.add_fuel(42002000014, 13., 18000., outpost_count);
+    for resource in celestial_resources.iter().cloned() {
+        harvest.add_resource(resource);
+    }
 
-    let best_production = harvest.best_production()?;
+    harvest.add_fuel(FUEL_TYPE_ID, FUEL_GJ_PER_UNIT, FUEL_GJ_NEEDED, outpost_count);
+    if let Some(destination) = destination {
+        harvest.deliver(destination.delivery(&celestial_resources)?);
+    }
+    if let Some(stability) = stability {
+        harvest.stabilize(stability.clone());
+    }
 
-    let resource_quantities: Vec<_> = variables.iter().map(|&v| best_production.value(v)).collect();
-    let result: Vec<_> = celestial_resources
-        .iter()
-        .zip(resource_quantities.iter().cloned())
-        .map(|(resource, quantity)| (resource.clone(), quantity))
-        .collect();
-    cache.set(key, Ok(result.clone()));
-    Ok(result)
-}
\ No newline at end of file
+    let base = harvest
+        .optimum(&Shift::None)?
+        .ok_or("Error solving the problem: Infeasible".to_string())?;
+    let sensitivity = if sensitivity { Some(analyse(&harvest, &base)?) } else { None };
+    let production: Vec<_> = celestial_resources.into_iter().zip(base.arrays).collect();
+    let plan = Plan { production, sensitivity };
+    cache.set(key, Ok(plan.clone()));
+    Ok(plan)
+}
+
+/// Share the outposts between the demands, each outpost serving only the problems it is linked to.
+pub fn solve_portfolio(outposts: Vec<(outpost::Model, Vec<String>)>, demands: Vec<Demand>) -> Result<PortfolioPlan, String> {
+    let horizon = demands.iter().map(|demand| demand.days).fold(0., f64::max);
+    let mut portfolio = Portfolio::new();
+    portfolio.add_demand(Demand {
+        name: FUEL_DEMAND.to_string(),
+        priority: 0,
+        days: horizon,
+        minimum_output: HashMap::from([(FUEL_TYPE_ID, fuel_required(horizon, outposts.len() as f64))]),
+        value: Default::default(),
+    });
+    for demand in demands {
+        portfolio.add_demand(demand);
+    }
+    for (outpost, problems) in outposts {
+        let mut serves = problems;
+        serves.push(FUEL_DEMAND.to_string());
+        portfolio.add_site(Site {
+            id: outpost.id,
+            name: outpost.name.clone(),
+            planets: outpost.planets,
+            arrays: outpost.arrays,
+            serves,
+            resources: resource::celestial_resources_by_outpost(outpost)?,
+        });
+    }
+    portfolio.solve()
+}
+
+/// Place the outposts' arrays period by period, each period also harvesting the fuel it burns.
+pub fn solve_schedule(outposts: Vec<outpost::Model>, periods: Vec<Period>, reanchoring: Reanchoring) -> Result<SchedulePlan, String> {
+    let outpost_count = outposts.len() as f64;
+    let (available_key, available_planet, celestial_resources) = map_constellation(outposts)?;
+    let mut schedule = Schedule::new(available_key, available_planet, reanchoring);
+    for resource in celestial_resources {
+        schedule.add_resource(resource);
+    }
+    for mut period in periods {
+        *period.minimum_output.entry(FUEL_TYPE_ID).or_insert(0.) += fuel_required(period.days, outpost_count);
+        schedule.add_period(period);
+    }
+    schedule.solve()
+}
@@ -0,0 +1,96 @@
+// src/main.rs
+
+use std::path::PathBuf;
+
+use arboard::Clipboard;
+use clap::{Parser, Subcommand};
+use futures::executor::block_on;
+use tabled::{Table, Tabled};
+
+use material_lp::data::{find_region, get_item};
+use material_lp::objective::parse_decomposed_list;
+use material_lp::price::{PriceFile, PriceSource};
+use material_lp::scout::{scout, Prospective, DEFAULT_ARRAYS, DEFAULT_PLANETS};
+
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Rank the constellations to anchor new outposts in for a material list
+    Scout {
+        /// Outposts to anchor in the constellation
+        #[arg(long)]
+        outposts: i32,
+        /// Planets each outpost harvests
+        #[arg(long, default_value_t = DEFAULT_PLANETS)]
+        planets: i32,
+        /// Arrays on each planet
+        #[arg(long, default_value_t = DEFAULT_ARRAYS)]
+        arrays: i32,
+        /// Days between fuels
+        #[arg(long, default_value_t = 7.)]
+        days: f64,
+        /// Constellations listed
+        #[arg(long, default_value_t = 10)]
+        top: usize,
+        /// Only constellations of this region
+        #[arg(long)]
+        region: Option<String>,
+        /// Exported material list, read from the clipboard when omitted
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// JSON or CSV prices valuing the materials instead of the list's own valuations
+        #[arg(long)]
+        prices: Option<PathBuf>,
+    },
+}
+
+#[derive(Tabled)]
+struct Row {
+    constellation: String,
+    system: String,
+    coverage: String,
+    isk_per_day: String,
+    missing: String,
+}
+
+fn main() -> Result<(), anyhow::Error> {
+    match Cli::parse().command {
+        Command::Scout { outposts, planets, arrays, days, top, region, file, prices } => {
+            let region_id = match region {
+                Some(region) => Some(*find_region(&region).ok_or_else(|| anyhow::anyhow!("No region is named {region}"))?),
+                None => None,
+            };
+            let text = match file {
+                Some(file) => std::fs::read_to_string(file)?,
+                None => Clipboard::new()?.get_text()?,
+            };
+            let mut materials = parse_decomposed_list(&text)?;
+            if let Some(path) = prices {
+                block_on(PriceFile { path }.snapshot())?.revalue(&mut materials);
+            }
+            let prospective = Prospective { outposts, planets, arrays, days };
+            let rows = scout(&materials, &prospective, region_id, top)
+                .into_iter()
+                .map(|prospect| Row {
+                    constellation: prospect.constellation,
+                    system: prospect.system,
+                    coverage: format!("{:.0}%", prospect.evaluation.coverage * 100.),
+                    isk_per_day: format!("{:.0}", prospect.evaluation.isk_per_day),
+                    missing: prospect.evaluation.missing
+                        .iter()
+                        .map(|resource_type_id| get_item(*resource_type_id)
+                            .map(|item| item.en_name.to_string())
+                            .unwrap_or_else(|| resource_type_id.to_string()))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                });
+            println!("{}", Table::new(rows));
+        }
+    }
+    Ok(())
+}
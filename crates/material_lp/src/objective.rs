@@ -129,45 +129,50 @@ mod objective {
         (minimum_output, value)
     }
     
-    pub fn map_constellation(outposts: Vec<outpost::Model>) -> (HashMap<String, i32>, HashMap<i64, i32>, Vec<CelestialResource>) {
+    /// Arrays available per constellation and per planet, and the resources they can be placed on.
+    pub type Availability = (HashMap<String, i32>, HashMap<i64, i32>, Vec<CelestialResource>);
+
+    pub fn map_constellation(outposts: Vec<outpost::Model>) -> Result<Availability, String> {
         let mut available_constellation: HashMap<String, i32> = HashMap::new();
         let mut available_planet: HashMap<i64, i32> = HashMap::new();
         let mut available_celestial_resource: Vec<CelestialResource> = Vec::new();
         
         for outpost in outposts {
-            let constellation_id = find_constellation_by_system(&outpost.system).expect("Failed to find constellation by system");
-            let constellation = get_constellation(*constellation_id);
-            match available_planets_by_outpost(outpost.clone(), outpost.arrays) {
-                Ok(planets) => {
-                    *available_constellation
-                        .entry(constellation.unwrap().en_name.to_string())
-                        .or_insert(0) += outpost.arrays * outpost.planets;
-                    for (key, _value) in planets {
-                        *available_planet.entry(key).or_insert(0) += outpost.arrays;
-                    }
-                    available_celestial_resource.extend(celestial_resources_by_constellation(*constellation_id))
-                }
-                Err(err) => {
-                    println!("Error: {}", err);
-                }
+            let constellation_id = *find_constellation_by_system(&outpost.system)
+                .ok_or(format!("The system {} of {} is not known", outpost.system, outpost.name))?;
+            let constellation = get_constellation(constellation_id)
+                .ok_or(format!("The constellation {constellation_id} is not known"))?;
+            let planets = available_planets_by_outpost(outpost.clone(), outpost.arrays).map_err(|err| err.to_string())?;
+            *available_constellation
+                .entry(constellation.en_name.to_string())
+                .or_insert(0) += outpost.arrays * outpost.planets;
+            for (key, _value) in planets {
+                *available_planet.entry(key).or_insert(0) += outpost.arrays;
             }
+            available_celestial_resource.extend(celestial_resources_by_constellation(constellation_id)?)
         }
         
-        (available_constellation, available_planet, available_celestial_resource)
+        Ok((available_constellation, available_planet, available_celestial_resource))
     }
     
-    pub fn push_material(line: &str, materials: &mut Vec<Material>) {
+    /// Add the material on a tab separated line of the list, naming the field that could not be read.
+    pub fn push_material(line: &str, materials: &mut Vec<Material>) -> Result<(), anyhow::Error> {
         let material_split: Vec<&str> = line.split("\t").collect();
         if material_split.len() >= 4 {
+            let name = material_split[1];
             materials.push(
                 Material {
-                    resource_type_id: *find_item(material_split[1].into()).unwrap(),
-                    name: material_split[1].into(),
-                    quantity: material_split[2].parse::<i64>().unwrap(),
-                    valuation: material_split[3].trim().parse::<f64>().unwrap(),
+                    resource_type_id: *find_item(name)
+                        .ok_or_else(|| anyhow::anyhow!("{name} is not a known material"))?,
+                    name: name.into(),
+                    quantity: material_split[2].parse::<i64>()
+                        .map_err(|_| anyhow::anyhow!("the quantity {} of {name} is not a whole number", material_split[2]))?,
+                    valuation: material_split[3].trim().parse::<f64>()
+                        .map_err(|_| anyhow::anyhow!("the valuation {} of {name} is not a number", material_split[3].trim()))?,
                 }
             );
         }
+        Ok(())
     }
 
     pub fn parse_decomposed_list(text: &str) ->  Result<Vec::<Material>, anyhow::Error> {
@@ -179,8 +184,9 @@ mod objective {
             if first_line != expected_header {
                 return Err(anyhow::anyhow!("Invalid header line.").into());
             }
-            for line in lines {
-                push_material(line, &mut materials);
+            for (index, line) in lines.enumerate() {
+                push_material(line, &mut materials)
+                    .map_err(|e| anyhow::anyhow!("Line {}: {e}", index + 2))?;
             }
         } else {
             return Err(anyhow::anyhow!("No header line.").into());
@@ -189,8 +195,9 @@ mod objective {
     }
 
     pub fn available_planets_by_outpost(outpost: outpost::Model, number: i32) -> Result<HashMap<i64, i32>, Box<dyn std::error::Error>> {
-        let constellation_id = find_constellation_by_system(&outpost.system).expect("Failed to find constellation by system");
-        let celestials = slice_celestials(*constellation_id).expect("Failed to slice celestials");
+        let constellation_id = find_constellation_by_system(&outpost.system)
+            .ok_or(format!("The system {} of {} is not known", outpost.system, outpost.name))?;
+        let celestials = slice_celestials(*constellation_id)?;
         let available_planets: HashMap<i64, i32> = PLANETS
             .iter()
             .filter(|(key, _)| celestials.contains_key(*key))
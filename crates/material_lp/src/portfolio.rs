@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use good_lp::{default_solver, variable, variables, Expression, ResolutionError, Solution, SolverModel, Variable};
+
+use crate::objective::map_objective;
+use crate::problem::{get_resource_value, Value};
+use crate::resource::{CelestialResource, Material};
+
+/// An outpost whose arrays are shared between the demands it may serve.
+pub struct Site {
+    /// The outpost's id, its name is only unique for its capsuleer.
+    pub id: i32,
+    pub name: String,
+    pub planets: i32,
+    /// Arrays on each planet.
+    pub arrays: i32,
+    /// Names of the demands the outpost may serve.
+    pub serves: Vec<String>,
+    pub resources: Vec<CelestialResource>,
+}
+
+/// A build the corporation's outposts harvest for.
+pub struct Demand {
+    pub name: String,
+    /// Demands are secured in ascending priority, then by the earliest deadline.
+    pub priority: i32,
+    /// Days the requirements must be harvested within, the arrays serving it are valued over as long.
+    pub days: f64,
+    pub minimum_output: HashMap<i64, f64>,
+    pub value: Value,
+}
+
+impl Demand {
+    pub fn new(name: String, priority: i32, days: f64, materials: Vec<Material>) -> Demand {
+        let (minimum_output, value) = map_objective(materials);
+        Demand { name, priority, days, minimum_output, value }
+    }
+}
+
+/// Arrays of an outpost on a resource, harvested for one demand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Assignment {
+    pub site_id: i32,
+    pub site: String,
+    pub demand: String,
+    pub resource: CelestialResource,
+    pub arrays: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PortfolioPlan {
+    pub assignments: Vec<Assignment>,
+    /// Demands whose requirements do not fit next to the more urgent ones, they only get what is left.
+    pub unmet: Vec<String>,
+    pub value: f64,
+}
+
+/// Several demands solved together over the outposts they share.
+#[derive(Default)]
+pub struct Portfolio {
+    sites: Vec<Site>,
+    demands: Vec<Demand>,
+}
+
+impl Portfolio {
+    pub fn new() -> Portfolio {
+        Portfolio::default()
+    }
+
+    pub fn add_site(&mut self, site: Site) {
+        self.sites.push(site);
+    }
+
+    pub fn add_demand(&mut self, demand: Demand) {
+        self.demands.push(demand);
+    }
+
+    /// Secure the requirements of each demand in order of urgency while they still fit, then place every array for the most value.
+    pub fn solve(&self) -> Result<PortfolioPlan, String> {
+        let mut order: Vec<usize> = (0..self.demands.len()).collect();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (&self.demands[a], &self.demands[b]);
+            a.priority.cmp(&b.priority).then(a.days.total_cmp(&b.days))
+        });
+
+        let mut secured = Vec::with_capacity(order.len());
+        let mut unmet = Vec::new();
+        for index in order {
+            secured.push(index);
+            if self.demands[index].minimum_output.is_empty() || self.optimum(&secured)?.is_some() {
+                continue;
+            }
+            secured.pop();
+            unmet.push(self.demands[index].name.clone());
+        }
+
+        let mut plan = self.optimum(&secured)?.ok_or("The demands do not fit the outposts".to_string())?;
+        plan.unmet = unmet;
+        Ok(plan)
+    }
+
+    /// The best placement meeting the requirements of the secured demands, `None` when they do not fit.
+    fn optimum(&self, secured: &[usize]) -> Result<Option<PortfolioPlan>, String> {
+        let mut vars = variables!();
+        let mut placements: Vec<(usize, usize, usize, Variable)> = Vec::new();
+        let mut total_value: Expression = 0.into();
+        let mut constraints = Vec::new();
+        let mut output: HashMap<(usize, i64), Expression> = HashMap::new();
+
+        for (site_index, site) in self.sites.iter().enumerate() {
+            let mut consumed_site: Expression = 0.into();
+            let mut consumed_planet: HashMap<i64, Expression> = HashMap::new();
+            for (demand_index, demand) in self.demands.iter().enumerate() {
+                if !site.serves.contains(&demand.name) {
+                    continue;
+                }
+                for (resource_index, resource) in site.resources.iter().enumerate() {
+                    let arrays = vars.add(variable().min(0).max(site.arrays));
+                    placements.push((site_index, demand_index, resource_index, arrays));
+                    total_value += get_resource_value(resource, &demand.value) * arrays * demand.days * 24.;
+                    consumed_site += arrays;
+                    *consumed_planet.entry(resource.planet_id).or_insert(0.into()) += arrays;
+                    *output.entry((demand_index, resource.resource_type_id)).or_insert(0.into()) +=
+                        arrays * resource.init_output * demand.days * 24.;
+                }
+            }
+            constraints.push(consumed_site.leq(site.planets * site.arrays));
+            for consumed_planet in consumed_planet.into_values() {
+                constraints.push(consumed_planet.leq(site.arrays));
+            }
+        }
+
+        for &demand_index in secured {
+            for (resource_type_id, minimum_output) in &self.demands[demand_index].minimum_output {
+                let harvested = output.remove(&(demand_index, *resource_type_id)).unwrap_or_else(|| 0.into());
+                constraints.push(harvested.geq(*minimum_output));
+            }
+        }
+
+        let mut solution = vars
+            .maximise(total_value.clone())
+            .using(default_solver);
+        for constraint in constraints {
+            solution = solution.with(constraint);
+        }
+        let solution = match solution.solve() {
+            Ok(solution) => solution,
+            Err(ResolutionError::Infeasible) => return Ok(None),
+            Err(err) => return Err(format!("Error solving the portfolio: {:?}", err)),
+        };
+
+        let assignments = placements
+            .into_iter()
+            .map(|(site_index, demand_index, resource_index, arrays)| (site_index, demand_index, resource_index, solution.value(arrays)))
+            .filter(|(_, _, _, arrays)| *arrays > 1e-6)
+            .map(|(site_index, demand_index, resource_index, arrays)| Assignment {
+                site_id: self.sites[site_index].id,
+                site: self.sites[site_index].name.clone(),
+                demand: self.demands[demand_index].name.clone(),
+                resource: self.sites[site_index].resources[resource_index].clone(),
+                arrays,
+            })
+            .collect();
+        Ok(Some(PortfolioPlan { assignments, unmet: Vec::new(), value: total_value.eval_with(&solution) }))
+    }
+}
@@ -0,0 +1,196 @@
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use async_trait::async_trait;
+use sea_orm::prelude::{ChronoDate, DateTimeUtc};
+
+use crate::data::find_item;
+use crate::resource::Material;
+use manager::database::DatabaseConnection;
+use manager::entities::{price_book, price_book_entry, price_snapshot};
+use manager::new_price_snapshot;
+
+/// ISK per unit of each resource type as a source quoted them at a moment.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PriceSnapshot {
+    pub source: String,
+    pub taken_at: DateTimeUtc,
+    pub prices: BTreeMap<i64, f64>,
+}
+
+impl PriceSnapshot {
+    /// Value each quoted material at its price, leaving the others as they were and returning their names.
+    pub fn revalue(&self, materials: &mut [Material]) -> Vec<Box<str>> {
+        let mut unpriced = Vec::new();
+        for material in materials.iter_mut() {
+            match self.prices.get(&material.resource_type_id) {
+                Some(price) => material.valuation = price * material.quantity as f64,
+                None => unpriced.push(material.name.clone()),
+            }
+        }
+        unpriced
+    }
+
+    /// Carry over the earlier prices of materials this snapshot does not quote, so a snapshot
+    /// taken from one material list keeps what the corporation recorded for the others.
+    pub fn merge(&mut self, earlier: &PriceSnapshot) {
+        for (resource_type_id, price) in &earlier.prices {
+            self.prices.entry(*resource_type_id).or_insert(*price);
+        }
+    }
+
+    /// Keep the snapshot for the corporation so solves can be repeated against it.
+    pub async fn record(&self, db: &DatabaseConnection, corporation_id: i32) -> Result<price_snapshot::Model, manager::Error> {
+        let prices = self.prices
+            .iter()
+            .map(|(resource_type_id, price)| (resource_type_id.to_string(), serde_json::json!(price)))
+            .collect::<serde_json::Map<String, serde_json::Value>>();
+        new_price_snapshot(db, corporation_id, &self.source, prices.into(), self.taken_at).await
+    }
+
+    /// The corporation's prices as they were at the moment, `None` when nothing was recorded by then.
+    pub async fn as_of(db: &DatabaseConnection, corporation_id: i32, at: DateTimeUtc) -> Result<Option<Self>, manager::Error> {
+        Ok(price_snapshot::Entity::find_at(corporation_id, at, db).await?.map(Self::from))
+    }
+}
+
+impl PriceSnapshot {
+    /// The prices of a price book version, taken when it was published.
+    pub fn from_price_book(book: price_book::Model, entries: Vec<price_book_entry::Model>) -> Self {
+        PriceSnapshot {
+            source: format!("price book v{}", book.version),
+            taken_at: book.created_at,
+            prices: entries.into_iter().map(|entry| (entry.resource_type_id, entry.price)).collect(),
+        }
+    }
+}
+
+impl From<price_snapshot::Model> for PriceSnapshot {
+    fn from(model: price_snapshot::Model) -> Self {
+        let prices = model.prices
+            .as_object()
+            .into_iter()
+            .flatten()
+            .filter_map(|(resource_type_id, price)| Some((resource_type_id.parse().ok()?, price.as_f64()?)))
+            .collect();
+        PriceSnapshot { source: model.source, taken_at: model.taken_at, prices }
+    }
+}
+
+/// Somewhere the value of a unit of each resource comes from.
+#[async_trait]
+pub trait PriceSource {
+    async fn snapshot(&self) -> Result<PriceSnapshot, anyhow::Error>;
+}
+
+/// The valuations of an exported material list, as pasted with the problem.
+pub struct PastedList {
+    pub name: String,
+    pub materials: Vec<Material>,
+}
+
+#[async_trait]
+impl PriceSource for PastedList {
+    async fn snapshot(&self) -> Result<PriceSnapshot, anyhow::Error> {
+        let prices = self.materials
+            .iter()
+            .filter(|material| material.quantity > 0)
+            .map(|material| (material.resource_type_id, material.valuation / material.quantity as f64))
+            .collect();
+        Ok(PriceSnapshot { source: self.name.clone(), taken_at: DateTimeUtc::from(std::time::SystemTime::now()), prices })
+    }
+}
+
+/// A local JSON or CSV file of prices standing in for a market feed, taken when the file was last written.
+pub struct PriceFile {
+    pub path: PathBuf,
+}
+
+#[async_trait]
+impl PriceSource for PriceFile {
+    async fn snapshot(&self) -> Result<PriceSnapshot, anyhow::Error> {
+        let text = std::fs::read_to_string(&self.path)?;
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+        Ok(PriceSnapshot {
+            source: self.path.display().to_string(),
+            taken_at: DateTimeUtc::from(modified),
+            prices: parse_prices(&text)?,
+        })
+    }
+}
+
+/// The snapshot the corporation had recorded at a moment, so an earlier solve can be run again.
+pub struct Recorded<'a> {
+    pub db: &'a DatabaseConnection,
+    pub corporation_id: i32,
+    pub at: DateTimeUtc,
+}
+
+#[async_trait]
+impl PriceSource for Recorded<'_> {
+    async fn snapshot(&self) -> Result<PriceSnapshot, anyhow::Error> {
+        PriceSnapshot::as_of(self.db, self.corporation_id, self.at)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("No prices were recorded by {}", self.at.format("%Y-%m-%d %H:%M")))
+    }
+}
+
+/// A version of the corporation's buyback price book, the latest when none is given.
+pub struct PriceBook<'a> {
+    pub db: &'a DatabaseConnection,
+    pub corporation_id: i32,
+    pub version: Option<i32>,
+}
+
+#[async_trait]
+impl PriceSource for PriceBook<'_> {
+    async fn snapshot(&self) -> Result<PriceSnapshot, anyhow::Error> {
+        let (book, entries) = price_book::Entity::find_with_entries(self.corporation_id, self.version, self.db)
+            .await?
+            .ok_or_else(|| anyhow::anyhow!("The corporation has no price book"))?;
+        Ok(PriceSnapshot::from_price_book(book, entries))
+    }
+}
+
+/// Read prices keyed by resource type id or English name, either a JSON object or `resource,price` lines.
+pub fn parse_prices(text: &str) -> Result<BTreeMap<i64, f64>, anyhow::Error> {
+    if text.trim_start().starts_with('{') {
+        let quoted: BTreeMap<String, f64> = serde_json::from_str(text)?;
+        return quoted.iter().map(|(resource, price)| Ok((resource_type_id(resource)?, *price))).collect();
+    }
+    let mut prices = BTreeMap::new();
+    for (index, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let Some((resource, price)) = line.rsplit_once(',') else {
+            return Err(anyhow::anyhow!("Line {} is not resource,price", index + 1));
+        };
+        let Ok(price) = price.trim().parse::<f64>() else {
+            // A header names its columns instead of pricing anything.
+            if index == 0 {
+                continue;
+            }
+            return Err(anyhow::anyhow!("Line {} has no price", index + 1));
+        };
+        prices.insert(resource_type_id(resource.trim())?, price);
+    }
+    Ok(prices)
+}
+
+fn resource_type_id(resource: &str) -> Result<i64, anyhow::Error> {
+    resource
+        .parse::<i64>()
+        .ok()
+        .or_else(|| find_item(resource).copied())
+        .ok_or_else(|| anyhow::anyhow!("No resource is named {resource}"))
+}
+
+/// The last moment of a `YYYY-MM-DD` day, so prices recorded that day count.
+pub fn end_of_day(date: &str) -> Option<DateTimeUtc> {
+    ChronoDate::parse_from_str(date, "%Y-%m-%d")
+        .ok()?
+        .and_hms_opt(23, 59, 59)
+        .map(|moment| moment.and_utc())
+}
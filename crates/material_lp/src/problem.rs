@@ -1,10 +1,10 @@
 use good_lp::variable::ProblemVariables;
-use good_lp::{default_solver, variable, variables, Expression, Solution, SolverModel, Variable};
+use good_lp::{default_solver, variable, variables, Constraint, Expression, ResolutionError, Solution, SolverModel, Variable};
 use crate::data::get_item;
 use crate::resource::CelestialResource;
 use std::collections::HashMap;
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Value {
     pub lustering_allow: f64,
     pub sheen_compound: f64,
@@ -47,16 +47,104 @@ pub struct ResourceHarvestProblem {
     vars: ProblemVariables,
     value: Value,
     pub available_array: i32,
-    days: f64,
-    
-    total_value: Expression,
-    total_array: Expression,
-    consumed_key: HashMap<String, Expression>,
-    consumed_planet: HashMap<i64, Expression>,
-    resource_output: HashMap<i64, Expression>,
+    pub days: f64,
+
+    /// Resources in the order of their variables, the model is built from them for every solve.
+    resources: Vec<CelestialResource>,
     pub available_key: HashMap<String, i32>,
     pub available_planet: HashMap<i64, i32>,
     pub minimum_output: HashMap<i64, f64>,
+    pub delivery: Option<Delivery>,
+    pub stability: Option<Stability>,
+}
+
+/// Where the harvest is hauled and what each jump to it costs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Delivery {
+    /// Jumps from each planet to the delivery system, planets missing cannot reach it.
+    pub jumps: HashMap<i64, usize>,
+    /// Planets further than this many jumps get no arrays.
+    pub max_jumps: Option<usize>,
+    /// ISK every unit loses for each jump it is hauled.
+    pub isk_per_unit_jump: f64,
+}
+
+impl Delivery {
+    /// Whether arrays on the planet can deliver at all.
+    pub fn reaches(&self, planet_id: i64) -> bool {
+        match (self.jumps.get(&planet_id), self.max_jumps) {
+            (Some(jumps), Some(max_jumps)) => *jumps <= max_jumps,
+            (Some(_), None) => true,
+            (None, _) => false,
+        }
+    }
+
+    /// ISK a unit harvested on the planet loses on the way.
+    pub fn haul(&self, planet_id: i64) -> f64 {
+        self.jumps.get(&planet_id).copied().unwrap_or(0) as f64 * self.isk_per_unit_jump
+    }
+}
+
+/// The arrays anchored today and what anchoring one elsewhere costs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Stability {
+    /// Arrays on each resource, keyed by planet and resource type.
+    pub current: HashMap<(i64, i64), f64>,
+    /// ISK lost for every array anchored where it is not today.
+    pub isk_per_move: f64,
+}
+
+/// Arrays on a resource today and in the plan.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Move {
+    pub planet_id: i64,
+    pub resource_type_id: i64,
+    pub from: f64,
+    pub to: f64,
+}
+
+impl Stability {
+    /// Every resource whose arrays the plan changes, by planet.
+    pub fn moves(&self, production: &[(CelestialResource, f64)]) -> Vec<Move> {
+        let mut planned: HashMap<(i64, i64), f64> = HashMap::new();
+        for (resource, arrays) in production {
+            *planned.entry((resource.planet_id, resource.resource_type_id)).or_insert(0.) += arrays;
+        }
+        let mut keys: Vec<(i64, i64)> = planned.keys().chain(self.current.keys()).copied().collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .map(|(planet_id, resource_type_id)| Move {
+                planet_id,
+                resource_type_id,
+                from: self.current.get(&(planet_id, resource_type_id)).copied().unwrap_or(0.),
+                to: planned.get(&(planet_id, resource_type_id)).copied().unwrap_or(0.),
+            })
+            .filter(|change| (change.to - change.from).abs() > 1e-6)
+            .collect()
+    }
+}
+
+/// One constraint of the problem moved, to price it by solving again.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Shift {
+    /// The problem as it was posed.
+    None,
+    /// One more array in the constellation.
+    Key(String),
+    /// One more array on the planet.
+    Planet(i64),
+    /// The material's minimum output multiplied by the factor.
+    Minimum(i64, f64),
+    /// At least one array on the resource with this variable index.
+    Force(usize),
+}
+
+/// The best value of a solve and the arrays placed on each resource, in the order they were added.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Optimum {
+    pub value: f64,
+    pub arrays: Vec<f64>,
 }
 
 impl ResourceHarvestProblem {
@@ -74,17 +162,25 @@ impl ResourceHarvestProblem {
             available_array,
             days,
 
-            total_value: 0.into(),
-            total_array: 0.into(),
-            consumed_key: HashMap::new(),
-            consumed_planet: HashMap::new(),
-            resource_output: HashMap::new(),
+            resources: Vec::new(),
             available_key,
             available_planet,
             minimum_output,
+            delivery: None,
+            stability: None,
         }
     }
 
+    /// Haul the harvest to a system, leaving out the planets it cannot reach.
+    pub fn deliver(&mut self, delivery: Delivery) {
+        self.delivery = Some(delivery);
+    }
+
+    /// Keep close to the arrays anchored today, charging for each one anchored elsewhere.
+    pub fn stabilize(&mut self, stability: Stability) {
+        self.stability = Some(stability);
+    }
+
     pub fn add_fuel(
         &mut self,
         material_id: i64,
@@ -99,63 +195,148 @@ impl ResourceHarvestProblem {
             .or_insert(quantity);
     }
 
+    /// The variable holding the arrays placed on the resource.
     pub fn add_resource(&mut self, resource: CelestialResource) -> Variable {
-        let planet_limit = self.available_planet
-            .get(&resource.planet_id)
-            .copied()
-            .unwrap_or(22);
-        let consumed_key = self.consumed_key
-            .entry(resource.key.clone())
-            .or_insert(0.into());
-        let consumed_planet = self.consumed_planet
-            .entry(resource.planet_id)
-            .or_insert(0.into());
-        let resource_output = self.resource_output
-            .entry(resource.resource_type_id)
-            .or_insert(0.into());
-        let array_quantity = self.vars.add(variable().min(0).max(planet_limit));
-
-        self.total_value += get_resource_value(&resource, &self.value) * array_quantity * self.days * 24.;
-        self.total_array += array_quantity;
-        *consumed_key += array_quantity;
-        *consumed_planet += array_quantity;
-        *resource_output += array_quantity * resource.init_output * self.days * 24.;
-
-        array_quantity
+        self.resources.push(resource);
+        self.vars.add(variable().min(0))
+    }
+
+    pub fn resources(&self) -> &[CelestialResource] {
+        &self.resources
+    }
+
+    /// ISK a resource yields per array over the whole period, net of hauling it.
+    pub fn resource_value(&self, index: usize) -> f64 {
+        self.hourly_value(&self.resources[index]) * self.days * 24.
+    }
+
+    /// ISK one array on the resource yields per hour once delivered.
+    fn hourly_value(&self, resource: &CelestialResource) -> f64 {
+        let haul = self.delivery
+            .as_ref()
+            .map_or(0., |delivery| delivery.haul(resource.planet_id) * resource.init_output);
+        get_resource_value(resource, &self.value) - haul
     }
 
     pub fn best_production(self) -> Result<impl Solution, String> {
-               
-        let mut solution = self.vars
-            .maximise(self.total_value)
-            .using(default_solver)
-            .with(self.total_array.eq(self.available_array))
-            ;
-
-        for (key, consumed_key) in &self.consumed_key {
-            let available_key = self.available_key.get(key).copied().unwrap_or(0);
-            solution = solution.with(consumed_key.clone().leq(available_key));
+        let model = self.model(&Shift::None);
+        let mut solution = model.vars
+            .maximise(model.total_value)
+            .using(default_solver);
+        for constraint in model.constraints {
+            solution = solution.with(constraint);
         }
 
-        for (planet_id, consumed_planet) in &self.consumed_planet {
-            let available_planet = self.available_planet.get(&planet_id).copied().unwrap_or(0);
-            solution = solution.with(consumed_planet.clone().leq(available_planet));
+        match solution.solve() {
+            Ok(solution) => Ok(solution),
+            Err(err) => Err(format!("Error solving the problem: {:?}", err)),
         }
-        
-        for (resource_type_id, minimum_output) in &self.minimum_output {
-            let sum_resource_output: Expression = self.resource_output
-                .iter()
-                .filter(|(key, _)| *key == resource_type_id)
-                .map(|(_, resource_output)| resource_output)
-                .sum();
-            solution = solution.with(sum_resource_output.geq(*minimum_output));
+    }
+
+    /// Solve with the constraint moved, `None` when nothing satisfies the moved problem.
+    pub fn optimum(&self, shift: &Shift) -> Result<Option<Optimum>, String> {
+        let model = self.model(shift);
+        let mut solution = model.vars
+            .maximise(model.total_value.clone())
+            .using(default_solver);
+        for constraint in model.constraints {
+            solution = solution.with(constraint);
         }
 
         match solution.solve() {
-            Ok(solution) => Ok(solution),
+            Ok(solution) => Ok(Some(Optimum {
+                value: model.total_value.eval_with(&solution),
+                arrays: model.variables.iter().map(|&variable| solution.value(variable)).collect(),
+            })),
+            Err(ResolutionError::Infeasible) => Ok(None),
             Err(err) => Err(format!("Error solving the problem: {:?}", err)),
         }
     }
+
+    /// The linear program for the resources, its variables match the ones `add_resource` handed out.
+    fn model(&self, shift: &Shift) -> Model {
+        let planet_shift = |planet_id: i64| if *shift == Shift::Planet(planet_id) { 1 } else { 0 };
+        let key_shift = |key: &str| matches!(shift, Shift::Key(shifted) if shifted == key) as i32;
+
+        let mut vars = variables!();
+        let mut variables = Vec::with_capacity(self.resources.len());
+        let mut total_value: Expression = 0.into();
+        let mut total_array: Expression = 0.into();
+        let mut consumed_key: HashMap<&str, Expression> = HashMap::new();
+        let mut consumed_planet: HashMap<i64, Expression> = HashMap::new();
+        let mut resource_output: HashMap<i64, Expression> = HashMap::new();
+
+        for (index, resource) in self.resources.iter().enumerate() {
+            let reachable = self.delivery.as_ref().is_none_or(|delivery| delivery.reaches(resource.planet_id));
+            let planet_limit = if reachable {
+                self.available_planet
+                    .get(&resource.planet_id)
+                    .copied()
+                    .unwrap_or(22) + planet_shift(resource.planet_id)
+            } else {
+                0
+            };
+            let minimum = if *shift == Shift::Force(index) { 1 } else { 0 };
+            let array_quantity = vars.add(variable().min(minimum).max(planet_limit));
+            variables.push(array_quantity);
+
+            total_value += self.hourly_value(resource) * array_quantity * self.days * 24.;
+            total_array += array_quantity;
+            *consumed_key.entry(resource.key.as_str()).or_insert(0.into()) += array_quantity;
+            *consumed_planet.entry(resource.planet_id).or_insert(0.into()) += array_quantity;
+            *resource_output.entry(resource.resource_type_id).or_insert(0.into()) +=
+                array_quantity * resource.init_output * self.days * 24.;
+        }
+
+        let available_array = self.available_array + matches!(shift, Shift::Key(_)) as i32;
+        let mut constraints = vec![total_array.eq(available_array)];
+
+        // Placed after the arrays so the variables still line up with the ones `add_resource` handed out.
+        // A resource is added once for every outpost in its constellation, so its arrays are summed first.
+        if let Some(stability) = &self.stability {
+            let mut placed: HashMap<(i64, i64), Expression> = HashMap::new();
+            for (resource, &array_quantity) in self.resources.iter().zip(&variables) {
+                *placed.entry((resource.planet_id, resource.resource_type_id)).or_insert(0.into()) += array_quantity;
+            }
+            for (key, array_quantity) in placed {
+                let anchored = vars.add(variable().min(0));
+                let current = stability.current.get(&key).copied().unwrap_or(0.);
+                constraints.push((array_quantity - current).leq(anchored));
+                total_value -= stability.isk_per_move * anchored;
+            }
+        }
+
+        for (key, consumed_key) in consumed_key {
+            let available_key = self.available_key.get(key).copied().unwrap_or(0) + key_shift(key);
+            constraints.push(consumed_key.leq(available_key));
+        }
+
+        for (planet_id, consumed_planet) in consumed_planet {
+            let available_planet = self.available_planet.get(&planet_id).copied().unwrap_or(0) + planet_shift(planet_id);
+            constraints.push(consumed_planet.leq(available_planet));
+        }
+
+        for (resource_type_id, minimum_output) in &self.minimum_output {
+            let factor = match shift {
+                Shift::Minimum(shifted, factor) if shifted == resource_type_id => *factor,
+                _ => 1.,
+            };
+            let sum_resource_output = resource_output
+                .get(resource_type_id)
+                .cloned()
+                .unwrap_or_else(|| 0.into());
+            constraints.push(sum_resource_output.geq(*minimum_output * factor));
+        }
+
+        Model { vars, variables, total_value, constraints }
+    }
+}
+
+struct Model {
+    vars: ProblemVariables,
+    variables: Vec<Variable>,
+    total_value: Expression,
+    constraints: Vec<Constraint>,
 }
 
 pub fn get_resource_value(resource: &CelestialResource, value: &Value) -> f64 {
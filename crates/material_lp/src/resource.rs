@@ -24,10 +24,11 @@ mod resource {
         pub richness_value: i64
     }
 
-    pub fn celestial_resources_by_outpost(outpost: outpost::Model) -> Vec<CelestialResource> {
+    pub fn celestial_resources_by_outpost(outpost: outpost::Model) -> Result<Vec<CelestialResource>, String> {
         let mut celestial_resources: Vec<CelestialResource> = Vec::new();
-        let constellation_id = find_constellation_by_system(&outpost.system).expect("Failed to find constellation by system");
-        let celestials = slice_celestials(*constellation_id).expect("Failed to slice celestials");
+        let constellation_id = find_constellation_by_system(&outpost.system)
+            .ok_or(format!("The system {} of {} is not known", outpost.system, outpost.name))?;
+        let celestials = slice_celestials(*constellation_id).map_err(|err| err.to_string())?;
         let outpost_name = outpost.name;
 
         for (_, planet) in PLANETS.iter().filter(|(key, _)| celestials.contains_key(*key)) {
@@ -44,19 +45,21 @@ mod resource {
                 })
             }
         }
-        celestial_resources
+        Ok(celestial_resources)
     }
 
-    pub fn celestial_resources_by_constellation(constellation_id: i64) -> Vec<CelestialResource> {
+    pub fn celestial_resources_by_constellation(constellation_id: i64) -> Result<Vec<CelestialResource>, String> {
         let mut celestial_resources: Vec<CelestialResource> = Vec::new();
-        let celestials = slice_celestials(constellation_id).expect("Failed to slice celestials");
+        let constellation = get_constellation(constellation_id)
+            .ok_or(format!("The constellation {constellation_id} is not known"))?;
+        let celestials = slice_celestials(constellation_id).map_err(|err| err.to_string())?;
 
         for (_, planet) in PLANETS.iter().filter(|(key, _)| celestials.contains_key(*key)) {
             let planet_id = planet.planet_id;
             
             for (_, resource) in &planet.resource_info {
                 celestial_resources.push(CelestialResource{
-                    key: get_constellation(constellation_id).unwrap().en_name.to_string(),
+                    key: constellation.en_name.to_string(),
                     planet_id,
                     resource_type_id: resource.resource_type_id,
                     init_output: resource.init_output,
@@ -65,7 +68,7 @@ mod resource {
                 })
             }
         }
-        celestial_resources
+        Ok(celestial_resources)
     }
 
 }
@@ -0,0 +1,42 @@
+use std::collections::{HashMap, VecDeque};
+
+/// Jumps from the origin to every system reachable through the stargates, the origin itself at 0.
+pub fn distances(stargates: &HashMap<i64, Vec<i64>>, origin: i64) -> HashMap<i64, usize> {
+    let mut distances = HashMap::from([(origin, 0)]);
+    let mut queue = VecDeque::from([origin]);
+    while let Some(system_id) = queue.pop_front() {
+        let jumps = distances[&system_id] + 1;
+        for next in stargates.get(&system_id).into_iter().flatten() {
+            if !distances.contains_key(next) {
+                distances.insert(*next, jumps);
+                queue.push_back(*next);
+            }
+        }
+    }
+    distances
+}
+
+/// The systems of a shortest route, both ends included, `None` when no stargates connect them.
+pub fn shortest_path(stargates: &HashMap<i64, Vec<i64>>, from: i64, to: i64) -> Option<Vec<i64>> {
+    let mut previous: HashMap<i64, i64> = HashMap::from([(from, from)]);
+    let mut queue = VecDeque::from([from]);
+    while let Some(system_id) = queue.pop_front() {
+        if system_id == to {
+            let mut path = vec![to];
+            let mut current = to;
+            while current != from {
+                current = previous[&current];
+                path.push(current);
+            }
+            path.reverse();
+            return Some(path);
+        }
+        for next in stargates.get(&system_id).into_iter().flatten() {
+            if !previous.contains_key(next) {
+                previous.insert(*next, system_id);
+                queue.push_back(*next);
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,190 @@
+use std::collections::{BTreeSet, HashMap};
+
+use good_lp::{default_solver, variable, variables, Expression, ResolutionError, Solution, SolverModel, Variable};
+
+use crate::objective::map_objective;
+use crate::problem::{get_resource_value, Value};
+use crate::resource::{CelestialResource, Material};
+
+/// One stretch of a build, harvested at its own prices towards its own requirements.
+pub struct Period {
+    pub days: f64,
+    /// Output due by the end of the period, on top of what earlier periods were due.
+    pub minimum_output: HashMap<i64, f64>,
+    pub value: Value,
+}
+
+impl Period {
+    pub fn new(days: f64, materials: Vec<Material>) -> Period {
+        let (minimum_output, value) = map_objective(materials);
+        Period { days, minimum_output, value }
+    }
+}
+
+/// What moving arrays between planets from one period to the next costs.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Reanchoring {
+    /// ISK lost for every array unanchored and anchored elsewhere.
+    pub isk_per_array: f64,
+    /// At most this many arrays move between two periods.
+    pub max_arrays: Option<f64>,
+}
+
+/// Arrays placed on each resource during a period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodPlan {
+    pub production: Vec<(CelestialResource, f64)>,
+    /// Arrays anchored on another resource than in the period before.
+    pub moved: f64,
+    /// ISK the period harvests, before paying for the moves.
+    pub value: f64,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SchedulePlan {
+    pub periods: Vec<PeriodPlan>,
+    /// ISK over every period net of re-anchoring.
+    pub value: f64,
+}
+
+/// Periods solved together over the same arrays, so a long build can be placed end to end.
+pub struct Schedule {
+    available_array: i32,
+    available_key: HashMap<String, i32>,
+    available_planet: HashMap<i64, i32>,
+    resources: Vec<CelestialResource>,
+    periods: Vec<Period>,
+    reanchoring: Reanchoring,
+}
+
+impl Schedule {
+    pub fn new(
+        available_key: HashMap<String, i32>,
+        available_planet: HashMap<i64, i32>,
+        reanchoring: Reanchoring,
+    ) -> Schedule {
+        let available_array = available_key.values().copied().sum();
+        Schedule {
+            available_array,
+            available_key,
+            available_planet,
+            resources: Vec::new(),
+            periods: Vec::new(),
+            reanchoring,
+        }
+    }
+
+    pub fn add_resource(&mut self, resource: CelestialResource) {
+        self.resources.push(resource);
+    }
+
+    pub fn add_period(&mut self, period: Period) {
+        self.periods.push(period);
+    }
+
+    /// Place every array in every period for the most value net of moving them, harvest carrying over to later periods.
+    pub fn solve(&self) -> Result<SchedulePlan, String> {
+        let mut vars = variables!();
+        let mut placements: Vec<Vec<Variable>> = Vec::with_capacity(self.periods.len());
+        let mut period_values: Vec<Expression> = Vec::with_capacity(self.periods.len());
+        let mut period_moves: Vec<Expression> = Vec::with_capacity(self.periods.len());
+        let mut total_value: Expression = 0.into();
+        let mut constraints = Vec::new();
+        // Output harvested so far and output due so far, per material.
+        let mut harvested: HashMap<i64, Expression> = HashMap::new();
+        let mut due: HashMap<i64, f64> = HashMap::new();
+        let materials: BTreeSet<i64> = self.periods
+            .iter()
+            .flat_map(|period| period.minimum_output.keys().copied())
+            .collect();
+
+        // Arrays on each resource in the period before, keyed by planet and resource type.
+        let mut previous: Option<HashMap<(i64, i64), Expression>> = None;
+
+        for period in &self.periods {
+            let mut arrays = Vec::with_capacity(self.resources.len());
+            let mut value: Expression = 0.into();
+            let mut total_array: Expression = 0.into();
+            let mut consumed_key: HashMap<&str, Expression> = HashMap::new();
+            let mut consumed_planet: HashMap<i64, Expression> = HashMap::new();
+            let mut placed: HashMap<(i64, i64), Expression> = HashMap::new();
+            for resource in &self.resources {
+                let planet_limit = self.available_planet.get(&resource.planet_id).copied().unwrap_or(22);
+                let array_quantity = vars.add(variable().min(0).max(planet_limit));
+                arrays.push(array_quantity);
+
+                value += get_resource_value(resource, &period.value) * array_quantity * period.days * 24.;
+                total_array += array_quantity;
+                *consumed_key.entry(resource.key.as_str()).or_insert(0.into()) += array_quantity;
+                *consumed_planet.entry(resource.planet_id).or_insert(0.into()) += array_quantity;
+                *placed.entry((resource.planet_id, resource.resource_type_id)).or_insert(0.into()) += array_quantity;
+                *harvested.entry(resource.resource_type_id).or_insert(0.into()) +=
+                    array_quantity * resource.init_output * period.days * 24.;
+            }
+
+            constraints.push(total_array.eq(self.available_array));
+            for (key, consumed_key) in consumed_key {
+                constraints.push(consumed_key.leq(self.available_key.get(key).copied().unwrap_or(0)));
+            }
+            for (planet_id, consumed_planet) in consumed_planet {
+                constraints.push(consumed_planet.leq(self.available_planet.get(&planet_id).copied().unwrap_or(0)));
+            }
+            for (resource_type_id, minimum_output) in &period.minimum_output {
+                *due.entry(*resource_type_id).or_insert(0.) += minimum_output;
+            }
+            for resource_type_id in &materials {
+                let harvested = harvested.get(resource_type_id).cloned().unwrap_or_else(|| 0.into());
+                constraints.push(harvested.geq(due.get(resource_type_id).copied().unwrap_or(0.)));
+            }
+
+            // The arrays of a resource that grew since the period before were moved there from elsewhere.
+            // A resource is added once for every outpost in its constellation, so its arrays are summed first.
+            let mut moved: Expression = 0.into();
+            if let Some(previous) = &previous {
+                for (key, array_quantity) in &placed {
+                    let anchored = vars.add(variable().min(0));
+                    let before = previous.get(key).cloned().unwrap_or_else(|| 0.into());
+                    constraints.push((array_quantity.clone() - before).leq(anchored));
+                    moved += anchored;
+                }
+                if let Some(max_arrays) = self.reanchoring.max_arrays {
+                    constraints.push(moved.clone().leq(max_arrays));
+                }
+            }
+
+            total_value += value.clone() - self.reanchoring.isk_per_array * moved.clone();
+            placements.push(arrays);
+            previous = Some(placed);
+            period_values.push(value);
+            period_moves.push(moved);
+        }
+
+        let mut solution = vars
+            .maximise(total_value.clone())
+            .using(default_solver);
+        for constraint in constraints {
+            solution = solution.with(constraint);
+        }
+        let solution = match solution.solve() {
+            Ok(solution) => solution,
+            Err(ResolutionError::Infeasible) => return Err("The requirements cannot be harvested in time".to_string()),
+            Err(err) => return Err(format!("Error solving the schedule: {:?}", err)),
+        };
+
+        let periods = placements
+            .into_iter()
+            .zip(period_values)
+            .zip(period_moves)
+            .map(|((arrays, value), moved)| PeriodPlan {
+                production: self.resources
+                    .iter()
+                    .zip(arrays)
+                    .map(|(resource, array_quantity)| (resource.clone(), solution.value(array_quantity)))
+                    .collect(),
+                moved: moved.eval_with(&solution),
+                value: value.eval_with(&solution),
+            })
+            .collect();
+        Ok(SchedulePlan { periods, value: total_value.eval_with(&solution) })
+    }
+}
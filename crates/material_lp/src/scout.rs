@@ -0,0 +1,160 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::data::{constellations_by_region, get_constellation, system_by_planet, CONSTELLATIONS};
+use crate::objective::map_objective;
+use crate::problem::get_resource_value;
+use crate::resource::{celestial_resources_by_constellation, CelestialResource, Material};
+
+/// Planets an outpost harvests when the caller does not say, as `create_outpost` anchors them.
+pub const DEFAULT_PLANETS: i32 = 12;
+/// Arrays on each planet when the caller does not say.
+pub const DEFAULT_ARRAYS: i32 = 26;
+
+/// Outposts a corporation is thinking of anchoring in one constellation.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Prospective {
+    pub outposts: i32,
+    pub planets: i32,
+    pub arrays: i32,
+    pub days: f64,
+}
+
+impl Prospective {
+    /// Arrays each harvested planet holds across the outposts.
+    fn arrays_per_planet(&self) -> f64 {
+        (self.outposts * self.arrays) as f64
+    }
+}
+
+/// What the best planets of a constellation yield for a requirement list.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evaluation {
+    /// ISK per day with every array on the most valuable resource of its planet.
+    pub isk_per_day: f64,
+    /// Average share of each required material the constellation could produce over the days, 1 when all of it.
+    pub coverage: f64,
+    /// Required materials no planet of the constellation has.
+    pub missing: Vec<i64>,
+    /// The harvested planets, most valuable first.
+    pub planets: Vec<i64>,
+}
+
+/// A place to anchor, ranked by requirement coverage then value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Prospect {
+    pub constellation_id: i64,
+    pub constellation: String,
+    /// The system holding most of the harvested planets.
+    pub system: String,
+    pub evaluation: Evaluation,
+}
+
+/// Rate the constellation's resources for the materials, an upper bound the solver meets when the requirements do not bind.
+pub fn evaluate(resources: &[CelestialResource], materials: &[Material], prospective: &Prospective) -> Evaluation {
+    let (_, value) = map_objective(materials.to_vec());
+    let mut planets: BTreeMap<i64, Vec<&CelestialResource>> = BTreeMap::new();
+    for resource in resources {
+        planets.entry(resource.planet_id).or_default().push(resource);
+    }
+    let harvested = (prospective.planets.max(0) as usize).min(planets.len());
+    let hours = prospective.days * 24.;
+
+    let mut best: Vec<(i64, f64)> = planets
+        .iter()
+        .map(|(planet_id, resources)| {
+            let per_array = resources
+                .iter()
+                .map(|resource| get_resource_value(resource, &value) * 24.)
+                .fold(0., f64::max);
+            (*planet_id, per_array)
+        })
+        .collect();
+    best.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    best.truncate(harvested);
+    let isk_per_day = best.iter().map(|(_, per_array)| per_array * prospective.arrays_per_planet()).sum();
+
+    let mut required: HashMap<i64, f64> = HashMap::new();
+    for material in materials {
+        *required.entry(material.resource_type_id).or_default() += material.quantity as f64;
+    }
+    let mut missing = Vec::new();
+    let mut covered = 0.;
+    for (&resource_type_id, &quantity) in &required {
+        let mut outputs: Vec<f64> = planets
+            .values()
+            .filter_map(|resources| resources
+                .iter()
+                .filter(|resource| resource.resource_type_id == resource_type_id)
+                .map(|resource| resource.init_output)
+                .reduce(f64::max))
+            .collect();
+        if outputs.is_empty() {
+            missing.push(resource_type_id);
+            continue;
+        }
+        outputs.sort_by(|a, b| b.total_cmp(a));
+        let producible: f64 = outputs
+            .iter()
+            .take(harvested)
+            .map(|output| output * prospective.arrays_per_planet() * hours)
+            .sum();
+        covered += if quantity <= 0. { 1. } else { (producible / quantity).min(1.) };
+    }
+    missing.sort();
+
+    Evaluation {
+        isk_per_day,
+        coverage: if required.is_empty() { 1. } else { covered / required.len() as f64 },
+        missing,
+        planets: best.into_iter().map(|(planet_id, _)| planet_id).collect(),
+    }
+}
+
+/// Order evaluations by coverage, then ISK per day.
+pub fn rank<T>(prospects: &mut [T], evaluation: impl Fn(&T) -> &Evaluation) {
+    prospects.sort_by(|a, b| {
+        let (a, b) = (evaluation(a), evaluation(b));
+        b.coverage.total_cmp(&a.coverage).then(b.isk_per_day.total_cmp(&a.isk_per_day))
+    });
+}
+
+/// The `limit` best constellations of the region, or all of New Eden, to anchor the prospective outposts in for the materials.
+pub fn scout(materials: &[Material], prospective: &Prospective, region_id: Option<i64>, limit: usize) -> Vec<Prospect> {
+    let constellations: Vec<i64> = match region_id {
+        Some(region_id) => constellations_by_region(region_id),
+        None => CONSTELLATIONS.keys().copied().collect(),
+    };
+    let mut prospects: Vec<Prospect> = constellations
+        .into_iter()
+        .filter_map(|constellation_id| {
+            let constellation = get_constellation(constellation_id)?;
+            let resources = celestial_resources_by_constellation(constellation_id).ok()?;
+            if resources.is_empty() {
+                return None;
+            }
+            Some(Prospect {
+                constellation_id,
+                constellation: constellation.en_name.to_string(),
+                system: String::new(),
+                evaluation: evaluate(&resources, materials, prospective),
+            })
+        })
+        .collect();
+    rank(&mut prospects, |prospect| &prospect.evaluation);
+    prospects.truncate(limit);
+
+    for prospect in &mut prospects {
+        let mut systems: BTreeMap<&str, usize> = BTreeMap::new();
+        for planet_id in &prospect.evaluation.planets {
+            if let Some(system) = system_by_planet(*planet_id) {
+                *systems.entry(&system.en_name).or_default() += 1;
+            }
+        }
+        prospect.system = systems
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+            .map(|(system, _)| system.to_string())
+            .unwrap_or_default();
+    }
+    prospects
+}
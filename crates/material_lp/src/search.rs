@@ -0,0 +1,54 @@
+/// Score how well a typed `query` matches a `candidate` name, `None` when it does not match at all.
+///
+/// Case is ignored. An exact name beats a prefix, a prefix beats a substring and a substring beats
+/// the query letters merely appearing in order, so `jta` still finds `Jita`.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    let query = query.trim().to_lowercase();
+    let candidate = candidate.to_lowercase();
+    if candidate.is_empty() {
+        return None;
+    }
+    if query.is_empty() {
+        return Some(0);
+    }
+    if candidate == query {
+        return Some(4000);
+    }
+    if candidate.starts_with(&query) {
+        return Some(3000u32.saturating_sub((candidate.len() - query.len()) as u32));
+    }
+    if let Some(index) = candidate.find(&query) {
+        return Some(2000u32.saturating_sub(index as u32));
+    }
+
+    let mut gaps = 0u32;
+    let mut remaining = candidate.chars();
+    for letter in query.chars() {
+        loop {
+            match remaining.next() {
+                Some(next) if next == letter => break,
+                Some(_) => gaps += 1,
+                None => return None,
+            }
+        }
+    }
+    Some(1000u32.saturating_sub(gaps).max(1))
+}
+
+/// The best scoring of several names for the same thing, such as its localized names.
+pub fn best_match<'a>(query: &str, names: impl IntoIterator<Item = &'a str>) -> Option<(u32, &'a str)> {
+    names
+        .into_iter()
+        .filter_map(|name| fuzzy_score(query, name).map(|score| (score, name)))
+        .max_by_key(|(score, _)| *score)
+}
+
+/// Names matching the query, best first and alphabetically among equals, at most `limit` of them.
+pub fn rank<'a>(query: &str, names: impl IntoIterator<Item = &'a str>, limit: usize) -> Vec<&'a str> {
+    let mut matches: Vec<(u32, &str)> = names
+        .into_iter()
+        .filter_map(|name| fuzzy_score(query, name).map(|score| (score, name)))
+        .collect();
+    matches.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(b.1)));
+    matches.into_iter().take(limit).map(|(_, name)| name).collect()
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::problem::{Optimum, ResourceHarvestProblem, Shift};
+
+/// Required materials are raised by this share to price them.
+pub const MATERIAL_STEP: f64 = 0.1;
+/// Unused resources priced, the ones worth the most per array.
+pub const REDUCED_COSTS: usize = 10;
+/// Slack below this is a constraint the plan uses up.
+const BINDING: f64 = 1e-6;
+
+/// ISK per day one more array in the constellation or on the planet adds to the plan.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArrayValue<K> {
+    pub key: K,
+    /// `None` when the plan cannot use the array.
+    pub isk_per_day: Option<f64>,
+}
+
+/// ISK the plan loses over the whole period when a material minimum is raised by `MATERIAL_STEP`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MaterialCost {
+    pub resource_type_id: i64,
+    /// `None` when the raised minimum cannot be met.
+    pub isk: Option<f64>,
+}
+
+/// ISK per day the plan loses by placing an array on a resource it left unused.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReducedCost {
+    pub planet_id: i64,
+    pub resource_type_id: i64,
+    /// `None` when an array there breaks a constraint.
+    pub isk_per_day: Option<f64>,
+}
+
+/// How the best plan answers to its constraints, each found by solving again with one of them moved.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Sensitivity {
+    pub isk_per_day: f64,
+    /// Every constellation, most valuable first.
+    pub constellations: Vec<ArrayValue<String>>,
+    /// Planets whose arrays the plan uses up, most valuable first.
+    pub planets: Vec<ArrayValue<i64>>,
+    /// Every required material, costliest first.
+    pub materials: Vec<MaterialCost>,
+    /// The unused resources worth the most per array, cheapest to adopt first.
+    pub reduced_costs: Vec<ReducedCost>,
+}
+
+/// Price the constraints of the problem around its best plan `base`.
+pub fn analyse(harvest: &ResourceHarvestProblem, base: &Optimum) -> Result<Sensitivity, String> {
+    let days = harvest.days;
+    let per_day = |optimum: Option<Optimum>| optimum.map(|optimum| (optimum.value - base.value) / days);
+    let resources = harvest.resources();
+
+    let mut constellations = Vec::new();
+    for key in harvest.available_key.keys() {
+        let shifted = harvest.optimum(&Shift::Key(key.clone()))?;
+        constellations.push(ArrayValue { key: key.clone(), isk_per_day: per_day(shifted) });
+    }
+
+    let mut consumed_planet: BTreeMap<i64, f64> = BTreeMap::new();
+    let mut produced: BTreeMap<i64, f64> = BTreeMap::new();
+    for (resource, arrays) in resources.iter().zip(&base.arrays) {
+        *consumed_planet.entry(resource.planet_id).or_default() += arrays;
+        *produced.entry(resource.resource_type_id).or_default() += arrays * resource.init_output * days * 24.;
+    }
+
+    let mut planets = Vec::new();
+    for (planet_id, consumed) in consumed_planet {
+        let available = harvest.available_planet.get(&planet_id).copied().unwrap_or(0) as f64;
+        if consumed + BINDING < available {
+            continue;
+        }
+        let shifted = harvest.optimum(&Shift::Planet(planet_id))?;
+        planets.push(ArrayValue { key: planet_id, isk_per_day: per_day(shifted) });
+    }
+
+    let mut materials = Vec::new();
+    for (&resource_type_id, &minimum) in &harvest.minimum_output {
+        let produced = produced.get(&resource_type_id).copied().unwrap_or(0.);
+        let isk = if produced + BINDING >= minimum * (1. + MATERIAL_STEP) {
+            Some(0.)
+        } else {
+            harvest
+                .optimum(&Shift::Minimum(resource_type_id, 1. + MATERIAL_STEP))?
+                .map(|optimum| base.value - optimum.value)
+        };
+        materials.push(MaterialCost { resource_type_id, isk });
+    }
+
+    // Outposts sharing a constellation add its resources once each, a resource is unused when no copy is.
+    let mut unused: BTreeMap<(i64, i64), (usize, bool)> = BTreeMap::new();
+    for (index, (resource, arrays)) in resources.iter().zip(&base.arrays).enumerate() {
+        let entry = unused
+            .entry((resource.planet_id, resource.resource_type_id))
+            .or_insert((index, true));
+        entry.1 &= *arrays < BINDING;
+    }
+    let mut candidates: Vec<(usize, (i64, i64))> = unused
+        .into_iter()
+        .filter(|(_, (_, unused))| *unused)
+        .map(|(key, (index, _))| (index, key))
+        .collect();
+    candidates.sort_by(|(a, _), (b, _)| harvest.resource_value(*b).total_cmp(&harvest.resource_value(*a)));
+    let mut reduced_costs = Vec::new();
+    for (index, (planet_id, resource_type_id)) in candidates.into_iter().take(REDUCED_COSTS) {
+        let shifted = harvest.optimum(&Shift::Force(index))?;
+        reduced_costs.push(ReducedCost { planet_id, resource_type_id, isk_per_day: per_day(shifted) });
+    }
+
+    let descending = |value: Option<f64>| -value.unwrap_or(f64::NEG_INFINITY);
+    constellations.sort_by(|a, b| descending(a.isk_per_day).total_cmp(&descending(b.isk_per_day)));
+    planets.sort_by(|a, b| descending(a.isk_per_day).total_cmp(&descending(b.isk_per_day)));
+    materials.sort_by(|a, b| b.isk.unwrap_or(f64::INFINITY).total_cmp(&a.isk.unwrap_or(f64::INFINITY)));
+    reduced_costs.sort_by(|a, b| descending(a.isk_per_day).total_cmp(&descending(b.isk_per_day)));
+
+    Ok(Sensitivity {
+        isk_per_day: base.value / days,
+        constellations,
+        planets,
+        materials,
+        reduced_costs,
+    })
+}
@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+
+use crate::objective::map_objective;
+use crate::problem::get_resource_value;
+use crate::resource::{CelestialResource, Material};
+use crate::{fuel_required, FUEL_TYPE_ID};
+
+/// Allocations below this many arrays are solver noise.
+const MINIMUM_ARRAYS: f64 = 0.005;
+
+/// Arrays the solver placed on one resource of one planet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Harvest {
+    pub planet_id: i64,
+    pub resource_type_id: i64,
+    pub arrays: f64,
+    pub units_per_day: f64,
+    pub isk_per_day: f64,
+}
+
+/// The harvests of every outpost sharing a constellation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Section {
+    pub key: String,
+    pub harvests: Vec<Harvest>,
+    pub arrays: f64,
+    pub isk_per_day: f64,
+}
+
+/// The harvests of every constellation in one region.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RegionTotal {
+    pub region_id: i64,
+    /// Keys of the sections harvesting in the region.
+    pub sections: Vec<String>,
+    pub arrays: f64,
+    pub isk_per_day: f64,
+}
+
+/// How much of a required material the plan produces over the whole period.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Progress {
+    pub resource_type_id: i64,
+    pub name: Box<str>,
+    pub required: f64,
+    pub produced: f64,
+}
+
+impl Progress {
+    pub fn ratio(&self) -> f64 {
+        if self.required <= 0. {
+            1.
+        } else {
+            (self.produced / self.required).clamp(0., 1.)
+        }
+    }
+
+    pub fn met(&self) -> bool {
+        self.produced + 0.5 >= self.required
+    }
+}
+
+/// A solver result totalled per constellation and checked against the problem's materials.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Summary {
+    pub sections: Vec<Section>,
+    pub isk_per_day: f64,
+    pub fuel: f64,
+    pub materials: Vec<Progress>,
+}
+
+/// Total the solver result, fuel is taken out of the fuel material before comparing it with its requirement.
+pub fn summarize(
+    result: &[(CelestialResource, f64)],
+    materials: &[Material],
+    days: f64,
+    outposts: usize,
+) -> Summary {
+    let (_, value) = map_objective(materials.to_vec());
+
+    // Outposts in the same constellation add the same resources, merge them per planet and resource.
+    let mut harvests: BTreeMap<String, BTreeMap<(i64, i64), Harvest>> = BTreeMap::new();
+    for (resource, arrays) in result.iter().filter(|(_, arrays)| *arrays >= MINIMUM_ARRAYS) {
+        let harvest = harvests
+            .entry(resource.key.clone())
+            .or_default()
+            .entry((resource.planet_id, resource.resource_type_id))
+            .or_insert(Harvest {
+                planet_id: resource.planet_id,
+                resource_type_id: resource.resource_type_id,
+                arrays: 0.,
+                units_per_day: 0.,
+                isk_per_day: 0.,
+            });
+        harvest.arrays += arrays;
+        harvest.units_per_day += resource.init_output * arrays * 24.;
+        harvest.isk_per_day += get_resource_value(resource, &value) * arrays * 24.;
+    }
+
+    let sections: Vec<Section> = harvests
+        .into_iter()
+        .map(|(key, harvests)| {
+            let harvests: Vec<Harvest> = harvests.into_values().collect();
+            Section {
+                key,
+                arrays: harvests.iter().map(|harvest| harvest.arrays).sum(),
+                isk_per_day: harvests.iter().map(|harvest| harvest.isk_per_day).sum(),
+                harvests,
+            }
+        })
+        .collect();
+
+    let fuel = fuel_required(days, outposts as f64);
+    let produced = |resource_type_id: i64| -> f64 {
+        sections
+            .iter()
+            .flat_map(|section| &section.harvests)
+            .filter(|harvest| harvest.resource_type_id == resource_type_id)
+            .map(|harvest| harvest.units_per_day * days)
+            .sum()
+    };
+    let materials = materials
+        .iter()
+        .map(|material| {
+            let mut produced = produced(material.resource_type_id);
+            if material.resource_type_id == FUEL_TYPE_ID {
+                produced = (produced - fuel).max(0.);
+            }
+            Progress {
+                resource_type_id: material.resource_type_id,
+                name: material.name.clone(),
+                required: material.quantity as f64,
+                produced,
+            }
+        })
+        .collect();
+
+    Summary {
+        isk_per_day: sections.iter().map(|section| section.isk_per_day).sum(),
+        sections,
+        fuel,
+        materials,
+    }
+}
+
+/// Total the summary per region, `region_of` maps a planet to its region, most valuable region first.
+pub fn by_region(summary: &Summary, region_of: impl Fn(i64) -> Option<i64>) -> Vec<RegionTotal> {
+    let mut regions: BTreeMap<i64, RegionTotal> = BTreeMap::new();
+    for section in &summary.sections {
+        for harvest in &section.harvests {
+            let Some(region_id) = region_of(harvest.planet_id) else {
+                continue;
+            };
+            let region = regions.entry(region_id).or_insert(RegionTotal {
+                region_id,
+                sections: vec![],
+                arrays: 0.,
+                isk_per_day: 0.,
+            });
+            if !region.sections.contains(&section.key) {
+                region.sections.push(section.key.clone());
+            }
+            region.arrays += harvest.arrays;
+            region.isk_per_day += harvest.isk_per_day;
+        }
+    }
+    let mut regions: Vec<RegionTotal> = regions.into_values().collect();
+    regions.sort_by(|a, b| b.isk_per_day.total_cmp(&a.isk_per_day));
+    regions
+}
@@ -38,6 +38,20 @@ mod tests {
         assert!(materials.unwrap().contains(&material));
     }
 
+    #[test]
+    fn parse_an_unknown_material() {
+        let materials = parse_decomposed_list("ID	Names	Quantity	Valuation 
+        1	Lustering Alloy	4	10100069.2 
+        2	Liquid Ozon	1	171.78 
+        ");
+        assert_eq!(materials.unwrap_err().to_string(), "Line 3: Liquid Ozon is not a known material");
+
+        let materials = parse_decomposed_list("ID	Names	Quantity	Valuation 
+        1	Lustering Alloy	four	10100069.2 
+        ");
+        assert!(materials.unwrap_err().to_string().starts_with("Line 2: the quantity four"));
+    }
+
     #[test]
     fn map_an_objective() {
         let materials = parse_decomposed_list("ID	Names	Quantity	Valuation 
@@ -62,7 +76,7 @@ mod tests {
                 valuation: 10100069.2
             }
         ];
-        push_material("1	Lustering Alloy	4	10100069.2 ", &mut result);
+        push_material("1	Lustering Alloy	4	10100069.2 ", &mut result).expect("Failed to push material");
         assert_eq!(result, materials);
     }
 
@@ -82,7 +96,7 @@ mod tests {
             create_outpost(&db, "Outpost5", "Mohas", "Emily").await,
             create_outpost(&db, "Outpost6", "Mohas", "Fiona").await,
         ];
-        let (available_outpost, available_planet, celestial_resources) = map_constellation(outposts);
+        let (available_outpost, available_planet, celestial_resources) = map_constellation(outposts).expect("Failed to map constellations");
         assert_eq!(available_outpost.len(), 3);
         assert_eq!(available_outpost.values().copied().sum::<i32>(), 1872);
         assert_eq!(available_planet.len(), 174);
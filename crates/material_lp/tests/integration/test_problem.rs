@@ -64,7 +64,7 @@ async fn fuel_problem() {
     let materials = parse_decomposed_list(constraint).unwrap();
     let (minimum_output, value) = map_objective(materials);
     let outpost_count = outposts.len() as f64;
-    let (available_key, available_planet, _celestial_resources) = map_constellation(outposts);
+    let (available_key, available_planet, _celestial_resources) = map_constellation(outposts).expect("Failed to map constellations");
     let mut harvest = ResourceHarvestProblem::new(
         available_key,
         available_planet,
@@ -110,8 +110,8 @@ async fn using_constellation() {
     ").unwrap();
     let cache = cache::Cache::new(std::time::Duration::from_secs(60));
 
-    let results = match solve_for_constellation(outposts, materials, 7., &cache) {
-        Ok(res) => res,
+    let results = match solve_for_constellation(1, outposts, materials, 7., None, None, false, &cache) {
+        Ok(plan) => plan.production,
         Err(err) => {
             // Handle the error or panic with a message
             panic!("Failed to solve problem: {}", err);
@@ -1,3 +1,12 @@
 pub mod test_cache;
 pub mod test_data;
+pub mod test_portfolio;
+pub mod test_price;
+pub mod test_route;
+pub mod test_schedule;
+pub mod test_scout;
+pub mod test_search;
+pub mod test_sensitivity;
+pub mod test_stability;
+pub mod test_summary;
 pub mod resource_allocation_problem;
\ No newline at end of file
@@ -2,8 +2,15 @@
 mod tests {
     use std::time::Duration;
 
+    use manager::entities::outpost;
     use material_lp::cache::Cache;
     use material_lp::resource::CelestialResource;
+    use material_lp::sensitivity::Sensitivity;
+    use material_lp::{plan_key, Plan};
+
+    fn plan(production: Vec<(CelestialResource, f64)>) -> Plan {
+        Plan { production, sensitivity: Some(Sensitivity::default()) }
+    }
 
     fn celestial_resource_1() -> (CelestialResource, f64) {
         (
@@ -38,7 +45,7 @@ mod tests {
         let cache = Cache::new(Duration::from_secs(60));
         let key = "test_key".to_string();
         let celestial_resources = vec![celestial_resource_1()];
-        let value = Ok(celestial_resources);
+        let value = Ok(plan(celestial_resources));
 
         cache.set(key.clone(), value.clone());
         let cached_value = cache.get(&key);
@@ -51,7 +58,7 @@ mod tests {
         let cache = Cache::new(Duration::from_secs(1));
         let key = "test_key".to_string();
         let celestial_resources = vec![celestial_resource_1()];
-        let value = Ok(celestial_resources);
+        let value = Ok(plan(celestial_resources));
 
         cache.set(key.clone(), value.clone());
         std::thread::sleep(Duration::from_secs(2));
@@ -65,7 +72,7 @@ mod tests {
         let cache = Cache::new(Duration::from_secs(60));
         let key = "test_key".to_string();
         let celestial_resources = vec![celestial_resource_1()];
-        let value = Ok(celestial_resources);
+        let value = Ok(plan(celestial_resources));
 
         cache.set(key.clone(), value.clone());
         std::thread::sleep(Duration::from_secs(1));
@@ -80,8 +87,8 @@ mod tests {
         let key = "test_key".to_string();
         let celestial_resources_1 = vec![celestial_resource_1()];
         let celestial_resources_2 = vec![celestial_resource_2()];
-        let value1 = Ok(celestial_resources_1);
-        let value2 = Ok(celestial_resources_2);
+        let value1 = Ok(plan(celestial_resources_1));
+        let value2 = Ok(plan(celestial_resources_2));
 
         cache.set(key.clone(), value1.clone());
         cache.set(key.clone(), value2.clone());
@@ -101,4 +108,32 @@ mod tests {
 
         assert_eq!(cached_value, Some(value));
     }
+
+    fn outpost(id: i32, system: &str, arrays: i32) -> outpost::Model {
+        outpost::Model {
+            id,
+            name: format!("Outpost {id}"),
+            system: system.to_string(),
+            planets: 6,
+            arrays,
+            capsuleer_id: 1,
+            problem_id: Some(1),
+            active: true,
+        }
+    }
+
+    #[test]
+    fn test_plan_key_tells_apart_corporations_and_outposts() {
+        let outposts = vec![outpost(1, "Tanoo", 22), outpost(2, "Futzchag", 22)];
+        let key = plan_key(1, &outposts, &[], 7., None, None, false);
+
+        let reordered = vec![outposts[1].clone(), outposts[0].clone()];
+        assert_eq!(plan_key(1, &reordered, &[], 7., None, None, false), key);
+        assert_ne!(plan_key(2, &outposts, &[], 7., None, None, false), key);
+        let moved = vec![outpost(1, "Mohas", 22), outpost(2, "Futzchag", 22)];
+        assert_ne!(plan_key(1, &moved, &[], 7., None, None, false), key);
+        let shrunk = vec![outpost(1, "Tanoo", 10), outpost(2, "Futzchag", 22)];
+        assert_ne!(plan_key(1, &shrunk, &[], 7., None, None, false), key);
+        assert_ne!(plan_key(1, &outposts, &[], 7., None, None, true), key);
+    }
 }
\ No newline at end of file
@@ -5,6 +5,9 @@ mod tests {
     use material_lp::data::{system_by_planet, find_item, find_constellation, find_system, find_constellation_by_system, slice_celestials};
     use material_lp::data::{Item, Constellation, Celestial, Resource, System, Planet};
     use material_lp::data::PLANETS;
+    use material_lp::data::{search_constellations, search_systems, search_resources};
+    use material_lp::data::{query_planets, PlanetQuery};
+    use material_lp::data::{constellations_by_region, find_region, get_region, region_by_constellation, search_regions};
 
     #[test]
     fn load_data_celestials_from_json() {
@@ -152,4 +155,58 @@ mod tests {
         let celestials = slice_celestials(*constellation_id).expect("Failed to slice celestials");
         assert_eq!(celestials.len(), 393);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn search_data_by_localized_name() {
+        let constellations = search_constellations("kusw", 5);
+        assert_eq!(constellations.first().map(|(_, constellation)| constellation.en_name.as_ref()), Some("KUSW-P"));
+
+        let system = get_system(30000001).expect("system 30000001");
+        let systems = search_systems(&system.zh_name, 25);
+        assert!(systems.iter().any(|(name, found)| *name == system.zh_name.as_ref() && *found == system));
+    }
+
+    #[test]
+    fn query_planets_in_a_system() {
+        let query = PlanetQuery { system_id: Some(30000001), resource_type_id: Some(42002000012), ..Default::default() };
+        let rows = query_planets(&query, 100);
+        let row = rows.iter().find(|row| row.planet_id == 40000002).expect("planet 40000002");
+        assert_eq!(row.system.as_ref(), "Tanoo");
+        assert_eq!(row.region.as_ref(), "Derelik");
+        assert_eq!(row.celestial_index, 1);
+        assert_eq!(row.resource.as_ref(), "Heavy Water");
+        assert_eq!(row.richness_index, 2);
+        assert!(rows.iter().all(|row| row.system_id == 30000001 && row.resource_type_id == 42002000012));
+        assert!(rows.windows(2).all(|pair| pair[0].init_output >= pair[1].init_output));
+    }
+
+    #[test]
+    fn query_planets_by_output_and_richness() {
+        let query = PlanetQuery { constellation_id: Some(20000001), min_output: Some(5.), min_richness: Some(3), ..Default::default() };
+        let rows = query_planets(&query, 10);
+        assert!(!rows.is_empty() && rows.len() <= 10);
+        assert!(rows.iter().all(|row| row.constellation_id == 20000001 && row.init_output >= 5. && row.richness_index >= 3));
+    }
+
+    #[test]
+    fn search_resources_by_name() {
+        let resources = search_resources("heavy wat", 5);
+        assert_eq!(resources.first().map(|(_, item)| item.en_name.as_ref()), Some("Heavy Water"));
+    }
+
+    #[test]
+    fn group_constellations_by_region() {
+        assert_eq!(region_by_constellation(20000001), Some(10000001));
+        assert!(constellations_by_region(10000001).contains(&20000001));
+        assert!(constellations_by_region(0).is_empty());
+    }
+
+    #[test]
+    fn lookup_region_by_name() {
+        let region_id = find_region("Derelik").expect("Region not found");
+        assert_eq!(*region_id, 10000001);
+        let region = get_region(10000001).expect("region 10000001");
+        let regions = search_regions(&region.zh_name, 25);
+        assert!(regions.iter().any(|(name, found)| *name == region.zh_name.as_ref() && *found == region));
+    }
+}
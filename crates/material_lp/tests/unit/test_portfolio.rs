@@ -0,0 +1,108 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use float_eq::assert_float_eq;
+    use material_lp::portfolio::{Demand, Portfolio, Site};
+    use material_lp::problem::Value;
+    use material_lp::resource::CelestialResource;
+
+    const NANITES: i64 = 42001000031;
+    const COOLANT: i64 = 42001000028;
+
+    fn resource(key: &str, planet_id: i64, resource_type_id: i64) -> CelestialResource {
+        CelestialResource { key: key.to_string(), planet_id, resource_type_id, init_output: 1., ..Default::default() }
+    }
+
+    /// One planet of nanites and one of coolant, two arrays on each.
+    fn site(id: i32, name: &str, serves: &[&str]) -> Site {
+        Site {
+            id,
+            name: name.to_string(),
+            planets: 1,
+            arrays: 2,
+            serves: serves.iter().map(|name| name.to_string()).collect(),
+            resources: vec![resource(name, 1, NANITES), resource(name, 2, COOLANT)],
+        }
+    }
+
+    fn demand(name: &str, priority: i32, minimum_output: HashMap<i64, f64>, value: Value) -> Demand {
+        Demand { name: name.to_string(), priority, days: 1., minimum_output, value }
+    }
+
+    fn arrays(plan: &material_lp::portfolio::PortfolioPlan, demand: &str, resource_type_id: i64) -> f64 {
+        plan.assignments
+            .iter()
+            .filter(|assignment| assignment.demand == demand && assignment.resource.resource_type_id == resource_type_id)
+            .map(|assignment| assignment.arrays)
+            .sum()
+    }
+
+    #[test]
+    fn test_shared_outpost_serves_both_problems() {
+        let mut portfolio = Portfolio::new();
+        portfolio.add_site(site(1, "Home", &["Hull", "Fuel"]));
+        portfolio.add_demand(demand("Hull", 1, HashMap::from([(NANITES, 24.)]), Value { nanites: 1., ..Default::default() }));
+        portfolio.add_demand(demand("Fuel", 2, HashMap::from([(COOLANT, 24.)]), Value { coolant: 1., ..Default::default() }));
+        let plan = portfolio.solve().expect("solve");
+
+        assert!(plan.unmet.is_empty());
+        assert_float_eq!(arrays(&plan, "Hull", NANITES), 1., abs <= 1e-6);
+        assert_float_eq!(arrays(&plan, "Fuel", COOLANT), 1., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_urgent_problem_is_secured_first() {
+        let mut portfolio = Portfolio::new();
+        portfolio.add_site(site(1, "Home", &["Hull", "Fuel"]));
+        // Both need both arrays of the only outpost.
+        portfolio.add_demand(demand("Fuel", 2, HashMap::from([(COOLANT, 48.)]), Value::default()));
+        portfolio.add_demand(demand("Hull", 1, HashMap::from([(NANITES, 48.)]), Value::default()));
+        let plan = portfolio.solve().expect("solve");
+
+        assert_eq!(plan.unmet, vec!["Fuel".to_string()]);
+        assert_float_eq!(arrays(&plan, "Hull", NANITES), 2., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_earlier_deadline_breaks_ties() {
+        let mut portfolio = Portfolio::new();
+        portfolio.add_site(site(1, "Home", &["Hull", "Fuel"]));
+        let mut later = demand("Fuel", 1, HashMap::from([(COOLANT, 96.)]), Value::default());
+        later.days = 2.;
+        portfolio.add_demand(later);
+        portfolio.add_demand(demand("Hull", 1, HashMap::from([(NANITES, 48.)]), Value::default()));
+        let plan = portfolio.solve().expect("solve");
+
+        assert_eq!(plan.unmet, vec!["Fuel".to_string()]);
+    }
+
+    #[test]
+    fn test_outpost_only_serves_linked_problems() {
+        let mut portfolio = Portfolio::new();
+        portfolio.add_site(site(1, "Home", &["Hull"]));
+        portfolio.add_site(site(2, "Away", &["Fuel"]));
+        portfolio.add_demand(demand("Hull", 1, HashMap::new(), Value { nanites: 1., ..Default::default() }));
+        portfolio.add_demand(demand("Fuel", 1, HashMap::new(), Value { coolant: 1., ..Default::default() }));
+        let plan = portfolio.solve().expect("solve");
+
+        assert!(plan.assignments.iter().all(|assignment| (assignment.site == "Home") == (assignment.demand == "Hull")));
+        assert_float_eq!(arrays(&plan, "Hull", NANITES), 2., abs <= 1e-6);
+        assert_float_eq!(arrays(&plan, "Fuel", COOLANT), 2., abs <= 1e-6);
+        assert_float_eq!(plan.value, 4. * 24., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_outposts_sharing_a_name_stay_apart() {
+        // Two capsuleers each anchored an outpost named Home.
+        let mut portfolio = Portfolio::new();
+        portfolio.add_site(site(1, "Home", &["Hull"]));
+        portfolio.add_site(site(2, "Home", &["Fuel"]));
+        portfolio.add_demand(demand("Hull", 1, HashMap::new(), Value { nanites: 1., ..Default::default() }));
+        portfolio.add_demand(demand("Fuel", 1, HashMap::new(), Value { coolant: 1., ..Default::default() }));
+        let plan = portfolio.solve().expect("solve");
+
+        assert!(!plan.assignments.is_empty());
+        assert!(plan.assignments.iter().all(|assignment| (assignment.site_id == 1) == (assignment.demand == "Hull")));
+    }
+}
@@ -0,0 +1,113 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::fs;
+
+    use assert_fs::prelude::*;
+    use float_eq::assert_float_eq;
+    use futures::executor::block_on;
+    use material_lp::price::{end_of_day, parse_prices, PastedList, PriceFile, PriceSnapshot, PriceSource};
+    use material_lp::resource::Material;
+    use manager::entities::{price_book, price_book_entry, price_snapshot};
+    use sea_orm::prelude::DateTimeUtc;
+
+    const NANITES: i64 = 42001000031;
+    const LIQUID_OZONE: i64 = 42002000014;
+
+    fn material(resource_type_id: i64, quantity: i64, valuation: f64) -> Material {
+        Material { resource_type_id, name: resource_type_id.to_string().into(), quantity, valuation }
+    }
+
+    #[test]
+    fn test_revalue_quoted_materials() {
+        let snapshot = PriceSnapshot {
+            source: "prices.csv".to_string(),
+            taken_at: DateTimeUtc::default(),
+            prices: BTreeMap::from([(NANITES, 400.)]),
+        };
+        let mut materials = vec![material(NANITES, 10, 3500.), material(LIQUID_OZONE, 5, 100.)];
+        let unpriced = snapshot.revalue(&mut materials);
+
+        assert_float_eq!(materials[0].valuation, 4000., abs <= 1e-9);
+        assert_float_eq!(materials[1].valuation, 100., abs <= 1e-9);
+        assert_eq!(unpriced, vec![LIQUID_OZONE.to_string().into()]);
+    }
+
+    #[test]
+    fn test_merge_keeps_earlier_prices() {
+        let earlier = PriceSnapshot {
+            source: "Fuel".to_string(),
+            taken_at: DateTimeUtc::default(),
+            prices: BTreeMap::from([(NANITES, 350.), (LIQUID_OZONE, 12.)]),
+        };
+        let mut snapshot = PriceSnapshot {
+            source: "Hull".to_string(),
+            taken_at: DateTimeUtc::default(),
+            prices: BTreeMap::from([(NANITES, 400.)]),
+        };
+        snapshot.merge(&earlier);
+
+        assert_eq!(snapshot.prices, BTreeMap::from([(NANITES, 400.), (LIQUID_OZONE, 12.)]));
+    }
+
+    #[test]
+    fn test_pasted_list_prices_per_unit() {
+        let list = PastedList { name: "Fuel".to_string(), materials: vec![material(NANITES, 10, 3500.), material(LIQUID_OZONE, 0, 0.)] };
+        let snapshot = block_on(list.snapshot()).unwrap();
+
+        assert_eq!(snapshot.source, "Fuel");
+        assert_eq!(snapshot.prices, BTreeMap::from([(NANITES, 350.)]));
+    }
+
+    #[test]
+    fn test_parse_prices() {
+        let csv = format!("resource,price\n{NANITES},350.5\n\n{LIQUID_OZONE}, 12\n");
+        assert_eq!(parse_prices(&csv).unwrap(), BTreeMap::from([(NANITES, 350.5), (LIQUID_OZONE, 12.)]));
+
+        let json = format!("{{\"{NANITES}\": 350.5}}");
+        assert_eq!(parse_prices(&json).unwrap(), BTreeMap::from([(NANITES, 350.5)]));
+
+        assert!(parse_prices(&format!("{NANITES},350\n{LIQUID_OZONE},cheap")).is_err());
+    }
+
+    #[test]
+    fn test_price_file_snapshot() {
+        let file = assert_fs::NamedTempFile::new("prices.csv").unwrap();
+        file.write_str(&format!("{NANITES},410\n")).unwrap();
+        let snapshot = block_on(PriceFile { path: file.path().to_path_buf() }.snapshot()).unwrap();
+
+        assert_eq!(snapshot.prices, BTreeMap::from([(NANITES, 410.)]));
+        assert_eq!(snapshot.taken_at, DateTimeUtc::from(fs::metadata(file.path()).unwrap().modified().unwrap()));
+    }
+
+    #[test]
+    fn test_snapshot_from_recorded_model() {
+        let model = price_snapshot::Model {
+            id: 1,
+            corporation_id: 1,
+            source: "Fuel".to_string(),
+            prices: serde_json::json!({ NANITES.to_string(): 350.0 }),
+            taken_at: DateTimeUtc::default(),
+        };
+
+        assert_eq!(PriceSnapshot::from(model).prices, BTreeMap::from([(NANITES, 350.)]));
+    }
+
+    #[test]
+    fn test_snapshot_from_price_book() {
+        let book = price_book::Model { id: 7, corporation_id: 1, version: 3, actor: "discord:Member".to_string(), created_at: DateTimeUtc::default() };
+        let entries = vec![price_book_entry::Model { id: 1, price_book_id: 7, resource_type_id: NANITES, price: 300. }];
+        let snapshot = PriceSnapshot::from_price_book(book, entries);
+
+        assert_eq!(snapshot.source, "price book v3");
+        assert_eq!(snapshot.prices, BTreeMap::from([(NANITES, 300.)]));
+    }
+
+    #[test]
+    fn test_end_of_day() {
+        let at = end_of_day("2025-01-24").unwrap();
+
+        assert_eq!(at.to_rfc3339(), "2025-01-24T23:59:59+00:00");
+        assert!(end_of_day("last week").is_none());
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use float_eq::assert_float_eq;
+    use material_lp::problem::{Delivery, ResourceHarvestProblem, Shift, Value};
+    use material_lp::resource::CelestialResource;
+    use material_lp::route::{distances, shortest_path};
+
+    const NANITES: i64 = 42001000031;
+
+    /// 1 - 2 - 3 - 4 in a line with a shortcut from 1 to 4 through 5, and 6 cut off.
+    fn stargates() -> HashMap<i64, Vec<i64>> {
+        HashMap::from([
+            (1, vec![2, 5]),
+            (2, vec![1, 3]),
+            (3, vec![2, 4]),
+            (4, vec![3, 5]),
+            (5, vec![1, 4]),
+            (6, vec![]),
+        ])
+    }
+
+    #[test]
+    fn test_distances() {
+        let distances = distances(&stargates(), 1);
+
+        assert_eq!(distances[&1], 0);
+        assert_eq!(distances[&3], 2);
+        assert_eq!(distances[&4], 2);
+        assert!(!distances.contains_key(&6));
+    }
+
+    #[test]
+    fn test_shortest_path() {
+        assert_eq!(shortest_path(&stargates(), 2, 5), Some(vec![2, 1, 5]));
+        assert_eq!(shortest_path(&stargates(), 3, 3), Some(vec![3]));
+        assert_eq!(shortest_path(&stargates(), 1, 6), None);
+    }
+
+    fn resource(planet_id: i64, init_output: f64) -> CelestialResource {
+        CelestialResource {
+            key: "Kimotoro".to_string(),
+            planet_id,
+            resource_type_id: NANITES,
+            init_output,
+            ..Default::default()
+        }
+    }
+
+    /// Two arrays, the far planet yields twice as much.
+    fn harvest(delivery: Delivery) -> ResourceHarvestProblem {
+        let mut harvest = ResourceHarvestProblem::new(
+            HashMap::from([("Kimotoro".to_string(), 2)]),
+            HashMap::from([(1, 2), (2, 2)]),
+            HashMap::new(),
+            Value { nanites: 1., ..Default::default() },
+            1.,
+        );
+        harvest.add_resource(resource(1, 1.));
+        harvest.add_resource(resource(2, 2.));
+        harvest.deliver(delivery);
+        harvest
+    }
+
+    #[test]
+    fn test_delivery_limits_jumps() {
+        let delivery = Delivery { jumps: HashMap::from([(1, 0), (2, 5)]), max_jumps: Some(3), isk_per_unit_jump: 0. };
+        let optimum = harvest(delivery).optimum(&Shift::None).expect("solve").expect("feasible");
+
+        assert_float_eq!(optimum.arrays[0], 2., abs <= 1e-6);
+        assert_float_eq!(optimum.arrays[1], 0., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_delivery_charges_each_jump() {
+        // Hauling 5 jumps at 0.3 a unit leaves the far planet 2 * (1 - 1.5) ISK an hour per array.
+        let delivery = Delivery { jumps: HashMap::from([(1, 0), (2, 5)]), max_jumps: None, isk_per_unit_jump: 0.3 };
+        let harvest = harvest(delivery);
+        let optimum = harvest.optimum(&Shift::None).expect("solve").expect("feasible");
+
+        assert_float_eq!(optimum.arrays[0], 2., abs <= 1e-6);
+        assert_float_eq!(optimum.value, 2. * 24., abs <= 1e-6);
+        assert_float_eq!(harvest.resource_value(1), -24., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_delivery_leaves_out_unreachable_planets() {
+        let delivery = Delivery { jumps: HashMap::from([(1, 0)]), ..Default::default() };
+        let optimum = harvest(delivery).optimum(&Shift::None).expect("solve").expect("feasible");
+
+        assert_float_eq!(optimum.arrays[1], 0., abs <= 1e-6);
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use float_eq::assert_float_eq;
+    use material_lp::problem::Value;
+    use material_lp::resource::CelestialResource;
+    use material_lp::schedule::{Period, Reanchoring, Schedule, SchedulePlan};
+
+    const NANITES: i64 = 42001000031;
+    const COOLANT: i64 = 42001000028;
+
+    /// One array to share between a planet of nanites and a planet of coolant.
+    fn schedule(reanchoring: Reanchoring, periods: Vec<Period>) -> Schedule {
+        let mut schedule = Schedule::new(
+            HashMap::from([("Kimotoro".to_string(), 1)]),
+            HashMap::from([(1, 1), (2, 1)]),
+            reanchoring,
+        );
+        schedule.add_resource(CelestialResource { key: "Kimotoro".to_string(), planet_id: 1, resource_type_id: NANITES, init_output: 1., ..Default::default() });
+        schedule.add_resource(CelestialResource { key: "Kimotoro".to_string(), planet_id: 2, resource_type_id: COOLANT, init_output: 1., ..Default::default() });
+        for period in periods {
+            schedule.add_period(period);
+        }
+        schedule
+    }
+
+    fn period(minimum_output: HashMap<i64, f64>, value: Value) -> Period {
+        Period { days: 1., minimum_output, value }
+    }
+
+    fn arrays(plan: &SchedulePlan, period: usize, resource_type_id: i64) -> f64 {
+        plan.periods[period].production
+            .iter()
+            .filter(|(resource, _)| resource.resource_type_id == resource_type_id)
+            .map(|(_, arrays)| arrays)
+            .sum()
+    }
+
+    #[test]
+    fn test_schedule_follows_changing_requirements() {
+        let plan = schedule(Reanchoring::default(), vec![
+            period(HashMap::from([(NANITES, 24.)]), Value::default()),
+            period(HashMap::from([(COOLANT, 24.)]), Value::default()),
+        ]).solve().expect("solve");
+
+        assert_float_eq!(arrays(&plan, 0, NANITES), 1., abs <= 1e-6);
+        assert_float_eq!(arrays(&plan, 1, COOLANT), 1., abs <= 1e-6);
+        assert_float_eq!(plan.periods[0].moved, 0., abs <= 1e-6);
+        assert_float_eq!(plan.periods[1].moved, 1., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_schedule_carries_harvest_forward() {
+        // Coolant due after the second period may be harvested during the first.
+        let plan = schedule(Reanchoring::default(), vec![
+            period(HashMap::new(), Value { nanites: 1., ..Default::default() }),
+            period(HashMap::from([(COOLANT, 24.)]), Value { nanites: 2., ..Default::default() }),
+        ]).solve().expect("solve");
+
+        assert_float_eq!(arrays(&plan, 0, COOLANT), 1., abs <= 1e-6);
+        assert_float_eq!(arrays(&plan, 1, NANITES), 1., abs <= 1e-6);
+        assert_float_eq!(plan.value, 2. * 24., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_reanchoring_cost_keeps_arrays() {
+        // Coolant is worth 12 ISK more in the second period.
+        let periods = || vec![
+            period(HashMap::new(), Value { nanites: 1., ..Default::default() }),
+            period(HashMap::new(), Value { nanites: 1., coolant: 1.5, ..Default::default() }),
+        ];
+        let costly = schedule(Reanchoring { isk_per_array: 20., max_arrays: None }, periods()).solve().expect("solve");
+        let cheap = schedule(Reanchoring { isk_per_array: 5., max_arrays: None }, periods()).solve().expect("solve");
+
+        assert_float_eq!(arrays(&costly, 1, NANITES), 1., abs <= 1e-6);
+        assert_float_eq!(arrays(&cheap, 1, COOLANT), 1., abs <= 1e-6);
+        assert_float_eq!(cheap.value, 24. + 36. - 5., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_reanchoring_limit() {
+        let result = schedule(Reanchoring { isk_per_array: 0., max_arrays: Some(0.) }, vec![
+            period(HashMap::from([(NANITES, 24.)]), Value::default()),
+            period(HashMap::from([(COOLANT, 24.)]), Value::default()),
+        ]).solve();
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use material_lp::resource::{CelestialResource, Material};
+    use material_lp::scout::{evaluate, rank, Prospective};
+
+    const NANITES: i64 = 42001000031;
+    const COOLANT: i64 = 42001000028;
+    const NOBLE_GAS: i64 = 42001000019;
+
+    fn resource(planet_id: i64, resource_type_id: i64, init_output: f64) -> CelestialResource {
+        CelestialResource {
+            key: "Kimotoro".to_string(),
+            planet_id,
+            resource_type_id,
+            init_output,
+            ..Default::default()
+        }
+    }
+
+    fn material(resource_type_id: i64, name: &str, quantity: i64, valuation: f64) -> Material {
+        Material { resource_type_id, name: name.into(), quantity, valuation }
+    }
+
+    fn resources() -> Vec<CelestialResource> {
+        vec![
+            resource(1, NANITES, 1.),
+            resource(2, COOLANT, 1.),
+            resource(2, NANITES, 0.5),
+            resource(3, COOLANT, 2.),
+        ]
+    }
+
+    fn prospective(planets: i32) -> Prospective {
+        Prospective { outposts: 1, planets, arrays: 1, days: 1. }
+    }
+
+    #[test]
+    fn test_value_of_best_planets() {
+        // Nanites are worth 10 a unit, coolant 1.
+        let materials = vec![material(NANITES, "Nanites", 10, 100.), material(COOLANT, "Coolant", 10, 10.)];
+        let evaluation = evaluate(&resources(), &materials, &prospective(2));
+
+        assert_eq!(evaluation.planets, vec![1, 2]);
+        assert_float_eq!(evaluation.isk_per_day, (10. + 5.) * 24., abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_coverage_and_missing() {
+        let materials = vec![
+            material(COOLANT, "Coolant", 96, 96.),
+            material(NOBLE_GAS, "Noble Gas", 1, 1.),
+        ];
+        // The best coolant planet alone yields 48 a day, half of the requirement.
+        let evaluation = evaluate(&resources(), &materials, &prospective(1));
+
+        assert_eq!(evaluation.missing, vec![NOBLE_GAS]);
+        assert_float_eq!(evaluation.coverage, 0.25, abs <= 1e-9);
+
+        let evaluation = evaluate(&resources(), &materials, &prospective(2));
+        assert_float_eq!(evaluation.coverage, 0.375, abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_planets_capped_by_constellation() {
+        let materials = vec![material(NANITES, "Nanites", 1, 1.)];
+        let evaluation = evaluate(&resources(), &materials, &prospective(12));
+
+        assert_eq!(evaluation.planets.len(), 3);
+        assert_float_eq!(evaluation.coverage, 1., abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_rank_by_coverage_then_value() {
+        let materials = vec![material(COOLANT, "Coolant", 96, 96.), material(NANITES, "Nanites", 1, 10.)];
+        let rich = evaluate(&resources(), &materials, &prospective(3));
+        let nanites_only = evaluate(&[resource(4, NANITES, 100.)], &materials, &prospective(3));
+        let coolant_only = evaluate(&[resource(5, COOLANT, 4.)], &materials, &prospective(3));
+
+        let mut prospects = vec![("nanites", nanites_only), ("coolant", coolant_only), ("rich", rich)];
+        rank(&mut prospects, |(_, evaluation)| evaluation);
+        let order: Vec<&str> = prospects.iter().map(|(name, _)| *name).collect();
+
+        assert_eq!(order, vec!["rich", "nanites", "coolant"]);
+    }
+}
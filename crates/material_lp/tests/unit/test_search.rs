@@ -0,0 +1,33 @@
+#[cfg(test)]
+mod tests {
+    use material_lp::search::{best_match, fuzzy_score, rank};
+
+    #[test]
+    fn test_fuzzy_score_orders_match_kinds() {
+        let exact = fuzzy_score("jita", "Jita").unwrap();
+        let prefix = fuzzy_score("ji", "Jita").unwrap();
+        let substring = fuzzy_score("ita", "Jita").unwrap();
+        let letters = fuzzy_score("jta", "Jita").unwrap();
+
+        assert!(exact > prefix);
+        assert!(prefix > substring);
+        assert!(substring > letters);
+        assert_eq!(fuzzy_score("xyz", "Jita"), None);
+        assert_eq!(fuzzy_score("jita", ""), None);
+    }
+
+    #[test]
+    fn test_best_match_uses_any_localized_name() {
+        let names = ["Jita", "吉他", ""];
+        assert_eq!(best_match("吉", names), Some((fuzzy_score("吉", "吉他").unwrap(), "吉他")));
+        assert_eq!(best_match("ji", names).map(|(_, name)| name), Some("Jita"));
+        assert_eq!(best_match("amarr", names), None);
+    }
+
+    #[test]
+    fn test_rank_limits_and_orders() {
+        let names = ["Perimeter", "Jita", "Jatate", "New Caldari"];
+        assert_eq!(rank("j", names, 10), vec!["Jita", "Jatate"]);
+        assert_eq!(rank("", names, 2), vec!["Jatate", "Jita"]);
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use float_eq::assert_float_eq;
+    use material_lp::problem::{ResourceHarvestProblem, Shift, Value};
+    use material_lp::resource::CelestialResource;
+    use material_lp::sensitivity::{analyse, ArrayValue, Sensitivity};
+
+    const NANITES: i64 = 42001000031;
+    const COOLANT: i64 = 42001000028;
+
+    fn resource(planet_id: i64, resource_type_id: i64, init_output: f64) -> CelestialResource {
+        CelestialResource {
+            key: "Kimotoro".to_string(),
+            planet_id,
+            resource_type_id,
+            init_output,
+            ..Default::default()
+        }
+    }
+
+    /// Three arrays over two planets of two arrays each, one of them must harvest a day of coolant.
+    fn harvest() -> ResourceHarvestProblem {
+        let value = Value { nanites: 10., coolant: 1., ..Default::default() };
+        let mut harvest = ResourceHarvestProblem::new(
+            HashMap::from([("Kimotoro".to_string(), 3)]),
+            HashMap::from([(1, 2), (2, 2)]),
+            HashMap::from([(COOLANT, 24.)]),
+            value,
+            1.,
+        );
+        harvest.add_resource(resource(1, NANITES, 1.));
+        harvest.add_resource(resource(2, COOLANT, 1.));
+        harvest.add_resource(resource(2, NANITES, 0.5));
+        harvest
+    }
+
+    fn analysed(harvest: &ResourceHarvestProblem) -> Sensitivity {
+        let base = harvest.optimum(&Shift::None).unwrap().unwrap();
+        analyse(harvest, &base).unwrap()
+    }
+
+    #[test]
+    fn test_shadow_prices() {
+        let sensitivity = analysed(&harvest());
+
+        assert_float_eq!(sensitivity.isk_per_day, 504., abs <= 1e-6);
+        let constellation = &sensitivity.constellations[0];
+        assert_eq!(constellation.key, "Kimotoro");
+        assert_float_eq!(constellation.isk_per_day.unwrap(), 120., abs <= 1e-6);
+        // Only the first planet is full, the total number of arrays stays the same.
+        assert_eq!(sensitivity.planets.len(), 1);
+        assert_eq!(sensitivity.planets[0].key, 1);
+        assert_float_eq!(sensitivity.planets[0].isk_per_day.unwrap(), 0., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_material_cost() {
+        let sensitivity = analysed(&harvest());
+
+        assert_eq!(sensitivity.materials.len(), 1);
+        assert_eq!(sensitivity.materials[0].resource_type_id, COOLANT);
+        assert_float_eq!(sensitivity.materials[0].isk.unwrap(), 21.6, abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_reduced_costs() {
+        let sensitivity = analysed(&harvest());
+
+        assert_eq!(sensitivity.reduced_costs.len(), 1);
+        let reduced_cost = &sensitivity.reduced_costs[0];
+        assert_eq!((reduced_cost.planet_id, reduced_cost.resource_type_id), (2, NANITES));
+        assert_float_eq!(reduced_cost.isk_per_day.unwrap(), -120., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_infeasible_shift() {
+        let mut harvest = harvest();
+        // Both coolant arrays are needed, a tenth more coolant than two arrays make cannot be met.
+        harvest.minimum_output.insert(COOLANT, 48.);
+        let sensitivity = analysed(&harvest);
+
+        assert_eq!(sensitivity.materials[0].isk, None);
+        assert!(!sensitivity.constellations.contains(&ArrayValue { key: "Kimotoro".to_string(), isk_per_day: None }));
+    }
+}
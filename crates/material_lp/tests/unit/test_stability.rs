@@ -0,0 +1,78 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use float_eq::assert_float_eq;
+    use material_lp::problem::{Move, ResourceHarvestProblem, Shift, Stability, Value};
+    use material_lp::resource::CelestialResource;
+
+    const NANITES: i64 = 42001000031;
+    const COOLANT: i64 = 42001000028;
+
+    fn resource(planet_id: i64, resource_type_id: i64) -> CelestialResource {
+        CelestialResource { key: "Kimotoro".to_string(), planet_id, resource_type_id, init_output: 1., ..Default::default() }
+    }
+
+    /// Two arrays anchored on nanites, coolant now worth 12 ISK a day more per array.
+    fn harvest(isk_per_move: f64) -> ResourceHarvestProblem {
+        let mut harvest = ResourceHarvestProblem::new(
+            HashMap::from([("Kimotoro".to_string(), 2)]),
+            HashMap::from([(1, 2), (2, 2)]),
+            HashMap::new(),
+            Value { nanites: 1., coolant: 1.5, ..Default::default() },
+            1.,
+        );
+        harvest.add_resource(resource(1, NANITES));
+        harvest.add_resource(resource(2, COOLANT));
+        harvest.stabilize(Stability { current: HashMap::from([((1, NANITES), 2.)]), isk_per_move });
+        harvest
+    }
+
+    #[test]
+    fn test_stability_keeps_current_layout() {
+        let optimum = harvest(20.).optimum(&Shift::None).expect("solve").expect("feasible");
+
+        assert_float_eq!(optimum.arrays[0], 2., abs <= 1e-6);
+        assert_float_eq!(optimum.value, 2. * 24., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_stability_moves_when_worth_it() {
+        let optimum = harvest(5.).optimum(&Shift::None).expect("solve").expect("feasible");
+
+        assert_float_eq!(optimum.arrays[1], 2., abs <= 1e-6);
+        assert_float_eq!(optimum.value, 2. * 36. - 2. * 5., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_stability_counts_repeated_resources_once() {
+        // Two outposts in the constellation add every resource twice, nanites now worth 12 ISK a day more per array.
+        let mut harvest = ResourceHarvestProblem::new(
+            HashMap::from([("Kimotoro".to_string(), 4)]),
+            HashMap::from([(1, 4), (2, 4)]),
+            HashMap::new(),
+            Value { nanites: 1.5, coolant: 1., ..Default::default() },
+            1.,
+        );
+        for _ in 0..2 {
+            harvest.add_resource(resource(1, NANITES));
+            harvest.add_resource(resource(2, COOLANT));
+        }
+        harvest.stabilize(Stability { current: HashMap::from([((1, NANITES), 2.), ((2, COOLANT), 2.)]), isk_per_move: 20. });
+        let optimum = harvest.optimum(&Shift::None).expect("solve").expect("feasible");
+
+        assert_float_eq!(optimum.arrays[0] + optimum.arrays[2], 2., abs <= 1e-6);
+        assert_float_eq!(optimum.value, 2. * 36. + 2. * 24., abs <= 1e-6);
+    }
+
+    #[test]
+    fn test_stability_moves() {
+        let stability = Stability { current: HashMap::from([((1, NANITES), 2.), ((3, NANITES), 1.)]), isk_per_move: 0. };
+        let moves = stability.moves(&[(resource(1, NANITES), 1.), (resource(2, COOLANT), 1.), (resource(3, NANITES), 1.)]);
+
+        assert_eq!(moves, vec![
+            Move { planet_id: 1, resource_type_id: NANITES, from: 2., to: 1. },
+            Move { planet_id: 2, resource_type_id: COOLANT, from: 0., to: 1. },
+        ]);
+    }
+}
@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod tests {
+    use float_eq::assert_float_eq;
+    use material_lp::fuel_required;
+    use material_lp::resource::{CelestialResource, Material};
+    use material_lp::summary::{by_region, summarize};
+
+    fn resource(key: &str, planet_id: i64, resource_type_id: i64, init_output: f64) -> CelestialResource {
+        CelestialResource {
+            key: key.to_string(),
+            planet_id,
+            resource_type_id,
+            init_output,
+            ..Default::default()
+        }
+    }
+
+    fn material(resource_type_id: i64, name: &str, quantity: i64, valuation: f64) -> Material {
+        Material { resource_type_id, name: name.into(), quantity, valuation }
+    }
+
+    #[test]
+    fn test_summarize_totals_each_constellation() {
+        let result = vec![
+            (resource("Kimotoro", 1, 42002000012, 2.), 1.5),
+            (resource("Kimotoro", 1, 42002000012, 2.), 0.5),
+            (resource("Kimotoro", 2, 42002000012, 2.), 0.001),
+            (resource("Okkamon", 3, 42002000014, 1.), 100.),
+        ];
+        let materials = vec![
+            material(42002000012, "Heavy Water", 100, 1000.),
+            material(42002000014, "Liquid Ozone", 10, 50.),
+        ];
+
+        let summary = summarize(&result, &materials, 2., 1);
+
+        assert_eq!(summary.sections.len(), 2);
+        let kimotoro = &summary.sections[0];
+        assert_eq!(kimotoro.key, "Kimotoro");
+        assert_eq!(kimotoro.harvests.len(), 1);
+        assert_float_eq!(kimotoro.arrays, 2., abs <= 1e-9);
+        assert_float_eq!(kimotoro.isk_per_day, 10. * 2. * 2. * 24., abs <= 1e-9);
+        assert_float_eq!(summary.isk_per_day, 960. + 5. * 100. * 24., abs <= 1e-9);
+        assert_float_eq!(summary.fuel, fuel_required(2., 1.), abs <= 1e-9);
+
+        let heavy_water = &summary.materials[0];
+        assert_float_eq!(heavy_water.produced, 192., abs <= 1e-9);
+        assert!(heavy_water.met());
+        assert_float_eq!(heavy_water.ratio(), 1., abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_summarize_keeps_fuel_out_of_requirements() {
+        let result = vec![(resource("Okkamon", 3, 42002000014, 1.), 100.)];
+        let materials = vec![material(42002000014, "Liquid Ozone", 10, 50.)];
+
+        let summary = summarize(&result, &materials, 1., 1);
+
+        let ozone = &summary.materials[0];
+        assert_float_eq!(ozone.produced, 0., abs <= 1e-9);
+        assert!(!ozone.met());
+        assert_float_eq!(ozone.ratio(), 0., abs <= 1e-9);
+    }
+
+    #[test]
+    fn test_totals_each_region() {
+        let result = vec![
+            (resource("Kimotoro", 1, 42002000012, 2.), 2.),
+            (resource("Okkamon", 3, 42002000014, 1.), 100.),
+            (resource("Okkamon", 4, 42002000012, 1.), 1.),
+        ];
+        let materials = vec![
+            material(42002000012, "Heavy Water", 100, 1000.),
+            material(42002000014, "Liquid Ozone", 10, 50.),
+        ];
+        let summary = summarize(&result, &materials, 1., 1);
+
+        // Planets 1 and 4 are in the Forge, 3 in Lonetrek.
+        let regions = by_region(&summary, |planet_id| Some(if planet_id == 3 { 10000016 } else { 10000002 }));
+
+        assert_eq!(regions.len(), 2);
+        let lonetrek = &regions[0];
+        assert_eq!(lonetrek.region_id, 10000016);
+        assert_eq!(lonetrek.sections, vec!["Okkamon".to_string()]);
+        assert_float_eq!(lonetrek.isk_per_day, 5. * 100. * 24., abs <= 1e-9);
+        let forge = &regions[1];
+        assert_eq!(forge.sections, vec!["Kimotoro".to_string(), "Okkamon".to_string()]);
+        assert_float_eq!(forge.arrays, 3., abs <= 1e-9);
+        assert_float_eq!(forge.isk_per_day, 10. * 2. * 2. * 24. + 10. * 24., abs <= 1e-9);
+    }
+}
@@ -0,0 +1,104 @@
+use jsonwebtoken::{decode, encode, DecodingKey, EncodingKey, Header, Validation};
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::serde::{Deserialize, Serialize};
+use rocket::State;
+use sea_orm::*;
+
+use manager::entities::{prelude::*, *};
+
+const TOKEN_LIFETIME: u64 = 60 * 60 * 24 * 30;
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct Claims {
+    pub sub: String,
+    pub corporation_id: i32,
+    pub exp: u64,
+}
+
+pub struct IdentityProvider {
+    key: String,
+}
+
+impl IdentityProvider {
+    pub fn new(key: &str) -> Self {
+        IdentityProvider { key: key.to_string() }
+    }
+
+    pub fn issue(&self, principal: &str, corporation_id: i32) -> Result<String, jsonwebtoken::errors::Error> {
+        let claims = Claims {
+            sub: principal.to_string(),
+            corporation_id,
+            exp: jsonwebtoken::get_current_timestamp() + TOKEN_LIFETIME,
+        };
+        encode(&Header::default(), &claims, &EncodingKey::from_secret(self.key.as_bytes()))
+    }
+
+    pub fn verify(&self, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+        decode::<Claims>(token, &DecodingKey::from_secret(self.key.as_bytes()), &Validation::default())
+            .map(|data| data.claims)
+    }
+}
+
+/// The corporation and alliance every request is scoped to, taken from the bearer token.
+pub struct Tenant {
+    pub corporation: corporation::Model,
+    pub alliance: alliance::Model,
+    /// Subject of the token, recorded as the actor of changes.
+    pub principal: String,
+}
+
+#[derive(Debug)]
+pub enum TenantError {
+    MissingToken,
+    InvalidToken,
+    InactiveCorporation,
+    Database,
+}
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for Tenant {
+    type Error = TenantError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        let token = match request.headers().get_one("Authorization").and_then(|header| header.strip_prefix("Bearer ")) {
+            Some(token) => token,
+            None => return Outcome::Error((Status::Unauthorized, TenantError::MissingToken)),
+        };
+        let identity_provider = match request.guard::<&State<IdentityProvider>>().await {
+            Outcome::Success(identity_provider) => identity_provider,
+            _ => return Outcome::Error((Status::InternalServerError, TenantError::InvalidToken)),
+        };
+        let db = match request.guard::<&State<DatabaseConnection>>().await {
+            Outcome::Success(db) => db as &DatabaseConnection,
+            _ => return Outcome::Error((Status::InternalServerError, TenantError::InvalidToken)),
+        };
+        let claims = match identity_provider.verify(token) {
+            Ok(claims) => claims,
+            Err(_) => return Outcome::Error((Status::Unauthorized, TenantError::InvalidToken)),
+        };
+
+        let corporation = match Corporation::find_by_id(claims.corporation_id).one(db).await {
+            Ok(Some(corporation)) if corporation.active => corporation,
+            Ok(_) => return Outcome::Error((Status::Forbidden, TenantError::InactiveCorporation)),
+            Err(e) => {
+                rocket::error!("Failed to load tenant: {e}");
+                return Outcome::Error((Status::InternalServerError, TenantError::Database));
+            }
+        };
+        let alliance = match Alliance::find_by_id(corporation.alliance_id).one(db).await {
+            Ok(Some(alliance)) => alliance,
+            Ok(None) => return Outcome::Error((Status::Forbidden, TenantError::InactiveCorporation)),
+            Err(e) => {
+                rocket::error!("Failed to load tenant: {e}");
+                return Outcome::Error((Status::InternalServerError, TenantError::Database));
+            }
+        };
+
+        Outcome::Success(Tenant {
+            corporation,
+            alliance,
+            principal: claims.sub,
+        })
+    }
+}
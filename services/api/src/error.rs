@@ -3,7 +3,14 @@ use rocket::http::{ContentType, Status};
 use rocket::response::{Responder, Response};
 use rocket::Request;
 use rocket::serde::json::serde_json;
+use rocket::serde::{Deserialize, Serialize};
 use std::io::Cursor;
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ErrorResponse {
+    pub error: String,
+}
 
 pub struct ErrorResponder {
     status: Status,
@@ -21,7 +28,8 @@ impl ErrorResponder {
 
 impl<'r> Responder<'r, 'static> for ErrorResponder {
     fn respond_to(self, _: &'r Request<'_>) -> Result<Response<'static>, Status> {
-        let body = serde_json::json!({ "error": self.message }).to_string();
+        let body = serde_json::to_string(&ErrorResponse { error: self.message })
+            .map_err(|_| Status::InternalServerError)?;
         Ok(Response::build()
             .status(self.status)
             .header(ContentType::JSON)
@@ -40,6 +48,24 @@ impl From<DbErr> for ErrorResponder {
     }
 }
 
+impl From<manager::Error> for ErrorResponder {
+    fn from(err: manager::Error) -> ErrorResponder {
+        let status = match &err {
+            manager::Error::NotFound { .. } => Status::NotFound,
+            manager::Error::Duplicate { .. } | manager::Error::Ambiguous { .. } => Status::Conflict,
+            manager::Error::Inactive { .. } => Status::Gone,
+            manager::Error::Unsupported { .. } => Status::UnprocessableEntity,
+            manager::Error::Forbidden { .. } => Status::Forbidden,
+            manager::Error::Connection(_) => Status::ServiceUnavailable,
+            manager::Error::Database(_) => Status::InternalServerError,
+        };
+        ErrorResponder {
+            status,
+            message: err.to_string(),
+        }
+    }
+}
+
 impl From<String> for ErrorResponder {
     fn from(string: String) -> ErrorResponder {
         ErrorResponder { 
@@ -1,2 +1,3 @@
+pub mod auth;
 pub mod error;
 pub mod routes;
\ No newline at end of file
@@ -1,5 +1,6 @@
 // main.rs
 
+mod auth;
 mod error;
 mod routes;
 
@@ -24,6 +25,10 @@ struct Cli {
     /// Identity Provider Key 
     #[arg(short, long, default_value = "precious")]
     secret: String,
+
+    /// Print a bearer token scoped to the corporation id and exit
+    #[arg(short, long)]
+    issue: Option<i32>,
 }
 
 
@@ -32,10 +37,18 @@ async fn rocket() -> _ {
     let args = Cli::parse();
     let address: String = args.address.parse().expect("Invalid IP address");
     let port = args.port;
+    let identity_provider = auth::IdentityProvider::new(&args.secret);
+    if let Some(corporation_id) = args.issue {
+        let token = identity_provider
+            .issue("api", corporation_id)
+            .expect("Failed to issue token");
+        println!("{token}");
+        std::process::exit(0);
+    }
     let config = environment::EnvironmentManager::load_config(&args.config)
         .await
         .expect("Failed to load configuration");
-    let db = database::DatabaseManager::revision(&config)
+    let db = database::DatabaseManager::start(&config, database::MigrationPolicy::from_env())
         .await
         .expect("Failed to connect to database");
 
@@ -47,5 +60,6 @@ async fn rocket() -> _ {
         )
         .manage(db)
         .manage(config)
+        .manage(identity_provider)
         .mount("/api/", routes::routes())
 }
\ No newline at end of file
@@ -0,0 +1,160 @@
+// routes/alliances.rs
+
+use rocket::serde::json::Json;
+use sea_orm::*;
+use rocket::*;
+use rocket::http::Status;
+use rocket::serde::Deserialize;
+use rocket::serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::auth::Tenant;
+use crate::error::*;
+use manager::audit::Actor;
+use manager::entities::alliance;
+use manager::service::{found_alliance, rename_alliance, retire_alliance};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct AllianceResponse {
+    pub id: i32,
+    pub name: String,
+    pub active: bool,
+}
+
+impl From<alliance::Model> for AllianceResponse {
+    fn from(alliance: alliance::Model) -> Self {
+        AllianceResponse {
+            id: alliance.id,
+            name: alliance.name,
+            active: alliance.active,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct AllianceRequest {
+    pub name: String,
+}
+
+fn tenant_alliance(tenant: Tenant, id: i32) -> Result<alliance::Model, ErrorResponder> {
+    if tenant.alliance.id != id {
+        return Err(ErrorResponder::new(Status::NotFound, "Alliance not found"));
+    }
+    Ok(tenant.alliance)
+}
+
+/// Only the executor corporation may rename or close its alliance.
+pub(crate) fn executor_alliance(tenant: Tenant, id: i32) -> Result<alliance::Model, ErrorResponder> {
+    let corporation_id = tenant.corporation.id;
+    let alliance = tenant_alliance(tenant, id)?;
+    if !alliance.is_executor(corporation_id) {
+        return Err(ErrorResponder::new(Status::Forbidden, "Only the executor corporation can change the alliance"));
+    }
+    Ok(alliance)
+}
+
+#[utoipa::path(
+    tag = "alliances",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "The alliance of the caller's corporation", body = Vec<AllianceResponse>),
+        (status = 401, description = "Missing or invalid bearer token"),
+    )
+)]
+#[get("/alliances")]
+async fn alliances(tenant: Tenant) -> Json<Vec<AllianceResponse>> {
+    Json(vec![tenant.alliance.into()])
+}
+
+#[utoipa::path(
+    tag = "alliances",
+    security(("bearer" = [])),
+    params(("id" = i32, Path, description = "Alliance id")),
+    responses(
+        (status = 200, description = "The alliance", body = AllianceResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Alliance not found for the caller", body = ErrorResponse),
+    )
+)]
+#[get("/alliances/<id>")]
+async fn get_alliance(tenant: Tenant, id: i32) -> Result<Json<AllianceResponse>, ErrorResponder> {
+    tenant_alliance(tenant, id).map(|alliance| Json(alliance.into()))
+}
+
+#[utoipa::path(
+    tag = "alliances",
+    security(("bearer" = [])),
+    request_body = AllianceRequest,
+    responses(
+        (status = 201, description = "Alliance founded and joined by the caller's corporation as its executor"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 409, description = "Alliance already registered", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[put("/alliances", data = "<alliance_data>")]
+async fn create_alliance(tenant: Tenant, alliance_data: Json<AllianceRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+
+    found_alliance(db, Actor::Api(&tenant.principal), tenant.corporation.id, &alliance_data.name)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?;
+
+    Ok(Status::Created)
+}
+
+#[utoipa::path(
+    tag = "alliances",
+    security(("bearer" = [])),
+    params(("id" = i32, Path, description = "Alliance id")),
+    request_body = AllianceRequest,
+    responses(
+        (status = 200, description = "Alliance renamed", body = AllianceResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "The caller's corporation is not the executor", body = ErrorResponse),
+        (status = 404, description = "Alliance not found for the caller", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[put("/alliances/<id>", data = "<alliance_data>")]
+async fn update_alliance(tenant: Tenant, id: i32, alliance_data: Json<AllianceRequest>, db: &State<DatabaseConnection>) -> Result<Json<AllianceResponse>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let corporation_id = tenant.corporation.id;
+    let principal = tenant.principal.clone();
+    let alliance = executor_alliance(tenant, id)?;
+
+    rename_alliance(db, Actor::Api(&principal), corporation_id, alliance.id, &alliance_data.name)
+        .await
+        .map(|alliance| Json(alliance.into()))
+        .map_err(|e| ErrorResponder::from(e))
+}
+
+#[utoipa::path(
+    tag = "alliances",
+    security(("bearer" = [])),
+    params(("id" = i32, Path, description = "Alliance id")),
+    responses(
+        (status = 204, description = "Alliance deactivated"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "The caller's corporation is not the executor", body = ErrorResponse),
+        (status = 404, description = "Alliance not found for the caller", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[delete("/alliances/<id>")]
+async fn delete_alliance(tenant: Tenant, id: i32, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let corporation_id = tenant.corporation.id;
+    let principal = tenant.principal.clone();
+    let alliance = executor_alliance(tenant, id)?;
+
+    retire_alliance(db, Actor::Api(&principal), corporation_id, alliance.id)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?;
+
+    Ok(Status::NoContent)
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![alliances, get_alliance, create_alliance, update_alliance, delete_alliance]
+}
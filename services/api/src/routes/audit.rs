@@ -0,0 +1,72 @@
+// routes/audit.rs
+
+use rocket::serde::json::{Json, Value};
+use rocket::*;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::Tenant;
+use crate::error::*;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::AuditEvent;
+
+/// Events returned when the caller does not ask for a number.
+const DEFAULT_LIMIT: u64 = 100;
+const MAX_LIMIT: u64 = 1000;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct AuditEventResponse {
+    pub id: i32,
+    /// `discord:<user>` or `api:<principal>`
+    pub actor: String,
+    /// One of `create`, `update`, `assign` or `delete`
+    pub action: String,
+    pub entity: String,
+    pub entity_id: i32,
+    /// The row before the change, absent when it was created
+    #[schema(value_type = Option<Object>)]
+    pub before: Option<Value>,
+    /// The row after the change
+    #[schema(value_type = Option<Object>)]
+    pub after: Option<Value>,
+    /// RFC 3339 timestamp
+    pub created_at: String,
+}
+
+#[utoipa::path(
+    tag = "audit",
+    security(("bearer" = [])),
+    params(
+        ("entity" = Option<String>, Query, description = "Only changes to this kind of record, e.g. outpost"),
+        ("limit" = Option<u64>, Query, description = "Most events to return, 100 by default and at most 1000"),
+    ),
+    responses(
+        (status = 200, description = "Changes to the caller's corporation, latest first", body = Vec<AuditEventResponse>),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[get("/audit?<entity>&<limit>")]
+async fn audit(tenant: Tenant, entity: Option<&str>, limit: Option<u64>, db: &State<DatabaseConnection>) -> Result<Json<Vec<AuditEventResponse>>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let events = AuditEvent::find_by_corporation(tenant.corporation.id, entity, limit, db)
+        .await?;
+
+    let event_responses: Vec<AuditEventResponse> = events.into_iter().map(|event| AuditEventResponse {
+        id: event.id,
+        actor: event.actor,
+        action: event.action,
+        entity: event.entity,
+        entity_id: event.entity_id,
+        before: event.before,
+        after: event.after,
+        created_at: event.created_at.to_rfc3339(),
+    }).collect();
+
+    Ok(Json(event_responses))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![audit]
+}
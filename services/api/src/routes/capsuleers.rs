@@ -5,15 +5,27 @@ use sea_orm::*;
 use rocket::*;
 use rocket::http::Status;
 use rocket::serde::Deserialize;
+use utoipa::ToSchema;
 
+use crate::auth::Tenant;
 use crate::error::*;
-use manager::entities::{prelude::*, *};
+use manager::audit::Actor;
+use manager::entities::prelude::*;
+use manager::service::register_capsuleer_with_skills;
 
+#[utoipa::path(
+    tag = "capsuleers",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Names of the capsuleers in the caller's corporation", body = Vec<String>),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[get("/capsuleers")]
-async fn capsuleers(db: &State<DatabaseConnection>) -> Result<Json<Vec<String>>, ErrorResponder> {
+async fn capsuleers(tenant: Tenant, db: &State<DatabaseConnection>) -> Result<Json<Vec<String>>, ErrorResponder> {
     let db = db as &DatabaseConnection;
-    let capsuleer_names = capsuleer::Entity::find()
-        .all(db)
+    let capsuleer_names = Capsuleer::find_active_by_corporation(tenant.corporation.id, db)
         .await
         .map(|capsuleers| capsuleers.into_iter().map(|capsuleer| capsuleer.name).collect::<Vec<String>>())
         .map_err(|e| ErrorResponder::from(e))?;
@@ -21,31 +33,38 @@ async fn capsuleers(db: &State<DatabaseConnection>) -> Result<Json<Vec<String>>,
     Ok(Json(capsuleer_names))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct CapsuleerRequest {
     pub name: String,
     pub member: String
 }
 
+#[utoipa::path(
+    tag = "capsuleers",
+    security(("bearer" = [])),
+    request_body = CapsuleerRequest,
+    responses(
+        (status = 201, description = "Capsuleer registered to the member"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Member not found in the caller's corporation", body = ErrorResponse),
+        (status = 409, description = "Capsuleer already registered", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[put("/capsuleers", data = "<capsuleer_data>")]
-async fn create_capsuleer(capsuleer_data: Json<CapsuleerRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+async fn create_capsuleer(tenant: Tenant, capsuleer_data: Json<CapsuleerRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
     let db = db as &DatabaseConnection;
-    let member = Member::find_by_name(&capsuleer_data.member, &db)
+    let member = Member::find_active_by_corporation(tenant.corporation.id, db)
         .await
-        .unwrap()
-        .unwrap();
-    let capsuleer_data = capsuleer_data.into_inner();
-    let capsuleer = capsuleer::ActiveModel {
-        name: ActiveValue::Set(capsuleer_data.name),
-        member_id: ActiveValue::Set(member.id),
-        ..Default::default()
-    };
-
-    Capsuleer::insert(capsuleer)
-        .exec(db)
+        .map_err(|e| ErrorResponder::from(e))?
+        .into_iter()
+        .find(|member| member.name == capsuleer_data.member)
+        .ok_or(ErrorResponder::new(Status::NotFound, "Member not found"))?;
+
+    register_capsuleer_with_skills(db, Actor::Api(&tenant.principal), tenant.corporation.id, &member.name, &capsuleer_data.name, &[])
         .await
         .map_err(|e| ErrorResponder::from(e))?;
-    
+
     Ok(Status::Created)
 }
 
@@ -0,0 +1,203 @@
+// routes/corporations.rs
+
+use rocket::serde::json::Json;
+use sea_orm::*;
+use rocket::*;
+use rocket::http::Status;
+use rocket::serde::Deserialize;
+use rocket::serde::Serialize;
+use utoipa::ToSchema;
+
+use crate::auth::Tenant;
+use crate::error::*;
+use crate::routes::alliances::executor_alliance;
+use manager::audit::Actor;
+use manager::entities::{prelude::*, corporation, corporation_claim};
+use manager::service::{found_corporation, issue_claim, rename_corporation, retire_corporation};
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct CorporationResponse {
+    pub id: i32,
+    pub name: String,
+    pub active: bool,
+    pub alliance_id: i32,
+}
+
+impl From<corporation::Model> for CorporationResponse {
+    fn from(corporation: corporation::Model) -> Self {
+        CorporationResponse {
+            id: corporation.id,
+            name: corporation.name,
+            active: corporation.active,
+            alliance_id: corporation.alliance_id,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct CorporationRequest {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ClaimResponse {
+    /// Passed to the Discord `/setup` command once, within an hour
+    pub token: String,
+    /// RFC 3339 timestamp
+    pub created_at: String,
+}
+
+impl From<corporation_claim::Model> for ClaimResponse {
+    fn from(claim: corporation_claim::Model) -> Self {
+        ClaimResponse {
+            token: claim.token,
+            created_at: claim.created_at.to_rfc3339(),
+        }
+    }
+}
+
+/// Only the caller's own corporation may be changed, other corporations in the alliance are read only.
+fn tenant_corporation(tenant: Tenant, id: i32) -> Result<corporation::Model, ErrorResponder> {
+    if tenant.corporation.id != id {
+        return Err(ErrorResponder::new(Status::Forbidden, "Only the caller's corporation can be changed"));
+    }
+    Ok(tenant.corporation)
+}
+
+#[utoipa::path(
+    tag = "corporations",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Corporations in the caller's alliance", body = Vec<CorporationResponse>),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[get("/corporations")]
+async fn corporations(tenant: Tenant, db: &State<DatabaseConnection>) -> Result<Json<Vec<CorporationResponse>>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let corporations = Corporation::find_by_alliance(tenant.alliance.id, db)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?;
+
+    Ok(Json(corporations.into_iter().map(CorporationResponse::from).collect()))
+}
+
+#[utoipa::path(
+    tag = "corporations",
+    security(("bearer" = [])),
+    params(("id" = i32, Path, description = "Corporation id")),
+    responses(
+        (status = 200, description = "The corporation", body = CorporationResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Corporation not found in the caller's alliance", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[get("/corporations/<id>")]
+async fn get_corporation(tenant: Tenant, id: i32, db: &State<DatabaseConnection>) -> Result<Json<CorporationResponse>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    Corporation::find_by_id(id)
+        .filter(corporation::Column::AllianceId.eq(tenant.alliance.id))
+        .one(db)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?
+        .map(|corporation| Json(corporation.into()))
+        .ok_or(ErrorResponder::new(Status::NotFound, "Corporation not found"))
+}
+
+#[utoipa::path(
+    tag = "corporations",
+    security(("bearer" = [])),
+    request_body = CorporationRequest,
+    responses(
+        (status = 201, description = "Corporation registered in the caller's alliance"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "The caller's corporation is not the executor", body = ErrorResponse),
+        (status = 409, description = "Corporation already registered", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[put("/corporations", data = "<corporation_data>")]
+async fn create_corporation(tenant: Tenant, corporation_data: Json<CorporationRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let corporation_id = tenant.corporation.id;
+    let principal = tenant.principal.clone();
+    let alliance_id = tenant.alliance.id;
+    executor_alliance(tenant, alliance_id)?;
+
+    found_corporation(db, Actor::Api(&principal), corporation_id, alliance_id, &corporation_data.name)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?;
+
+    Ok(Status::Created)
+}
+
+#[utoipa::path(
+    tag = "corporations",
+    security(("bearer" = [])),
+    params(("id" = i32, Path, description = "Corporation id")),
+    request_body = CorporationRequest,
+    responses(
+        (status = 200, description = "Corporation renamed", body = CorporationResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "Not the caller's corporation", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[put("/corporations/<id>", data = "<corporation_data>")]
+async fn update_corporation(tenant: Tenant, id: i32, corporation_data: Json<CorporationRequest>, db: &State<DatabaseConnection>) -> Result<Json<CorporationResponse>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let principal = tenant.principal.clone();
+    let corporation = tenant_corporation(tenant, id)?;
+
+    rename_corporation(db, Actor::Api(&principal), corporation.id, &corporation_data.name)
+        .await
+        .map(|corporation| Json(corporation.into()))
+        .map_err(|e| ErrorResponder::from(e))
+}
+
+#[utoipa::path(
+    tag = "corporations",
+    security(("bearer" = [])),
+    params(("id" = i32, Path, description = "Corporation id")),
+    responses(
+        (status = 204, description = "Corporation deactivated with its members, capsuleers, outposts and problems"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 403, description = "Not the caller's corporation", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[delete("/corporations/<id>")]
+async fn delete_corporation(tenant: Tenant, id: i32, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let principal = tenant.principal.clone();
+    let corporation = tenant_corporation(tenant, id)?;
+
+    retire_corporation(db, Actor::Api(&principal), corporation.id)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?;
+
+    Ok(Status::NoContent)
+}
+
+#[utoipa::path(
+    tag = "corporations",
+    security(("bearer" = [])),
+    responses(
+        (status = 201, description = "A one-time token binding a Discord server to the caller's corporation, or admitting a new corporation to its alliance when the caller is the executor", body = ClaimResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[post("/corporations/claim")]
+async fn claim_corporation(tenant: Tenant, db: &State<DatabaseConnection>) -> Result<(Status, Json<ClaimResponse>), ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let claim = issue_claim(db, Actor::Api(&tenant.principal), tenant.corporation.id).await?;
+
+    Ok((Status::Created, Json(claim.into())))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![corporations, get_corporation, create_corporation, update_corporation, delete_corporation, claim_corporation]
+}
@@ -0,0 +1,88 @@
+// routes/export.rs
+
+use rocket::serde::json::Json;
+use rocket::*;
+use rocket::http::Status;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::Tenant;
+use crate::error::*;
+use manager::audit::Actor;
+use manager::database::DatabaseConnection;
+use manager::export::{self, Export, ImportSummary, OnCollision};
+use material_lp::data::find_system;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct ImportResponse {
+    /// Rows added under their exported name
+    pub created: usize,
+    /// Rows whose name was taken and were merged into the existing row
+    pub merged: usize,
+    /// Rows whose name was taken and were added as `<name> (2)`, `<name> (3)`, ...
+    pub renamed: usize,
+}
+
+impl From<ImportSummary> for ImportResponse {
+    fn from(summary: ImportSummary) -> Self {
+        ImportResponse {
+            created: summary.created,
+            merged: summary.merged,
+            renamed: summary.renamed,
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "export",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Versioned planning state of the caller's corporation: members, capsuleers, skills, problems and outposts", body = Object),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[get("/export")]
+async fn export_corporation(tenant: Tenant, db: &State<DatabaseConnection>) -> Result<Json<Export>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let export = export::export_corporation(db, tenant.corporation.id).await?;
+
+    Ok(Json(export))
+}
+
+#[utoipa::path(
+    tag = "export",
+    security(("bearer" = [])),
+    params(("on_collision" = Option<String>, Query, description = "`merge` into rows whose name is taken, the default, or `rename` the imported rows")),
+    request_body(content = Object, description = "A document returned by GET /api/export, possibly from another deployment"),
+    responses(
+        (status = 200, description = "Export imported into the caller's corporation under new ids", body = ImportResponse),
+        (status = 400, description = "Unknown collision policy", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "The export refers to a row it does not contain", body = ErrorResponse),
+        (status = 422, description = "Export version not supported, or an outpost in a system not found in Eve", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[post("/import?<on_collision>", format = "json", data = "<export>")]
+async fn import_corporation(tenant: Tenant, on_collision: Option<&str>, export: Json<Export>, db: &State<DatabaseConnection>) -> Result<Json<ImportResponse>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let on_collision = match on_collision {
+        None | Some("merge") => OnCollision::Merge,
+        Some("rename") => OnCollision::Rename,
+        Some(_) => return Err(ErrorResponder::new(Status::BadRequest, "on_collision is either merge or rename")),
+    };
+    if let Some(outpost) = export.outposts.iter().find(|outpost| find_system(&outpost.system).is_none()) {
+        return Err(ErrorResponder::new(
+            Status::UnprocessableEntity,
+            &format!("Outpost {} is in {}, which is not found in Eve", outpost.name, outpost.system),
+        ));
+    }
+    let summary = export::import_corporation(db, Actor::Api(&tenant.principal), tenant.corporation.id, &export, on_collision).await?;
+
+    Ok(Json(summary.into()))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![export_corporation, import_corporation]
+}
@@ -5,16 +5,28 @@ use sea_orm::*;
 use rocket::*;
 use rocket::http::Status;
 use rocket::serde::Deserialize;
+use utoipa::ToSchema;
 
+use crate::auth::Tenant;
 use crate::error::*;
-use manager::entities::{prelude::*, *};
+use manager::audit::Actor;
+use manager::entities::prelude::*;
+use manager::service::{register_member, transfer_member};
 
+#[utoipa::path(
+    tag = "members",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Names of the members of the caller's corporation", body = Vec<String>),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[get("/members")]
-async fn members(db: &State<DatabaseConnection>) -> Result<Json<Vec<String>>, ErrorResponder> {
+async fn members(tenant: Tenant, db: &State<DatabaseConnection>) -> Result<Json<Vec<String>>, ErrorResponder> {
     let db = db as &DatabaseConnection;
 
-    let member_names = member::Entity::find()
-        .all(db)
+    let member_names = Member::find_active_by_corporation(tenant.corporation.id, db)
         .await
         .map(|members| members.into_iter().map(|member| member.name).collect::<Vec<String>>())
         .map_err(|e| ErrorResponder::from(e))?;
@@ -22,37 +34,76 @@ async fn members(db: &State<DatabaseConnection>) -> Result<Json<Vec<String>>, Er
     Ok(Json(member_names))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct MemberRequest {
     pub name: String,
 }
 
+#[utoipa::path(
+    tag = "members",
+    security(("bearer" = [])),
+    request_body = MemberRequest,
+    responses(
+        (status = 201, description = "Member registered to the caller's corporation, or already was"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[put("/members", data = "<member_data>")]
-async fn create_member(member_data: Json<MemberRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+async fn create_member(tenant: Tenant, member_data: Json<MemberRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
     let db = db as &DatabaseConnection;
 
-    if let Some(_) = Member::find_by_name(&member_data.name, &db)
-        .await
-        .unwrap() {
-        return Err(ErrorResponder::new(Status::Conflict, "Member already registered"));
-    }
-
-    let member_data = member_data.into_inner();
-    let member = member::ActiveModel {
-        name: ActiveValue::Set(member_data.name),
-        ..Default::default()
-    };
-
-    Member::insert(member)
-        .exec(db)
+    register_member(db, Actor::Api(&tenant.principal), tenant.corporation.id, &member_data.name)
         .await
         .map_err(|e| ErrorResponder::from(e))?;
-    
+
     Ok(Status::Created)
 }
 
+#[derive(Debug, Deserialize, ToSchema)]
+pub struct TransferRequest {
+    /// Name of a corporation in the caller's alliance
+    pub corporation: String,
+}
+
+#[utoipa::path(
+    tag = "members",
+    security(("bearer" = [])),
+    params(("name" = String, Path, description = "Name of a member of the caller's corporation")),
+    request_body = TransferRequest,
+    responses(
+        (status = 200, description = "Member and their capsuleers moved to the corporation"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Member or corporation not found", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[post("/members/<name>/transfer", data = "<transfer_data>")]
+async fn transfer(tenant: Tenant, name: &str, transfer_data: Json<TransferRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+
+    let member = Member::find_active_by_corporation(tenant.corporation.id, db)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?
+        .into_iter()
+        .find(|member| member.name == name)
+        .ok_or(ErrorResponder::new(Status::NotFound, "Member not found"))?;
+    let corporation = Corporation::find_by_alliance(tenant.alliance.id, db)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?
+        .into_iter()
+        .find(|corporation| corporation.name == transfer_data.corporation && corporation.active)
+        .ok_or(ErrorResponder::new(Status::NotFound, "Corporation not found"))?;
+
+    transfer_member(db, Actor::Api(&tenant.principal), tenant.corporation.id, member.id, corporation.id)
+        .await
+        .map_err(|e| ErrorResponder::from(e))?;
+
+    Ok(Status::Ok)
+}
+
 pub fn routes() -> Vec<rocket::Route> {
-    routes![members, create_member]
+    routes![members, create_member, transfer]
 }
 
 
@@ -1,7 +1,14 @@
 // reoutes/mod.rs
 
+pub mod alliances;
+pub mod audit;
+pub mod corporations;
+pub mod export;
 pub mod members;
 pub mod capsuleers;
+pub mod openapi;
+pub mod planets;
+pub mod prices;
 pub mod skills;
 
 use rocket::State;
@@ -10,8 +17,15 @@ use rocket::routes;
 use rocket::serde::json::Json;
 use manager::database::DatabaseConnection;
 
-use crate::error::ErrorResponder;
+use crate::error::{ErrorResponder, ErrorResponse};
 
+#[utoipa::path(
+    tag = "health",
+    responses(
+        (status = 200, description = "Database is reachable", body = String),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[get("/health-check")]
 pub async fn health_check(db: &State<DatabaseConnection>) -> Result<Json<&str>, ErrorResponder> {
     db.ping().await.map_err(|e| ErrorResponder::from(e))?;
@@ -20,9 +34,16 @@ pub async fn health_check(db: &State<DatabaseConnection>) -> Result<Json<&str>,
 
 pub fn routes() -> Vec<rocket::Route> {
     let mut all_routes = Vec::new();
+    all_routes.extend(alliances::routes());
+    all_routes.extend(corporations::routes());
     all_routes.extend(skills::routes());
     all_routes.extend(capsuleers::routes());
     all_routes.extend(members::routes());
+    all_routes.extend(audit::routes());
+    all_routes.extend(export::routes());
+    all_routes.extend(planets::routes());
+    all_routes.extend(prices::routes());
+    all_routes.extend(openapi::routes());
     all_routes.extend(routes![health_check]);
     all_routes
 }
\ No newline at end of file
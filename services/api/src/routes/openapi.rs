@@ -0,0 +1,108 @@
+// routes/openapi.rs
+
+use rocket::serde::json::Json;
+use rocket::*;
+use utoipa::{Modify, OpenApi};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+
+use crate::error::ErrorResponse;
+use super::{alliances, audit, capsuleers, corporations, export, members, planets, prices, skills};
+
+/// Bearer tokens carry the corporation every request is scoped to.
+struct BearerSecurity;
+
+impl Modify for BearerSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+    }
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "eve-anchor",
+        description = "Manage the alliances, corporations, members, capsuleers and skills planning planetary production with eve-anchor, browse who changed them and the resources of every planet, and keep the corporation's buyback prices."
+    ),
+    servers((url = "/api")),
+    modifiers(&BearerSecurity),
+    paths(
+        super::health_check,
+        openapi,
+        alliances::alliances,
+        alliances::get_alliance,
+        alliances::create_alliance,
+        alliances::update_alliance,
+        alliances::delete_alliance,
+        corporations::corporations,
+        corporations::get_corporation,
+        corporations::create_corporation,
+        corporations::update_corporation,
+        corporations::delete_corporation,
+        corporations::claim_corporation,
+        members::members,
+        members::create_member,
+        members::transfer,
+        capsuleers::capsuleers,
+        capsuleers::create_capsuleer,
+        skills::skills,
+        skills::create_skill,
+        audit::audit,
+        export::export_corporation,
+        export::import_corporation,
+        planets::planets,
+        prices::get_price_book,
+        prices::update_price_book,
+    ),
+    components(schemas(
+        ErrorResponse,
+        alliances::AllianceRequest,
+        alliances::AllianceResponse,
+        corporations::CorporationRequest,
+        corporations::CorporationResponse,
+        corporations::ClaimResponse,
+        members::MemberRequest,
+        members::TransferRequest,
+        capsuleers::CapsuleerRequest,
+        skills::SkillRequest,
+        skills::SkillResponse,
+        audit::AuditEventResponse,
+        export::ImportResponse,
+        planets::PlanetResponse,
+        prices::PriceEntry,
+        prices::PriceBookRequest,
+        prices::PriceBookResponse,
+    )),
+    tags(
+        (name = "health", description = "Service availability"),
+        (name = "openapi", description = "This document"),
+        (name = "alliances", description = "The alliance of the caller's corporation"),
+        (name = "corporations", description = "Corporations in the caller's alliance"),
+        (name = "members", description = "Discord members registered with eve-anchor"),
+        (name = "capsuleers", description = "In game characters owned by a member"),
+        (name = "skills", description = "Planetology skills of a capsuleer"),
+        (name = "audit", description = "Who changed what in the caller's corporation"),
+        (name = "export", description = "Back up the caller's corporation or move it between deployments"),
+        (name = "planets", description = "Resources of every planet in New Eden"),
+        (name = "prices", description = "Buyback prices the caller's corporation pays its members"),
+    )
+)]
+pub struct ApiDoc;
+
+#[utoipa::path(
+    tag = "openapi",
+    responses(
+        (status = 200, description = "OpenAPI 3 document describing every route", content_type = "application/json"),
+    )
+)]
+#[get("/openapi.json")]
+pub async fn openapi() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![openapi]
+}
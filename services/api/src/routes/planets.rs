@@ -0,0 +1,110 @@
+// routes/planets.rs
+
+use rocket::serde::json::Json;
+use rocket::*;
+use rocket::http::Status;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::Tenant;
+use crate::error::*;
+use material_lp::data::{find_constellation, find_item, find_region, find_system, query_planets, PlanetQuery, PlanetRow};
+
+/// Rows returned when the caller does not ask for a number.
+const DEFAULT_LIMIT: usize = 100;
+const MAX_LIMIT: usize = 1000;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct PlanetResponse {
+    pub planet_id: i64,
+    pub region_id: i64,
+    pub region: String,
+    pub constellation_id: i64,
+    pub constellation: String,
+    pub system_id: i64,
+    pub system: String,
+    /// Position of the planet in its system, as the game labels it
+    pub celestial_index: i64,
+    pub resource_type_id: i64,
+    pub resource: String,
+    /// Units per hour of one array
+    pub init_output: f64,
+    pub richness_index: i64,
+    pub richness_value: i64,
+}
+
+impl From<PlanetRow> for PlanetResponse {
+    fn from(row: PlanetRow) -> Self {
+        PlanetResponse {
+            planet_id: row.planet_id,
+            region_id: row.region_id,
+            region: row.region.to_string(),
+            constellation_id: row.constellation_id,
+            constellation: row.constellation.to_string(),
+            system_id: row.system_id,
+            system: row.system.to_string(),
+            celestial_index: row.celestial_index,
+            resource_type_id: row.resource_type_id,
+            resource: row.resource.to_string(),
+            init_output: row.init_output,
+            richness_index: row.richness_index,
+            richness_value: row.richness_value,
+        }
+    }
+}
+
+/// Resolve an English name with `find`, a 404 naming the `kind` when it is unknown.
+fn resolve(name: Option<&str>, kind: &str, find: fn(&str) -> Option<&'static i64>) -> Result<Option<i64>, ErrorResponder> {
+    name.map(|name| find(name)
+        .copied()
+        .ok_or_else(|| ErrorResponder::new(Status::NotFound, &format!("No {kind} is named {name}"))))
+        .transpose()
+}
+
+#[utoipa::path(
+    tag = "planets",
+    security(("bearer" = [])),
+    params(
+        ("resource" = Option<String>, Query, description = "Only this resource, by English name, e.g. Heavy Water"),
+        ("region" = Option<String>, Query, description = "Only planets in this region, by English name"),
+        ("constellation" = Option<String>, Query, description = "Only planets in this constellation, by English name"),
+        ("system" = Option<String>, Query, description = "Only planets in this system, by English name"),
+        ("min_output" = Option<f64>, Query, description = "Only resources yielding at least this many units per hour"),
+        ("min_richness" = Option<i64>, Query, description = "Only resources at least this rich"),
+        ("limit" = Option<usize>, Query, description = "Most rows to return, 100 by default and at most 1000"),
+    ),
+    responses(
+        (status = 200, description = "Planet resources matching every filter, highest output first", body = Vec<PlanetResponse>),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Unknown resource, region, constellation or system", body = ErrorResponse),
+    )
+)]
+#[allow(clippy::too_many_arguments)]
+#[get("/planets?<resource>&<region>&<constellation>&<system>&<min_output>&<min_richness>&<limit>")]
+async fn planets(
+    _tenant: Tenant,
+    resource: Option<&str>,
+    region: Option<&str>,
+    constellation: Option<&str>,
+    system: Option<&str>,
+    min_output: Option<f64>,
+    min_richness: Option<i64>,
+    limit: Option<usize>,
+    ) -> Result<Json<Vec<PlanetResponse>>, ErrorResponder> {
+    let query = PlanetQuery {
+        resource_type_id: resolve(resource, "resource", find_item)?,
+        region_id: resolve(region, "region", find_region)?,
+        constellation_id: resolve(constellation, "constellation", find_constellation)?,
+        system_id: resolve(system, "system", find_system)?,
+        min_output,
+        min_richness,
+    };
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT);
+    let rows = query_planets(&query, limit);
+
+    Ok(Json(rows.into_iter().map(PlanetResponse::from).collect()))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![planets]
+}
@@ -0,0 +1,101 @@
+// routes/prices.rs
+
+use rocket::serde::json::Json;
+use rocket::*;
+use rocket::http::Status;
+use rocket::serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::auth::Tenant;
+use crate::error::*;
+use manager::audit::Actor;
+use manager::database::DatabaseConnection;
+use manager::entities::{price_book, price_book_entry};
+use manager::entities::prelude::PriceBook;
+use manager::service::set_prices;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct PriceEntry {
+    pub resource_type_id: i64,
+    /// ISK per unit, absent to take the resource out of the price book
+    pub price: Option<f64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct PriceBookRequest {
+    /// Changes applied to the latest prices, resources left out keep their price
+    pub prices: Vec<PriceEntry>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
+pub struct PriceBookResponse {
+    pub version: i32,
+    /// `discord:<user>` or `api:<principal>`
+    pub actor: String,
+    /// RFC 3339 timestamp
+    pub created_at: String,
+    pub prices: Vec<PriceEntry>,
+}
+
+impl From<(price_book::Model, Vec<price_book_entry::Model>)> for PriceBookResponse {
+    fn from((book, entries): (price_book::Model, Vec<price_book_entry::Model>)) -> Self {
+        PriceBookResponse {
+            version: book.version,
+            actor: book.actor,
+            created_at: book.created_at.to_rfc3339(),
+            prices: entries
+                .into_iter()
+                .map(|entry| PriceEntry { resource_type_id: entry.resource_type_id, price: Some(entry.price) })
+                .collect(),
+        }
+    }
+}
+
+#[utoipa::path(
+    tag = "prices",
+    security(("bearer" = [])),
+    params(("version" = Option<i32>, Query, description = "An earlier version, the latest by default")),
+    responses(
+        (status = 200, description = "Buyback prices of the caller's corporation", body = PriceBookResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "No price book, or no such version", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[get("/price_book?<version>")]
+async fn get_price_book(tenant: Tenant, version: Option<i32>, db: &State<DatabaseConnection>) -> Result<Json<PriceBookResponse>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    let book = PriceBook::find_with_entries(tenant.corporation.id, version, db)
+        .await?
+        .ok_or_else(|| ErrorResponder::new(Status::NotFound, "The corporation has no such price book"))?;
+
+    Ok(Json(book.into()))
+}
+
+#[utoipa::path(
+    tag = "prices",
+    security(("bearer" = [])),
+    request_body = PriceBookRequest,
+    responses(
+        (status = 200, description = "The next version of the price book with the changes applied", body = PriceBookResponse),
+        (status = 400, description = "A price is negative", body = ErrorResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 409, description = "Another version was published at the same time", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
+#[put("/price_book", data = "<request>")]
+async fn update_price_book(tenant: Tenant, request: Json<PriceBookRequest>, db: &State<DatabaseConnection>) -> Result<Json<PriceBookResponse>, ErrorResponder> {
+    let db = db as &DatabaseConnection;
+    if request.prices.iter().any(|entry| entry.price.is_some_and(|price| price < 0.)) {
+        return Err(ErrorResponder::new(Status::BadRequest, "Prices can not be negative"));
+    }
+    let changes: Vec<(i64, Option<f64>)> = request.prices.iter().map(|entry| (entry.resource_type_id, entry.price)).collect();
+    let book = set_prices(db, Actor::Api(&tenant.principal), tenant.corporation.id, &changes).await?;
+
+    Ok(Json(book.into()))
+}
+
+pub fn routes() -> Vec<rocket::Route> {
+    routes![get_price_book, update_price_book]
+}
@@ -6,11 +6,15 @@ use rocket::*;
 use rocket::http::Status;
 use rocket::serde::Deserialize;
 use rocket::serde::Serialize;
+use utoipa::ToSchema;
 
+use crate::auth::Tenant;
 use crate::error::*;
+use manager::audit::Actor;
 use manager::entities::{prelude::*, *};
+use manager::service::{register_skill, NewSkill};
 
-#[derive(Debug, Deserialize, Serialize)]
+#[derive(Debug, Deserialize, Serialize, ToSchema)]
 pub struct SkillResponse {
     pub id: i32,
     pub name: String,
@@ -20,10 +24,22 @@ pub struct SkillResponse {
     pub expert: i32,
 }
 
+#[utoipa::path(
+    tag = "skills",
+    security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Skills of every capsuleer in the caller's corporation", body = Vec<SkillResponse>),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[get("/skills")]
-async fn skills(db: &State<DatabaseConnection>) -> Result<Json<Vec<SkillResponse>>, ErrorResponder> {
+async fn skills(tenant: Tenant, db: &State<DatabaseConnection>) -> Result<Json<Vec<SkillResponse>>, ErrorResponder> {
     let db = db as &DatabaseConnection;
     let skills = skill::Entity::find()
+        .inner_join(capsuleer::Entity)
+        .filter(capsuleer::Column::CorporationId.eq(tenant.corporation.id))
+        .filter(capsuleer::Column::Active.eq(true))
         .all(db)
         .await
         .map_err(|e| ErrorResponder::from(e))?;
@@ -40,7 +56,7 @@ async fn skills(db: &State<DatabaseConnection>) -> Result<Json<Vec<SkillResponse
     Ok(Json(skill_responses))
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct SkillRequest {
     pub name: String,
     pub capsuleer: String,
@@ -49,28 +65,37 @@ pub struct SkillRequest {
     pub expert: i32,
 }
 
+#[utoipa::path(
+    tag = "skills",
+    security(("bearer" = [])),
+    request_body = SkillRequest,
+    responses(
+        (status = 201, description = "Skill registered to the capsuleer"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "Capsuleer not found in the caller's corporation", body = ErrorResponse),
+        (status = 500, description = "Database error", body = ErrorResponse),
+    )
+)]
 #[put("/skills", data = "<skill_data>")]
-async fn create_skill(skill_data: Json<SkillRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
+async fn create_skill(tenant: Tenant, skill_data: Json<SkillRequest>, db: &State<DatabaseConnection>) -> Result<Status, ErrorResponder> {
     let db = db as &DatabaseConnection;
-    let capsuleer = Capsuleer::find_by_name(&skill_data.capsuleer, &db)
+    let capsuleer = Capsuleer::find_active_by_corporation(tenant.corporation.id, db)
         .await
-        .unwrap()
-        .unwrap();
-    let skill_data = skill_data.into_inner();
-    let skill = skill::ActiveModel {
-        name: ActiveValue::Set(skill_data.name),
-        basic: ActiveValue::Set(skill_data.basic),
-        advanced: ActiveValue::Set(skill_data.advanced),
-        expert: ActiveValue::Set(skill_data.expert),
-        capsuleer_id: ActiveValue::Set(capsuleer.id),
-        ..Default::default()
+        .map_err(|e| ErrorResponder::from(e))?
+        .into_iter()
+        .find(|capsuleer| capsuleer.name == skill_data.capsuleer)
+        .ok_or(ErrorResponder::new(Status::NotFound, "Capsuleer not found"))?;
+    let skill = NewSkill {
+        name: &skill_data.name,
+        basic: skill_data.basic,
+        advanced: skill_data.advanced,
+        expert: skill_data.expert,
     };
 
-    Skill::insert(skill)
-        .exec(db)
+    register_skill(db, Actor::Api(&tenant.principal), tenant.corporation.id, capsuleer.id, skill)
         .await
         .map_err(|e| ErrorResponder::from(e))?;
-    
+
     Ok(Status::Created)
 }
 
@@ -2,7 +2,7 @@
 
 use rocket::*;
 use rocket::local::asynchronous::Client;
-use rocket::http::{ContentType, Status};
+use rocket::http::{ContentType, Header, Status};
 use sea_orm::{
     ConnectionTrait,
     Database,
@@ -19,10 +19,20 @@ use manager::database::postgres::database_exists;
 use manager::environment;
 use manager::environment::Configuration;
 use manager::migrator::Migrator;
+use manager::new_alliance;
+use manager::new_corporation;
 
+use api::auth::IdentityProvider;
+use api::routes::alliances;
+use api::routes::audit;
 use api::routes::capsuleers;
+use api::routes::corporations;
+use api::routes::export;
 use api::routes::health_check;
 use api::routes::members;
+use api::routes::openapi;
+use api::routes::planets;
+use api::routes::prices;
 use api::routes::skills;
 
 pub struct Session;
@@ -78,10 +88,18 @@ async fn local_rocket(env: &str) -> rocket::Rocket<Build> {
     rocket::build()
         .manage(config)
         .manage(db)
+        .manage(IdentityProvider::new(TEST_SECRET))
         .mount("/api/", routes![health_check])
+        .mount("/api/", alliances::routes())
+        .mount("/api/", corporations::routes())
         .mount("/api/", members::routes())
         .mount("/api/", capsuleers::routes())
         .mount("/api/", skills::routes())
+        .mount("/api/", audit::routes())
+        .mount("/api/", export::routes())
+        .mount("/api/", planets::routes())
+        .mount("/api/", prices::routes())
+        .mount("/api/", openapi::routes())
 }
 
 pub async fn create_client(env: &str) -> Client {
@@ -89,9 +107,27 @@ pub async fn create_client(env: &str) -> Client {
     Client::tracked(rocket).await.expect("Failed to create client")
 }
 
-pub async fn create_request(client: &Client, url: &str, request: &str) -> Status {
+pub async fn create_tenant(client: &Client, alliance_name: &str, corporation_name: &str) -> String {
+    let db = client.rocket().state::<DatabaseConnection>().expect("managed database");
+    let alliance = new_alliance(db, alliance_name)
+        .await
+        .expect("Failed to add alliance to database");
+    let corporation = new_corporation(db, corporation_name, alliance.last_insert_id)
+        .await
+        .expect("Failed to add corporation to database");
+    IdentityProvider::new(TEST_SECRET)
+        .issue(TEST_PRINCIPAL, corporation.last_insert_id)
+        .expect("Failed to issue token")
+}
+
+pub fn bearer(token: &str) -> Header<'static> {
+    Header::new("Authorization", format!("Bearer {token}"))
+}
+
+pub async fn create_request(client: &Client, token: &str, url: &str, request: &str) -> Status {
     let response = client.put(url)
         .header(ContentType::JSON)
+        .header(bearer(token))
         .body(request)
         .dispatch()
         .await;
@@ -100,6 +136,13 @@ pub async fn create_request(client: &Client, url: &str, request: &str) -> Status
 }
 
 
+pub const TEST_SECRET: &str = "precious";
+pub const TEST_PRINCIPAL: &str = "test";
+pub const TEST_ALLIANCE_NAME: &str = "NRFD";
+pub const TEST_CORPORATION_NAME: &str = "REEF";
+pub const TEST_OTHER_ALLIANCE_NAME: &str = "KEN";
+pub const TEST_OTHER_CORPORATION_NAME: &str = "KRKN";
+
 pub const TEST_MEMBER_NAME: &str = "Sturzaam";
 pub const TEST_MEMBER_REQUEST: &'static str = r##"{
     "name": "Sturzaam"
@@ -120,4 +163,5 @@ pub const TEST_SKILL_REQUEST: &'static str = r##"{
     "expert": 5
 }"##;
 
-pub mod routes;
\ No newline at end of file
+pub mod routes;
+pub mod spec;
\ No newline at end of file
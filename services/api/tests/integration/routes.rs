@@ -2,18 +2,41 @@
 
 #[cfg(test)]
 mod tests {
-    use rocket::http::Status;
+    use rocket::http::{ContentType, Status};
     use rocket::*;
 
+    use crate::TEST_ALLIANCE_NAME;
     use crate::TEST_CAPSULEER_NAME;
     use crate::TEST_CAPSULEER_REQUEST;
+    use crate::TEST_CORPORATION_NAME;
     use crate::TEST_MEMBER_NAME;
     use crate::TEST_MEMBER_REQUEST;
+    use crate::TEST_OTHER_ALLIANCE_NAME;
+    use crate::TEST_OTHER_CORPORATION_NAME;
+    use crate::TEST_PRINCIPAL;
+    use crate::TEST_SECRET;
     use crate::TEST_SKILL_NAME;
     use crate::TEST_SKILL_REQUEST;
+    use crate::bearer;
     use crate::create_client;
     use crate::create_request;
+    use crate::create_tenant;
 
+    use manager::audit::Actor;
+    use manager::entities::prelude::Corporation;
+    use manager::export::EXPORT_VERSION;
+    use manager::service;
+    use manager::service::NewOutpost;
+    use sea_orm::DatabaseConnection;
+
+    use api::auth::IdentityProvider;
+    use api::routes::alliances::AllianceResponse;
+    use api::routes::audit::AuditEventResponse;
+    use api::routes::export::ImportResponse;
+    use api::routes::planets::PlanetResponse;
+    use api::routes::prices::PriceBookResponse;
+    use api::routes::corporations::ClaimResponse;
+    use api::routes::corporations::CorporationResponse;
     use api::routes::skills::SkillResponse;
     use api::routes::skills::SkillRequest;
 
@@ -30,49 +53,52 @@ mod tests {
     #[tokio::test]
     async fn test_create_member() {
         let client = create_client("test").await;
-        let status = create_request(&client, "/api/members", TEST_MEMBER_REQUEST).await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/members", TEST_MEMBER_REQUEST).await;
         assert_eq!(status, Status::Created);
 
-        let response = client.get("/api/members").dispatch().await;
+        let response = client.get("/api/members").header(bearer(&token)).dispatch().await;
         assert_eq!(response.status(), Status::Ok);
 
         let body_str = response.into_string().await.expect("response into string");
         let member_names: Vec<String> = serde_json::from_str(&body_str).expect("deserialize member names");
-        
+
         assert!(member_names.contains(&TEST_MEMBER_NAME.to_string()));
     }
 
     #[tokio::test]
     async fn test_create_capsuleer() {
         let client = create_client("test").await;
-        let status = create_request(&client, "/api/members", TEST_MEMBER_REQUEST).await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/members", TEST_MEMBER_REQUEST).await;
         assert_eq!(status, Status::Created);
-        
-        let status = create_request(&client, "/api/capsuleers", TEST_CAPSULEER_REQUEST).await;
+
+        let status = create_request(&client, &token, "/api/capsuleers", TEST_CAPSULEER_REQUEST).await;
         assert_eq!(status, Status::Created);
 
-        let response = client.get("/api/capsuleers").dispatch().await;
+        let response = client.get("/api/capsuleers").header(bearer(&token)).dispatch().await;
         assert_eq!(response.status(), Status::Ok);
 
         let body_str = response.into_string().await.expect("response into string");
         let capsuleer_names: Vec<String> = serde_json::from_str(&body_str).expect("deserialize capsuleer names");
-        
+
         assert!(capsuleer_names.contains(&TEST_CAPSULEER_NAME.to_string()));
     }
 
     #[tokio::test]
     async fn test_create_capsuleer_skills() {
         let client = create_client("test").await;
-        let status = create_request(&client, "/api/members", TEST_MEMBER_REQUEST).await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/members", TEST_MEMBER_REQUEST).await;
         assert_eq!(status, Status::Created);
-        
-        let status = create_request(&client, "/api/capsuleers", TEST_CAPSULEER_REQUEST).await;
+
+        let status = create_request(&client, &token, "/api/capsuleers", TEST_CAPSULEER_REQUEST).await;
         assert_eq!(status, Status::Created);
-        
-        let status = create_request(&client, "/api/skills", TEST_SKILL_REQUEST).await;
+
+        let status = create_request(&client, &token, "/api/skills", TEST_SKILL_REQUEST).await;
         assert_eq!(status, Status::Created);
 
-        let response = client.get("/api/skills").dispatch().await;
+        let response = client.get("/api/skills").header(bearer(&token)).dispatch().await;
         assert_eq!(response.status(), Status::Ok);
 
         let body_str = response.into_string().await.expect("response into string");
@@ -83,4 +109,331 @@ mod tests {
         assert_eq!(skill.advanced, skill_request.advanced);
         assert_eq!(skill.expert, skill_request.expert);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_requires_bearer_token() {
+        let client = create_client("test").await;
+        let response = client.get("/api/members").dispatch().await;
+        assert_eq!(response.status(), Status::Unauthorized);
+
+        let response = client.get("/api/members").header(bearer("not-a-token")).dispatch().await;
+        assert_eq!(response.status(), Status::Unauthorized);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let other_token = create_tenant(&client, TEST_OTHER_ALLIANCE_NAME, TEST_OTHER_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/members", TEST_MEMBER_REQUEST).await;
+        assert_eq!(status, Status::Created);
+
+        let response = client.get("/api/members").header(bearer(&other_token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let member_names: Vec<String> = serde_json::from_str(&body_str).expect("deserialize member names");
+        assert!(member_names.is_empty());
+
+        let status = create_request(&client, &other_token, "/api/capsuleers", TEST_CAPSULEER_REQUEST).await;
+        assert_eq!(status, Status::NotFound);
+
+        let response = client.get("/api/alliances").header(bearer(&other_token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let alliances: Vec<AllianceResponse> = serde_json::from_str(&body_str).expect("deserialize alliances");
+        assert_eq!(alliances.len(), 1);
+        assert_eq!(alliances[0].name, TEST_OTHER_ALLIANCE_NAME);
+
+        let response = client.get(format!("/api/alliances/{}", alliances[0].id)).header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_manage_corporations() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/corporations", r#"{ "name": "KRKN" }"#).await;
+        assert_eq!(status, Status::Created);
+
+        let response = client.get("/api/corporations").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        let body_str = response.into_string().await.expect("response into string");
+        let corporations: Vec<CorporationResponse> = serde_json::from_str(&body_str).expect("deserialize corporations");
+        assert_eq!(corporations.len(), 2);
+
+        let own = corporations.iter().find(|corporation| corporation.name == TEST_CORPORATION_NAME).expect("own corporation");
+        let other = corporations.iter().find(|corporation| corporation.name == "KRKN").expect("other corporation");
+
+        let member_token = IdentityProvider::new(TEST_SECRET)
+            .issue(TEST_PRINCIPAL, other.id)
+            .expect("Failed to issue token");
+        let status = create_request(&client, &member_token, "/api/corporations", r#"{ "name": "Outsider" }"#).await;
+        assert_eq!(status, Status::Forbidden);
+        let response = client.put(format!("/api/alliances/{}", own.alliance_id))
+            .header(ContentType::JSON)
+            .header(bearer(&member_token))
+            .body(r#"{ "name": "Renamed" }"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+        let response = client.delete(format!("/api/alliances/{}", own.alliance_id)).header(bearer(&member_token)).dispatch().await;
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.put(format!("/api/corporations/{}", other.id))
+            .header(ContentType::JSON)
+            .header(bearer(&token))
+            .body(r#"{ "name": "Renamed" }"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Forbidden);
+
+        let response = client.put(format!("/api/corporations/{}", own.id))
+            .header(ContentType::JSON)
+            .header(bearer(&token))
+            .body(r#"{ "name": "Renamed" }"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let body_str = response.into_string().await.expect("response into string");
+        let renamed: CorporationResponse = serde_json::from_str(&body_str).expect("deserialize corporation");
+        assert_eq!(renamed.name, "Renamed");
+
+        let response = client.delete(format!("/api/corporations/{}", own.id)).header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::NoContent);
+
+        let response = client.get("/api/corporations").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::Forbidden);
+    }
+
+    #[tokio::test]
+    async fn test_claim_corporation() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+
+        let response = client.post("/api/corporations/claim").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::Created);
+        let body_str = response.into_string().await.expect("response into string");
+        let claim: ClaimResponse = serde_json::from_str(&body_str).expect("deserialize claim");
+
+        let db = client.rocket().state::<DatabaseConnection>().expect("managed database");
+        service::setup_guild(db, Actor::Api(TEST_PRINCIPAL), 1, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME, Some(&claim.token))
+            .await
+            .expect("Failed to set up guild with the claim");
+    }
+
+    #[tokio::test]
+    async fn test_manage_alliance() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/alliances", r#"{ "name": "KEN" }"#).await;
+        assert_eq!(status, Status::Created);
+
+        let response = client.get("/api/alliances").header(bearer(&token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let alliances: Vec<AllianceResponse> = serde_json::from_str(&body_str).expect("deserialize alliances");
+        assert_eq!(alliances[0].name, "KEN");
+
+        let response = client.delete(format!("/api/alliances/{}", alliances[0].id)).header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::NoContent);
+
+        let response = client.get(format!("/api/alliances/{}", alliances[0].id)).header(bearer(&token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let alliance: AllianceResponse = serde_json::from_str(&body_str).expect("deserialize alliance");
+        assert!(!alliance.active);
+    }
+
+    #[tokio::test]
+    async fn test_transfer_member() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/corporations", r#"{ "name": "KRKN" }"#).await;
+        assert_eq!(status, Status::Created);
+        let status = create_request(&client, &token, "/api/members", TEST_MEMBER_REQUEST).await;
+        assert_eq!(status, Status::Created);
+        let status = create_request(&client, &token, "/api/capsuleers", TEST_CAPSULEER_REQUEST).await;
+        assert_eq!(status, Status::Created);
+
+        let response = client.post(format!("/api/members/{TEST_MEMBER_NAME}/transfer"))
+            .header(ContentType::JSON)
+            .header(bearer(&token))
+            .body(r#"{ "corporation": "KRKN" }"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let response = client.get("/api/members").header(bearer(&token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let member_names: Vec<String> = serde_json::from_str(&body_str).expect("deserialize member names");
+        assert!(member_names.is_empty());
+
+        let response = client.get("/api/capsuleers").header(bearer(&token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let capsuleer_names: Vec<String> = serde_json::from_str(&body_str).expect("deserialize capsuleer names");
+        assert!(capsuleer_names.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_audit() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let other_token = create_tenant(&client, TEST_OTHER_ALLIANCE_NAME, TEST_OTHER_CORPORATION_NAME).await;
+        let db = client.rocket().state::<DatabaseConnection>().expect("managed database");
+        let corporation = Corporation::find_by_name(TEST_CORPORATION_NAME, db)
+            .await
+            .expect("Failed to find corporation")
+            .expect("corporation");
+        let outpost = NewOutpost { name: "Anchor", system: "Jita", planets: 6, arrays: 8 };
+        service::register_outpost_for_user(db, Actor::Api("test"), corporation.id, TEST_MEMBER_NAME, TEST_CAPSULEER_NAME, outpost)
+            .await
+            .expect("Failed to register outpost");
+
+        let response = client.get("/api/audit").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        let body_str = response.into_string().await.expect("response into string");
+        let events: Vec<AuditEventResponse> = serde_json::from_str(&body_str).expect("deserialize audit events");
+        let entities: Vec<&str> = events.iter().map(|event| event.entity.as_str()).collect();
+        assert_eq!(entities, vec!["outpost", "capsuleer", "member"]);
+        assert!(events.iter().all(|event| event.actor == "api:test" && event.action == "create"));
+        assert!(events[0].before.is_none());
+        assert_eq!(events[0].after.as_ref().expect("after")["name"], "Anchor");
+
+        let response = client.get("/api/audit?entity=member&limit=5").header(bearer(&token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let events: Vec<AuditEventResponse> = serde_json::from_str(&body_str).expect("deserialize audit events");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].entity, "member");
+
+        let response = client.get("/api/audit").header(bearer(&other_token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let events: Vec<AuditEventResponse> = serde_json::from_str(&body_str).expect("deserialize audit events");
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_export_import() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let other_token = create_tenant(&client, TEST_OTHER_ALLIANCE_NAME, TEST_OTHER_CORPORATION_NAME).await;
+        let status = create_request(&client, &token, "/api/members", TEST_MEMBER_REQUEST).await;
+        assert_eq!(status, Status::Created);
+        let status = create_request(&client, &token, "/api/capsuleers", TEST_CAPSULEER_REQUEST).await;
+        assert_eq!(status, Status::Created);
+        let status = create_request(&client, &token, "/api/skills", TEST_SKILL_REQUEST).await;
+        assert_eq!(status, Status::Created);
+
+        let response = client.get("/api/export").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        let export = response.into_string().await.expect("response into string");
+
+        let response = client.post("/api/import")
+            .header(ContentType::JSON)
+            .header(bearer(&other_token))
+            .body(&export)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let body_str = response.into_string().await.expect("response into string");
+        let summary: ImportResponse = serde_json::from_str(&body_str).expect("deserialize import summary");
+        assert_eq!((summary.created, summary.merged, summary.renamed), (3, 0, 0));
+
+        let response = client.get("/api/capsuleers").header(bearer(&other_token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let capsuleer_names: Vec<String> = serde_json::from_str(&body_str).expect("deserialize capsuleer names");
+        assert_eq!(capsuleer_names, vec![TEST_CAPSULEER_NAME.to_string()]);
+
+        let response = client.post("/api/import?on_collision=rename")
+            .header(ContentType::JSON)
+            .header(bearer(&other_token))
+            .body(&export)
+            .dispatch()
+            .await;
+        let body_str = response.into_string().await.expect("response into string");
+        let summary: ImportResponse = serde_json::from_str(&body_str).expect("deserialize import summary");
+        assert_eq!((summary.created, summary.merged, summary.renamed), (1, 0, 2));
+
+        let response = client.post("/api/import?on_collision=overwrite")
+            .header(ContentType::JSON)
+            .header(bearer(&other_token))
+            .body(&export)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let future = export.replacen(&format!("\"version\":{EXPORT_VERSION}"), "\"version\":99", 1);
+        let response = client.post("/api/import")
+            .header(ContentType::JSON)
+            .header(bearer(&other_token))
+            .body(future)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::UnprocessableEntity);
+    }
+
+    #[tokio::test]
+    async fn test_planets() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+
+        let response = client.get("/api/planets?resource=Heavy%20Water&system=Tanoo").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+        let body_str = response.into_string().await.expect("response into string");
+        let planets: Vec<PlanetResponse> = serde_json::from_str(&body_str).expect("deserialize planets");
+        assert!(planets.iter().all(|planet| planet.system == "Tanoo" && planet.resource == "Heavy Water"));
+        assert!(planets.windows(2).all(|pair| pair[0].init_output >= pair[1].init_output));
+        let planet = planets.iter().find(|planet| planet.planet_id == 40000002).expect("planet 40000002");
+        assert_eq!(planet.celestial_index, 1);
+
+        let response = client.get("/api/planets?region=Derelik&min_richness=3&limit=5").header(bearer(&token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let planets: Vec<PlanetResponse> = serde_json::from_str(&body_str).expect("deserialize planets");
+        assert_eq!(planets.len(), 5);
+        assert!(planets.iter().all(|planet| planet.region == "Derelik" && planet.richness_index >= 3));
+
+        let response = client.get("/api/planets?system=Nowhere").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_price_book() {
+        let client = create_client("test").await;
+        let token = create_tenant(&client, TEST_ALLIANCE_NAME, TEST_CORPORATION_NAME).await;
+        let other_token = create_tenant(&client, TEST_OTHER_ALLIANCE_NAME, TEST_OTHER_CORPORATION_NAME).await;
+
+        let response = client.get("/api/price_book").header(bearer(&token)).dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+
+        let response = client.put("/api/price_book")
+            .header(ContentType::JSON)
+            .header(bearer(&token))
+            .body(r#"{"prices": [{"resource_type_id": 42001000031, "price": 350.0}, {"resource_type_id": 42002000014, "price": 12.0}]}"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::Ok);
+        let response = client.put("/api/price_book")
+            .header(ContentType::JSON)
+            .header(bearer(&token))
+            .body(r#"{"prices": [{"resource_type_id": 42002000014, "price": null}]}"#)
+            .dispatch()
+            .await;
+        let body_str = response.into_string().await.expect("response into string");
+        let book: PriceBookResponse = serde_json::from_str(&body_str).expect("deserialize price book");
+        assert_eq!(book.version, 2);
+        assert!(book.actor.starts_with("api:"));
+        assert_eq!(book.prices.len(), 1);
+        assert_eq!(book.prices[0].price, Some(350.0));
+
+        let response = client.get("/api/price_book?version=1").header(bearer(&token)).dispatch().await;
+        let body_str = response.into_string().await.expect("response into string");
+        let book: PriceBookResponse = serde_json::from_str(&body_str).expect("deserialize price book");
+        assert_eq!(book.prices.len(), 2);
+
+        let response = client.put("/api/price_book")
+            .header(ContentType::JSON)
+            .header(bearer(&token))
+            .body(r#"{"prices": [{"resource_type_id": 42001000031, "price": -1.0}]}"#)
+            .dispatch()
+            .await;
+        assert_eq!(response.status(), Status::BadRequest);
+
+        let response = client.get("/api/price_book").header(bearer(&other_token)).dispatch().await;
+        assert_eq!(response.status(), Status::NotFound);
+    }
+}
@@ -0,0 +1,102 @@
+// tests/integration/spec.rs
+
+#[cfg(test)]
+mod tests {
+    use rocket::http::{Method, Status};
+    use utoipa::OpenApi;
+    use utoipa::openapi::path::{HttpMethod, PathItem};
+
+    use crate::create_client;
+
+    use api::routes;
+    use api::routes::openapi::ApiDoc;
+
+    fn openapi_path(route: &rocket::Route) -> String {
+        route.uri.path()
+            .to_string()
+            .replace('<', "{")
+            .replace('>', "}")
+    }
+
+    fn http_method(method: Method) -> HttpMethod {
+        match method {
+            Method::Get => HttpMethod::Get,
+            Method::Put => HttpMethod::Put,
+            Method::Post => HttpMethod::Post,
+            Method::Delete => HttpMethod::Delete,
+            Method::Options => HttpMethod::Options,
+            Method::Head => HttpMethod::Head,
+            Method::Patch => HttpMethod::Patch,
+            Method::Trace => HttpMethod::Trace,
+            method => panic!("{method} is not an OpenAPI operation"),
+        }
+    }
+
+    fn operation(path_item: &PathItem, method: HttpMethod) -> bool {
+        match method {
+            HttpMethod::Get => path_item.get.is_some(),
+            HttpMethod::Put => path_item.put.is_some(),
+            HttpMethod::Post => path_item.post.is_some(),
+            HttpMethod::Delete => path_item.delete.is_some(),
+            HttpMethod::Options => path_item.options.is_some(),
+            HttpMethod::Head => path_item.head.is_some(),
+            HttpMethod::Patch => path_item.patch.is_some(),
+            HttpMethod::Trace => path_item.trace.is_some(),
+        }
+    }
+
+    #[test]
+    fn test_openapi_documents_every_route() {
+        let openapi = ApiDoc::openapi();
+
+        for route in routes::routes() {
+            let path = openapi_path(&route);
+            let path_item = openapi.paths.paths
+                .get(&path)
+                .unwrap_or_else(|| panic!("{} {} is not documented", route.method, path));
+            assert!(
+                operation(path_item, http_method(route.method)),
+                "{} {} is not documented", route.method, path
+            );
+        }
+    }
+
+    #[test]
+    fn test_openapi_has_no_stale_paths() {
+        let openapi = ApiDoc::openapi();
+        let mounted: Vec<String> = routes::routes().iter().map(openapi_path).collect();
+
+        for path in openapi.paths.paths.keys() {
+            assert!(mounted.contains(path), "{path} is documented but not mounted");
+        }
+    }
+
+    #[test]
+    fn test_openapi_request_schemas() {
+        let openapi = ApiDoc::openapi();
+        let schemas = openapi.components.expect("components").schemas;
+
+        assert!(schemas.contains_key("MemberRequest"));
+        assert!(schemas.contains_key("CapsuleerRequest"));
+        assert!(schemas.contains_key("SkillRequest"));
+        assert!(schemas.contains_key("SkillResponse"));
+        assert!(schemas.contains_key("AuditEventResponse"));
+        assert!(schemas.contains_key("ImportResponse"));
+        assert!(schemas.contains_key("PlanetResponse"));
+        assert!(schemas.contains_key("PriceBookRequest"));
+        assert!(schemas.contains_key("PriceBookResponse"));
+    }
+
+    #[tokio::test]
+    async fn test_serve_openapi() {
+        let client = create_client("test").await;
+        let response = client.get("/api/openapi.json").dispatch().await;
+        assert_eq!(response.status(), Status::Ok);
+
+        let body_str = response.into_string().await.expect("response into string");
+        let openapi: serde_json::Value = serde_json::from_str(&body_str).expect("deserialize openapi");
+
+        assert!(openapi["openapi"].as_str().expect("openapi version").starts_with("3."));
+        assert!(openapi["paths"]["/members"]["put"].is_object());
+    }
+}
@@ -0,0 +1,113 @@
+// src/autocomplete.rs
+
+use serenity::all::CommandInteraction;
+use serenity::builder::{AutocompleteChoice, CreateAutocompleteResponse, CreateInteractionResponse};
+use serenity::prelude::*;
+use sea_orm::{ColumnTrait, EntityTrait, JoinType, QueryFilter, QuerySelect, RelationTrait};
+
+use material_lp::data::{search_constellations, search_regions, search_resources, search_systems};
+use material_lp::search::rank;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::*;
+use manager::entities::{capsuleer, outpost, problem};
+
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+/// Discord shows at most 25 suggestions.
+const CHOICES: usize = 25;
+/// Longer names can not be offered as a choice.
+const CHOICE_LENGTH: usize = 100;
+
+/// Suggest values for the focused option, named entities come from the guild's corporation.
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    db: &DatabaseConnection
+    ) -> Result<(), CommandError> {
+    let Some(focused) = interaction.data.autocomplete() else {
+        return Ok(());
+    };
+
+    let choices = match focused.name {
+        "system" | "deliver_to" => localized(search_systems(focused.value, CHOICES)
+            .into_iter()
+            .map(|(name, system)| (name, system.en_name.as_ref()))),
+        "constellation" => localized(search_constellations(focused.value, CHOICES)
+            .into_iter()
+            .map(|(name, constellation)| (name, constellation.en_name.as_ref()))),
+        "region" => localized(search_regions(focused.value, CHOICES)
+            .into_iter()
+            .map(|(name, region)| (name, region.en_name.as_ref()))),
+        "resource" => localized(search_resources(focused.value, CHOICES)
+            .into_iter()
+            .map(|(name, item)| (name, item.en_name.as_ref()))),
+        "problem" | "outpost" | "capsuleer" => {
+            let tenant = match interaction.guild_id {
+                Some(guild_id) => Tenant::resolve(guild_id, db).await?,
+                None => None,
+            };
+            match tenant {
+                Some(tenant) => {
+                    let names = names(focused.name, &tenant, db).await?;
+                    let names = names.iter().map(String::as_str).filter(|name| name.chars().count() <= CHOICE_LENGTH);
+                    rank(focused.value, names, CHOICES)
+                        .into_iter()
+                        .map(AutocompleteChoice::from)
+                        .collect()
+                }
+                None => vec![],
+            }
+        }
+        _ => vec![],
+    };
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Autocomplete(CreateAutocompleteResponse::new().set_choices(choices)),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Show the name that matched next to the English name the database stores.
+fn localized<'a>(matches: impl Iterator<Item = (&'a str, &'a str)>) -> Vec<AutocompleteChoice> {
+    matches
+        .map(|(name, en_name)| {
+            if name == en_name {
+                AutocompleteChoice::new(en_name, en_name)
+            } else {
+                AutocompleteChoice::new(format!("{en_name} ({name})"), en_name)
+            }
+        })
+        .collect()
+}
+
+async fn names(option: &str, tenant: &Tenant, db: &DatabaseConnection) -> Result<Vec<String>, CommandError> {
+    let names = match option {
+        "problem" => Problem::find()
+            .filter(problem::Column::CorporationId.eq(tenant.corporation.id))
+            .filter(problem::Column::Active.eq(true))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|problem| problem.name)
+            .collect(),
+        "outpost" => Outpost::find()
+            .join(JoinType::InnerJoin, outpost::Relation::Capsuleer.def())
+            .filter(capsuleer::Column::CorporationId.eq(tenant.corporation.id))
+            .filter(outpost::Column::Active.eq(true))
+            .all(db)
+            .await?
+            .into_iter()
+            .map(|outpost| outpost.name)
+            .collect(),
+        _ => Capsuleer::find_active_by_corporation(tenant.corporation.id, db)
+            .await?
+            .into_iter()
+            .map(|capsuleer| capsuleer.name)
+            .collect(),
+    };
+    Ok(names)
+}
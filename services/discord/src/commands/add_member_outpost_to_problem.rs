@@ -1,76 +1,35 @@
-use sea_orm::ActiveValue;
 use serenity::model::application::ResolvedOption;
-use serenity::model::application::ResolvedValue;
 use serenity::all::CommandOptionType;
-use serenity::all::CommandInteraction;
 use serenity::builder::*;
-use serenity::prelude::*;
-use serenity::utils::CreateQuickModal;
 
-use manager::database::sea_orm::ActiveModelTrait;
+use manager::audit::Actor;
 use manager::database::DatabaseConnection;
 use manager::entities::prelude::*;
-use manager::entities::*;
+use manager::service::assign_member_outposts;
 
-pub async fn run(
-    ctx: &Context,
-    interaction: &CommandInteraction,
-    db: &DatabaseConnection
-    ) -> Result<(), serenity::Error> {
-    
-    if let Some(ResolvedOption {
-        value: ResolvedValue::User(user, _), ..
-    }) = interaction.data.options().first()
-    {
-        let modal = CreateQuickModal::new("Problem Member")
-            .timeout(std::time::Duration::from_secs(600))
-            .short_field("Problem Name");
-        let response = interaction.quick_modal(ctx, modal).await?.unwrap();
-        let inputs = response.inputs;
-        let problem_name = &inputs[0];
+use crate::commands::{string_option, user_option};
+use crate::error::CommandError;
+use crate::tenant::Tenant;
 
-        let db = db as &DatabaseConnection;
-        let problem = Problem::find_by_name(problem_name, &db)
-            .await
-            .unwrap()
-            .unwrap();
-        let capsuleers: Vec<(member::Model, Option<capsuleer::Model>)> = Member::find_capsuleer_by_name(&user.tag(), &db)
-            .await
-            .unwrap();
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (Some(user), Some(problem_name)) = (user_option(options, "member"), string_option(options, "problem")) else {
+        return Err(CommandError::InvalidInput("Please provide a valid member and problem".to_string()));
+    };
 
-        for capsuleer in capsuleers {
-            let outposts = Outpost::find_by_capsuleer(capsuleer.1.unwrap().id, &db)
-                .await
-                .unwrap();
-            for outpost in outposts {
-                let mut active_outpost: outpost::ActiveModel = outpost.into();
-                active_outpost.problem_id = ActiveValue::Set(Some(problem.id));
-                active_outpost
-                    .update(db)
-                    .await
-                    .expect("Failed to update outpost");
-            }
-        }
-        response
-            .interaction
-            .create_response(
-                ctx,
-                CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                    format!("**Added**: {0} to {problem_name}", user.tag()),
-                )),
-            )
-            .await?;
-        return Ok(());
-    } else {
-        interaction.create_response(
-            ctx,
-            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                "Please provide a valid member",
-            )),
-        )
-        .await?;
-    }
-    Ok(())
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, db)
+        .await?
+        .filter(|problem| problem.active)
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+    let member = Member::find_by_corporation_and_name(tenant.corporation.id, &user.tag(), db)
+        .await?
+        .ok_or(CommandError::NotFound(user.tag()))?;
+    assign_member_outposts(db, actor, tenant.corporation.id, member.id, problem.id).await?;
+    Ok(format!("**Added**: {0} to {problem_name}", user.tag()))
 }
 
 pub fn register() -> CreateCommand {
@@ -78,4 +37,9 @@ pub fn register() -> CreateCommand {
         CreateCommandOption::new(CommandOptionType::User, "member", "The member to add outposts to problem")
             .required(true),
     )
-}
\ No newline at end of file
+    .add_option(
+        CreateCommandOption::new(CommandOptionType::String, "problem", "The problem the outposts harvest for")
+            .required(true)
+            .set_autocomplete(true),
+    )
+}
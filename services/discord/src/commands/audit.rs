@@ -0,0 +1,62 @@
+use prettytable::{Table, Row, Cell, format::FormatBuilder, row};
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::AuditEvent;
+
+use crate::commands::string_option;
+use crate::error::CommandError;
+use crate::report::Report;
+use crate::tenant::Tenant;
+
+/// Older changes are browsed with `GET /api/audit`.
+const CHANGES: u64 = 50;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant
+    ) -> Result<Report, CommandError> {
+    let entity = string_option(options, "entity");
+    let events = AuditEvent::find_by_corporation(tenant.corporation.id, entity, CHANGES, db).await?;
+    if events.is_empty() {
+        return Ok(Report::from("Nothing has changed yet".to_string()));
+    }
+
+    let mut table = Table::new();
+    table.set_format(
+        FormatBuilder::new()
+            .column_separator('|')
+            .borders('|')
+            .padding(1, 1)
+            .build()
+    );
+    table.add_row(row!["When", "Actor", "Action", "Entity", "ID", "Changed"]);
+    for event in events {
+        table.add_row(Row::new(vec![
+            Cell::new(&event.created_at.format("%Y-%m-%d %H:%M").to_string()),
+            Cell::new(&event.actor),
+            Cell::new(&event.action),
+            Cell::new(&event.entity),
+            Cell::new(&event.entity_id.to_string()),
+            Cell::new(&event.changed_fields().join(", ")),
+        ]));
+    }
+    Ok(Report::table("", &table, "audit.csv"))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("audit").description("Print the latest changes made to the corporation with eve-anchor").add_option(
+        CreateCommandOption::new(CommandOptionType::String, "entity", "Only changes to this kind of record")
+            .add_string_choice("Member", "member")
+            .add_string_choice("Capsuleer", "capsuleer")
+            .add_string_choice("Skill", "skill")
+            .add_string_choice("Outpost", "outpost")
+            .add_string_choice("Problem", "problem")
+            .add_string_choice("Price book", "price_book")
+            .add_string_choice("Array placement", "array_placement"),
+    )
+}
@@ -1,62 +1,42 @@
 use serenity::model::application::ResolvedOption;
 use serenity::model::application::ResolvedValue;
 use serenity::all::CommandOptionType;
-use serenity::all::CommandInteraction;
-use serenity::builder::*;
-use serenity::prelude::*;
-use sea_orm::DeleteResult;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
 
 use manager::database::DatabaseConnection;
 use manager::entities::prelude::Outpost;
+use manager::audit::Actor;
+use manager::service::delete_outpost;
 
-use crate::Handler;
+use crate::error::CommandError;
+use crate::tenant::Tenant;
 
 pub async fn run(
-    ctx: &Context,
-    interaction: &CommandInteraction,
-    handle: &Handler
-    ) -> Result<(), serenity::Error> {
-    if let Some(ResolvedOption {
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let Some(ResolvedOption {
         value: ResolvedValue::String(outpost_name), ..
-    }) = interaction.data.options().first()
-    {
-        let db = &handle.db as &DatabaseConnection;
-        let result: DeleteResult = Outpost::delete_by_name(outpost_name, &db)
-            .await
-            .expect("Failed to delete outpost");
-        if result.rows_affected > 0 {
-                interaction.create_response(
-                    ctx,
-                    CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                        format!("**Deleted** to delete: {outpost_name}"),
-                    )),
-                )
-                .await?;
-        } else {
-            interaction.create_response(
-                ctx,
-                CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                    format!("**Failed** to delete: {outpost_name}"),
-                )),
-            )
-            .await?;
-        }
-    } else {
-        interaction.create_response(
-            ctx,
-            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                "Please provide a valid outpost",
-            )),
-        )
-        .await?;
-    }
-    Ok(())
+    }) = options.first() else {
+        return Err(CommandError::InvalidInput("Please provide a valid outpost".to_string()));
+    };
+
+    let outpost = Outpost::find_active_by_corporation_and_name(tenant.corporation.id, outpost_name, db)
+        .await?
+        .ok_or(CommandError::NotFound(outpost_name.to_string()))?;
+    // Kept inactive rather than removed so its history survives.
+    delete_outpost(db, actor, tenant.corporation.id, outpost.id).await?;
+    Ok(format!("**Deleted**: {outpost_name}"))
 }
     
 
 pub fn register() -> CreateCommand {
     CreateCommand::new("delete_outpost").description("Delete outpost with eve-anchor").add_option(
         CreateCommandOption::new(CommandOptionType::String, "outpost", "The outpost to delete")
-            .required(true),
+            .required(true)
+            .set_autocomplete(true),
     )
-}
\ No newline at end of file
+}
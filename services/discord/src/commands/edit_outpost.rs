@@ -0,0 +1,86 @@
+use serenity::all::CommandOptionType;
+use serenity::all::CommandInteraction;
+use serenity::all::InputTextStyle;
+use serenity::builder::*;
+use serenity::prelude::*;
+use serenity::utils::CreateQuickModal;
+
+use material_lp::data::find_system;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::*;
+use manager::entities::outpost;
+use manager::audit::Actor;
+use manager::service::{edit_outpost, NewOutpost};
+
+use crate::Handler;
+use crate::commands::string_option;
+use crate::error::{parse, respond, CommandError};
+use crate::report::Report;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
+
+    let options = interaction.data.options();
+    let Some(outpost_name) = string_option(&options, "outpost") else {
+        return Err(CommandError::InvalidInput("Please provide a valid outpost".to_string()));
+    };
+    let outpost = find_outpost(outpost_name, &handle.db, tenant).await?;
+
+    let modal = CreateQuickModal::new("Outpost")
+        .timeout(std::time::Duration::from_secs(600))
+        .field(CreateInputText::new(InputTextStyle::Short, "Name", "").value(&outpost.name))
+        .field(CreateInputText::new(InputTextStyle::Short, "System", "").value(&outpost.system))
+        .field(CreateInputText::new(InputTextStyle::Short, "Planets", "").value(outpost.planets.to_string()))
+        .field(CreateInputText::new(InputTextStyle::Short, "Arrays", "").value(outpost.arrays.to_string()));
+    let response = interaction.quick_modal(ctx, modal).await?.ok_or(CommandError::Timeout)?;
+
+    let actor = interaction.user.tag();
+    let result = submit(&response.inputs, outpost, &handle.db, tenant, Actor::Discord(&actor)).await;
+    respond(ctx, interaction, Some(&response.interaction), result.map(Report::from)).await
+}
+
+/// The outpost when one of the corporation's capsuleers anchored it.
+async fn find_outpost(
+    name: &str,
+    db: &DatabaseConnection,
+    tenant: &Tenant
+    ) -> Result<outpost::Model, CommandError> {
+    Outpost::find_active_by_corporation_and_name(tenant.corporation.id, name, db)
+        .await?
+        .ok_or(CommandError::NotFound(name.to_string()))
+}
+
+async fn submit(
+    inputs: &[String],
+    outpost: outpost::Model,
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (name, system, planets, arrays) = (inputs[0].trim(), inputs[1].trim(), &inputs[2], &inputs[3]);
+    let planets = parse::<i32>("Planets", planets)?;
+    let arrays = parse::<i32>("Arrays", arrays)?;
+    if find_system(system).is_none() {
+        return Err(CommandError::InvalidInput(format!("{system} is not found in Eve")));
+    }
+
+    let edit = NewOutpost { name, system, planets, arrays };
+    edit_outpost(db, actor, tenant.corporation.id, outpost.id, edit).await?;
+
+    Ok(format!(
+        "**Updated**: {name} in {system} with {arrays} arrays for each of {planets} planets",
+    ))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("edit_outpost").description("Update an outpost registered with eve-anchor").add_option(
+        CreateCommandOption::new(CommandOptionType::String, "outpost", "The outpost to update")
+            .required(true)
+            .set_autocomplete(true),
+    )
+}
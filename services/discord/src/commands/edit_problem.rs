@@ -0,0 +1,86 @@
+use serenity::all::CommandOptionType;
+use serenity::all::CommandInteraction;
+use serenity::all::InputTextStyle;
+use serenity::builder::*;
+use serenity::prelude::*;
+use serenity::utils::CreateQuickModal;
+
+use material_lp::objective::parse_decomposed_list;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::*;
+use manager::entities::problem;
+use manager::audit::Actor;
+use manager::service::edit_problem;
+
+use crate::Handler;
+use crate::commands::string_option;
+use crate::error::{respond, CommandError};
+use crate::report::Report;
+use crate::tenant::Tenant;
+
+/// Discord does not pre-fill a text input with more than this many characters.
+const INPUT_LENGTH: usize = 4000;
+
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
+
+    let options = interaction.data.options();
+    let Some(problem_name) = string_option(&options, "problem") else {
+        return Err(CommandError::InvalidInput("Please provide a valid problem".to_string()));
+    };
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, &handle.db)
+        .await?
+        .filter(|problem| problem.active)
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+
+    // A material list too long to pre-fill is kept when the field is left blank.
+    let materials = String::from_utf8_lossy(&problem.constraint).to_string();
+    let materials = if materials.chars().count() <= INPUT_LENGTH { materials } else { String::new() };
+    let modal = CreateQuickModal::new("Problem")
+        .timeout(std::time::Duration::from_secs(600))
+        .field(CreateInputText::new(InputTextStyle::Short, "Problem Name", "").value(&problem.name))
+        .field(
+            CreateInputText::new(InputTextStyle::Paragraph, "Material List Exported from Eve Echoes", "")
+                .value(materials)
+                .required(false),
+        );
+    let response = interaction.quick_modal(ctx, modal).await?.ok_or(CommandError::Timeout)?;
+
+    let actor = interaction.user.tag();
+    let result = submit(&response.inputs, problem, &handle.db, tenant, Actor::Discord(&actor)).await;
+    respond(ctx, interaction, Some(&response.interaction), result.map(Report::from)).await
+}
+
+async fn submit(
+    inputs: &[String],
+    problem: problem::Model,
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let problem_name = inputs[0].trim();
+    let constraint = match inputs.get(1).filter(|materials| !materials.trim().is_empty()) {
+        Some(materials) => {
+            parse_decomposed_list(materials)
+                .map_err(|e| CommandError::InvalidInput(format!("The material list could not be read: {e}")))?;
+            materials.to_string().into()
+        }
+        None => problem.constraint.clone(),
+    };
+
+    edit_problem(db, actor, tenant.corporation.id, problem.id, problem_name, constraint).await?;
+
+    Ok(format!("**Updated**: {problem_name}"))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("edit_problem").description("Rename a problem or replace its materials").add_option(
+        CreateCommandOption::new(CommandOptionType::String, "problem", "The problem to update")
+            .required(true)
+            .set_autocomplete(true),
+    )
+}
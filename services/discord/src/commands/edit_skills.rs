@@ -0,0 +1,68 @@
+use serenity::all::CommandOptionType;
+use serenity::all::CommandInteraction;
+use serenity::all::InputTextStyle;
+use serenity::builder::*;
+use serenity::prelude::*;
+use serenity::utils::CreateQuickModal;
+
+use manager::audit::Actor;
+use manager::entities::prelude::*;
+use manager::service::{set_skill, NewSkill};
+
+use crate::Handler;
+use crate::commands::string_option;
+use crate::error::{parse, respond, CommandError};
+use crate::report::Report;
+use crate::tenant::Tenant;
+
+const SKILL_NAME: &str = "Planetology";
+
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
+
+    let options = interaction.data.options();
+    let Some(capsuleer_name) = string_option(&options, "capsuleer") else {
+        return Err(CommandError::InvalidInput("Please provide a valid capsuleer".to_string()));
+    };
+    let capsuleer = Capsuleer::find_by_corporation_and_name(tenant.corporation.id, capsuleer_name, &handle.db)
+        .await?
+        .filter(|capsuleer| capsuleer.active)
+        .ok_or(CommandError::NotFound(capsuleer_name.to_string()))?;
+    let skill = Skill::find_by_capsuleer(capsuleer.id, &handle.db)
+        .await?
+        .into_iter()
+        .find(|skill| skill.name == SKILL_NAME);
+    let (basic, advanced, expert) = skill
+        .map(|skill| (skill.basic, skill.advanced, skill.expert))
+        .unwrap_or_default();
+
+    let modal = CreateQuickModal::new(format!("{} Skills", capsuleer.name))
+        .timeout(std::time::Duration::from_secs(600))
+        .field(CreateInputText::new(InputTextStyle::Short, "Basic Planetology", "").value(basic.to_string()))
+        .field(CreateInputText::new(InputTextStyle::Short, "Advanced Planetology", "").value(advanced.to_string()))
+        .field(CreateInputText::new(InputTextStyle::Short, "Expert Planetology", "").value(expert.to_string()));
+    let response = interaction.quick_modal(ctx, modal).await?.ok_or(CommandError::Timeout)?;
+
+    let actor = interaction.user.tag();
+    let result = async {
+        let basic = parse::<i32>("Basic Planetology", &response.inputs[0])?;
+        let advanced = parse::<i32>("Advanced Planetology", &response.inputs[1])?;
+        let expert = parse::<i32>("Expert Planetology", &response.inputs[2])?;
+        let skill = NewSkill { name: SKILL_NAME, basic, advanced, expert };
+        set_skill(&handle.db, Actor::Discord(&actor), tenant.corporation.id, capsuleer.id, skill).await?;
+        Ok(format!("**Updated**: {} with Planetology {basic}{advanced}{expert}", capsuleer.name))
+    }.await;
+    respond(ctx, interaction, Some(&response.interaction), result.map(Report::from)).await
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("edit_skills").description("Update the skills of a capsuleer").add_option(
+        CreateCommandOption::new(CommandOptionType::String, "capsuleer", "The capsuleer who trained")
+            .required(true)
+            .set_autocomplete(true),
+    )
+}
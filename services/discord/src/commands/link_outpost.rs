@@ -0,0 +1,59 @@
+use serenity::model::application::{ResolvedOption, ResolvedValue};
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::{Outpost, Problem};
+use manager::audit::Actor;
+use manager::service::{link_outpost, unlink_outpost};
+
+use crate::commands::string_option;
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (Some(outpost_name), Some(problem_name)) = (
+        string_option(options, "outpost"),
+        string_option(options, "problem"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide an outpost and a problem".to_string()));
+    };
+    let unlink = options.iter().any(|option| option.name == "unlink" && matches!(option.value, ResolvedValue::Boolean(true)));
+
+    let outpost = Outpost::find_active_by_corporation_and_name(tenant.corporation.id, outpost_name, db)
+        .await?
+        .ok_or(CommandError::NotFound(outpost_name.to_string()))?;
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, db)
+        .await?
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+    if unlink {
+        unlink_outpost(db, actor, tenant.corporation.id, outpost.id, problem.id).await?;
+        Ok(format!("**Unlinked**: {outpost_name} no longer serves {problem_name}"))
+    } else {
+        link_outpost(db, actor, tenant.corporation.id, outpost.id, problem.id).await?;
+        Ok(format!("**Linked**: {outpost_name} also serves {problem_name}"))
+    }
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("link_outpost").description("Let an outpost serve a further problem with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "outpost", "The outpost to share")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "problem", "The problem it also serves")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "unlink", "Stop serving the problem instead"),
+        )
+}
@@ -1,17 +1,25 @@
 use prettytable::{Table, Row, Cell, format::FormatBuilder, row};
 use serenity::all::CreateCommand;
-use sea_orm::EntityTrait;
+use sea_orm::{ColumnTrait, EntityTrait, JoinType, QueryFilter, QuerySelect, RelationTrait};
 
 use manager::database::DatabaseConnection;
 use manager::entities::prelude::Outpost;
+use manager::entities::{capsuleer, outpost};
+
+use crate::error::CommandError;
+use crate::report::Report;
+use crate::tenant::Tenant;
 
 pub async fn run(
-    db: &DatabaseConnection
-    ) -> String {
+    db: &DatabaseConnection,
+    tenant: &Tenant
+    ) -> Result<Report, CommandError> {
     let mut problems = Outpost::find()
+        .join(JoinType::InnerJoin, outpost::Relation::Capsuleer.def())
+        .filter(capsuleer::Column::CorporationId.eq(tenant.corporation.id))
+        .filter(outpost::Column::Active.eq(true))
         .all(db)
-        .await
-        .unwrap();
+        .await?;
     problems.sort_by(|a, b| a.system.cmp(&b.system));
     
     let mut table = Table::new();
@@ -32,7 +40,7 @@ pub async fn run(
             Cell::new(&problem.arrays.to_string()),
         ]));
     }
-    format!("```\n{}\n```", table.to_string())
+    Ok(Report::table("", &table, "outposts.csv"))
 }
 
 pub fn register() -> CreateCommand {
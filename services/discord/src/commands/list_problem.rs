@@ -1,17 +1,23 @@
 use prettytable::{Table, Row, Cell, format::FormatBuilder, row};
 use serenity::all::CreateCommand;
-use sea_orm::EntityTrait;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
 
 use manager::database::DatabaseConnection;
 use manager::entities::prelude::Problem;
+use manager::entities::problem;
+
+use crate::error::CommandError;
+use crate::report::Report;
+use crate::tenant::Tenant;
 
 pub async fn run(
-    db: &DatabaseConnection
-    ) -> String {
+    db: &DatabaseConnection,
+    tenant: &Tenant
+    ) -> Result<Report, CommandError> {
     let problems = Problem::find()
+        .filter(problem::Column::CorporationId.eq(tenant.corporation.id))
         .all(db)
-        .await
-        .unwrap();
+        .await?;
     let mut table = Table::new();
     table.set_format(
         FormatBuilder::new()
@@ -28,7 +34,7 @@ pub async fn run(
             Cell::new(&problem.active.to_string()),
         ]));
     }
-    format!("```\n{}\n```", table.to_string())
+    Ok(Report::table("", &table, "problems.csv"))
 }
 
 pub fn register() -> CreateCommand {
@@ -8,39 +8,36 @@ use material_lp::objective::parse_decomposed_list;
 use manager::database::DatabaseConnection;
 use manager::entities::prelude::Problem;
 
-
-use crate::report::material_table;
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+use crate::report::{material_table, Report};
 
 pub async fn run(
     options: &[ResolvedOption<'_>],
-    db: &DatabaseConnection
-    ) -> String {
-        let db = db as &DatabaseConnection;
-
-        if let Some(ResolvedOption {
+    db: &DatabaseConnection,
+    tenant: &Tenant
+    ) -> Result<Report, CommandError> {
+        let Some(ResolvedOption {
             value: ResolvedValue::String(name), ..
-        }) = options.first()
-        {
-            if let Some(problem) = Problem::find_by_name(
-                name,
-                &db
-            ).await.unwrap() {
-                let constraint_str = std::str::from_utf8(&problem.constraint)
-                    .expect("Failed to convert constraint to string");
-                let materials = parse_decomposed_list(constraint_str)
-                    .expect("Failed to parse constraint");
-                return material_table(materials);
-            } else {
-                "Please provide a valid problem or run the /problem command".to_string()
-            }
-        } else {
-            "Please provide a valid problem or run the /problem command".to_string()
-        }
+        }) = options.first() else {
+            return Err(CommandError::InvalidInput("Please provide a valid problem or run the /problem command".to_string()));
+        };
+
+        let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, name, db)
+            .await?
+            .filter(|problem| problem.active)
+            .ok_or(CommandError::NotFound(name.to_string()))?;
+        let constraint_str = std::str::from_utf8(&problem.constraint)
+            .map_err(|_| CommandError::InvalidInput(format!("The material list of {name} is not text")))?;
+        let materials = parse_decomposed_list(constraint_str)
+            .map_err(|e| CommandError::InvalidInput(format!("The material list of {name} could not be read: {e}")))?;
+        Ok(Report::table("", &material_table(materials), "materials.csv"))
     }
 
 pub fn register() -> CreateCommand {
     CreateCommand::new("list_problem_material").description("Print problem materials with eve-anchor").add_option(
         CreateCommandOption::new(CommandOptionType::String, "problem", "The problem name to list materials for.")
-            .required(true),
+            .required(true)
+            .set_autocomplete(true),
     )
-}
\ No newline at end of file
+}
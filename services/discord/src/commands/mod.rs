@@ -1,10 +1,53 @@
 pub mod add_member_outpost_to_problem;
+pub mod audit;
 pub mod delete_outpost;
+pub mod edit_outpost;
+pub mod edit_problem;
+pub mod edit_skills;
+pub mod link_outpost;
 pub mod new_capsuleer;
 pub mod new_member;
 pub mod new_outpost;
 pub mod new_problem;
+pub mod place_arrays;
+pub mod plan_build;
+pub mod planets;
+pub mod price_book;
+pub mod problem_prices;
+pub mod restore;
+pub mod retire_capsuleer;
+pub mod retire_problem;
+pub mod schedule_problem;
+pub mod scout;
+pub mod set_price;
+pub mod setup;
+pub mod solve_portfolio;
 pub mod solve_problem;
 pub mod list_outpost;
 pub mod list_problem;
-pub mod list_problem_material;
\ No newline at end of file
+pub mod list_problem_material;
+
+use serenity::model::application::{ResolvedOption, ResolvedValue};
+use serenity::model::user::User;
+
+pub fn string_option<'a>(options: &[ResolvedOption<'a>], name: &str) -> Option<&'a str> {
+    options.iter().find_map(|option| match option.value {
+        ResolvedValue::String(value) if option.name == name => Some(value),
+        _ => None,
+    })
+}
+
+pub fn number_option(options: &[ResolvedOption<'_>], name: &str) -> Option<f64> {
+    options.iter().find_map(|option| match option.value {
+        ResolvedValue::Number(value) if option.name == name => Some(value),
+        ResolvedValue::Integer(value) if option.name == name => Some(value as f64),
+        _ => None,
+    })
+}
+
+pub fn user_option<'a>(options: &[ResolvedOption<'a>], name: &str) -> Option<&'a User> {
+    options.iter().find_map(|option| match option.value {
+        ResolvedValue::User(user, _) if option.name == name => Some(user),
+        _ => None,
+    })
+}
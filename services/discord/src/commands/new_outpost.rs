@@ -1,5 +1,4 @@
-use serenity::model::application::ResolvedOption;
-use serenity::model::application::ResolvedValue;
+use serenity::model::user::User;
 use serenity::all::CommandOptionType;
 use serenity::all::CommandInteraction;
 use serenity::builder::*;
@@ -8,123 +7,66 @@ use serenity::utils::CreateQuickModal;
 
 use material_lp::data::find_system;
 use manager::database::DatabaseConnection;
-use manager::entities::prelude::*;
-use manager::*;
+use manager::audit::Actor;
+use manager::service::{register_outpost_for_user, NewOutpost};
 
 use crate::Handler;
+use crate::commands::{string_option, user_option};
+use crate::error::{parse, respond, CommandError};
+use crate::report::Report;
+use crate::tenant::Tenant;
 
 pub async fn run(
     ctx: &Context,
     interaction: &CommandInteraction,
-    handle: &Handler
-    ) -> Result<(), serenity::Error> {
-        
-    if let Some(ResolvedOption {
-        value: ResolvedValue::User(user, _), ..
-    }) = interaction.data.options().first()
-    {
-        let modal = CreateQuickModal::new("Outpost")
-            .timeout(std::time::Duration::from_secs(600))
-            .short_field("Capsuleer")
-            .short_field("Name")
-            .short_field("System")
-            .short_field("Planets")
-            .short_field("Arrays");
-        let response = interaction.quick_modal(ctx, modal).await?.unwrap();
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
 
-        let inputs = response.inputs;
-        let (capsuleer, name, system, planets, arrays)
-            = (&inputs[0], &inputs[1], &inputs[2], &inputs[3], &inputs[4]);
+    let options = interaction.data.options();
+    let (Some(user), Some(capsuleer), Some(system)) = (
+        user_option(&options, "member"),
+        string_option(&options, "capsuleer"),
+        string_option(&options, "system"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide a valid member, capsuleer and system".to_string()));
+    };
+    if find_system(system).is_none() {
+        return Err(CommandError::InvalidInput(format!("{system} is not found in Eve")));
+    }
+
+    let modal = CreateQuickModal::new("Outpost")
+        .timeout(std::time::Duration::from_secs(600))
+        .short_field("Name")
+        .short_field("Planets")
+        .short_field("Arrays");
+    let response = interaction.quick_modal(ctx, modal).await?.ok_or(CommandError::Timeout)?;
 
-        let db = &handle.db as &DatabaseConnection;
-        if find_system(system).is_none() {
-            response
-                .interaction.create_response(
-                    ctx,
-                    CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                        format!("{system} is not found in Eve"),
-                    )),
-                )
-                .await?;
-                return Ok(());
-        };
-        if let Some(_member) = Member::find_by_name(&user.tag(), &db)
-            .await
-            .unwrap() {
-            if let Some(capsuleer) = Capsuleer::find_by_name(capsuleer, &db)
-                .await
-                .unwrap() {
-                    let _ = new_outpost(
-                        &db,
-                        name,
-                        system,
-                        planets.parse::<i32>().unwrap_or(0),
-                        arrays.parse::<i32>().unwrap_or(0),
-                        capsuleer.id,
-                        None
-                    )
-                        .await
-                        .expect("Failed to add outpost to database");
-            } else {
-                let member = new_member(&db, &user.tag(), handle.corporation.id).await.expect("Failed to add member to database");
-                let capsuleer = new_capsuleer(&db, capsuleer, member.last_insert_id, handle.corporation.id)
-                    .await
-                    .expect("Failed to add capsuleer to database");
-                let _ = new_outpost(
-                    &db,
-                    name,
-                    system,
-                    planets.parse::<i32>().unwrap_or(0),
-                    arrays.parse::<i32>().unwrap_or(0),
-                    capsuleer.last_insert_id,
-                    None
-                )
-                    .await
-                    .expect("Failed to add outpost to database");
-            }
-        } else {
-            let member = new_member(&db, &user.tag(), handle.corporation.id).await.expect("Failed to add member to database");
-            let capsuleer = new_capsuleer(&db, capsuleer, member.last_insert_id, handle.corporation.id)
-                .await
-                .expect("Failed to add capsuleer to database");
-                let _ = new_outpost(
-                    &db,
-                    name,
-                    system,
-                    planets.parse::<i32>().unwrap_or(0),
-                    arrays.parse::<i32>().unwrap_or(0),
-                    capsuleer.last_insert_id,
-                    None
-                )
-                    .await
-                    .expect("Failed to add outpost to database");
-        }
-        response
-            .interaction
-            .create_response(
-                ctx,
-                CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                    format!(
-                        "**Register**: {name} to {0} in {system} with {1} arrays for each of {2} planets",
-                        user.tag(),
-                        arrays,
-                        planets,
-                    ),
-                )),
-            )
-            .await?;
+    let actor = interaction.user.tag();
+    let result = submit(&response.inputs, user, capsuleer, system, &handle.db, tenant, Actor::Discord(&actor)).await;
+    respond(ctx, interaction, Some(&response.interaction), result.map(Report::from)).await
+}
 
+async fn submit(
+    inputs: &[String],
+    user: &User,
+    capsuleer: &str,
+    system: &str,
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (name, planets, arrays) = (&inputs[0], &inputs[1], &inputs[2]);
+    let planets = parse::<i32>("Planets", planets)?;
+    let arrays = parse::<i32>("Arrays", arrays)?;
 
-    } else {
-        interaction.create_response(
-            ctx,
-            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                "Please provide a valid member",
-            )),
-        )
-        .await?;
-    }
-    Ok(())
+    let outpost = NewOutpost { name, system, planets, arrays };
+    register_outpost_for_user(db, actor, tenant.corporation.id, &user.tag(), capsuleer, outpost).await?;
+
+    Ok(format!(
+        "**Register**: {name} to {0} in {system} with {arrays} arrays for each of {planets} planets",
+        user.tag(),
+    ))
 }
 
 pub fn register() -> CreateCommand {
@@ -132,4 +74,14 @@ pub fn register() -> CreateCommand {
         CreateCommandOption::new(CommandOptionType::User, "member", "The member the outpost belongs to")
             .required(true),
     )
-}
\ No newline at end of file
+    .add_option(
+        CreateCommandOption::new(CommandOptionType::String, "capsuleer", "The capsuleer anchoring the outpost")
+            .required(true)
+            .set_autocomplete(true),
+    )
+    .add_option(
+        CreateCommandOption::new(CommandOptionType::String, "system", "The system the outpost is anchored in")
+            .required(true)
+            .set_autocomplete(true),
+    )
+}
@@ -1,79 +1,68 @@
 use serenity::model::application::ResolvedOption;
 use serenity::model::application::ResolvedValue;
+use serenity::model::user::User;
 use serenity::all::CommandOptionType;
 use serenity::all::CommandInteraction;
 use serenity::builder::*;
 use serenity::prelude::*;
 use serenity::utils::CreateQuickModal;
 
+use material_lp::objective::parse_decomposed_list;
 use manager::database::DatabaseConnection;
-use manager::entities::prelude::*;
-use manager::*;
+use manager::audit::Actor;
+use manager::service::register_problem_for_user;
 
 use crate::Handler;
+use crate::error::{respond, CommandError};
+use crate::report::Report;
+use crate::tenant::Tenant;
 
 pub async fn run(
     ctx: &Context,
     interaction: &CommandInteraction,
-    handle: &Handler
-    ) -> Result<(), serenity::Error> {
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
 
-    if let Some(ResolvedOption {
+    let Some(ResolvedOption {
         value: ResolvedValue::User(user, _), ..
-    }) = interaction.data.options().first()
-    {
-        let modal = CreateQuickModal::new("Problem")
-            .timeout(std::time::Duration::from_secs(600))
-            .short_field("Problem Name")
-            .paragraph_field("Material List Exported from Eve Echoes");
-        let response = interaction.quick_modal(ctx, modal).await?.unwrap();
+    }) = interaction.data.options().first().cloned() else {
+        return Err(CommandError::InvalidInput("Please provide a valid member".to_string()));
+    };
 
-        let inputs = response.inputs;
-        let (problem_name, constraints) = (&inputs[0], &inputs[1]);
-        let db = &handle.db as &DatabaseConnection;
-        if let Some(member) = Member::find_by_name(&user.tag(), &db)
-            .await
-            .unwrap() {
-                new_problem(
-                    &db,
-                    problem_name,
-                    constraints.to_string().into(),
-                    member.id,
-                    handle.corporation.id,
-                    Some(handle.alliance.id)
-                ).await.expect("Failed to add problem to database");
-        } else {
-            let member = new_member(&db, &user.tag(), handle.corporation.id).await.expect("Failed to add member to database");
-            new_problem(
-                &db,
-                problem_name,
-                constraints.to_string().into(),
-                member.last_insert_id,
-                handle.corporation.id,
-                Some(handle.alliance.id)
-            ).await.expect("Failed to add problem to database");
+    let modal = CreateQuickModal::new("Problem")
+        .timeout(std::time::Duration::from_secs(600))
+        .short_field("Problem Name")
+        .paragraph_field("Material List Exported from Eve Echoes");
+    let response = interaction.quick_modal(ctx, modal).await?.ok_or(CommandError::Timeout)?;
 
-        }
-        response
-            .interaction
-            .create_response(
-                ctx,
-                CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                    format!("**Problem**: {problem_name} created for {0} in {1}", &user.tag(), handle.corporation.name),
-                )),
-            )
-            .await?;
+    let actor = interaction.user.tag();
+    let result = submit(&response.inputs, user, &handle.db, tenant, Actor::Discord(&actor)).await;
+    respond(ctx, interaction, Some(&response.interaction), result.map(Report::from)).await
+}
+
+async fn submit(
+    inputs: &[String],
+    user: &User,
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (problem_name, constraints) = (&inputs[0], &inputs[1]);
+    parse_decomposed_list(constraints)
+        .map_err(|e| CommandError::InvalidInput(format!("The material list could not be read: {e}")))?;
 
-    } else {
-        interaction.create_response(
-            ctx,
-            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                "Please provide a valid member",
-            )),
-        )
-        .await?;
-    }
-    Ok(())
+    register_problem_for_user(
+        db,
+        actor,
+        tenant.corporation.id,
+        Some(tenant.alliance.id),
+        &user.tag(),
+        problem_name,
+        constraints.to_string().into()
+    ).await?;
+
+    Ok(format!("**Problem**: {problem_name} created for {0} in {1}", &user.tag(), tenant.corporation.name))
 }
 
 pub fn register() -> CreateCommand {
@@ -81,4 +70,4 @@ pub fn register() -> CreateCommand {
         CreateCommandOption::new(CommandOptionType::User, "member", "The member to solve a problem for")
             .required(true),
     )
-}
\ No newline at end of file
+}
@@ -0,0 +1,94 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use material_lp::data::{find_constellation_by_system, find_item, find_system, get_celestial, get_item, query_planets, system_by_planet, PlanetQuery};
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::Outpost;
+use manager::audit::Actor;
+use manager::service::place_arrays;
+
+use crate::commands::{number_option, string_option};
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (Some(outpost_name), Some(system), Some(planet), Some(resource), Some(count)) = (
+        string_option(options, "outpost"),
+        string_option(options, "system"),
+        number_option(options, "planet"),
+        string_option(options, "resource"),
+        number_option(options, "count"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide an outpost, planet, resource and number of arrays".to_string()));
+    };
+
+    let outpost = Outpost::find_active_by_corporation_and_name(tenant.corporation.id, outpost_name, db)
+        .await?
+        .ok_or(CommandError::NotFound(outpost_name.to_string()))?;
+    let system_id = *find_system(system).ok_or(CommandError::InvalidInput(format!("{system} is not found in Eve")))?;
+    if find_constellation_by_system(system) != find_constellation_by_system(&outpost.system) {
+        return Err(CommandError::InvalidInput(format!("{system} is not in the constellation {outpost_name} harvests")));
+    }
+    if count as i32 > outpost.arrays {
+        return Err(CommandError::InvalidInput(format!("{outpost_name} anchors at most {} arrays on a planet", outpost.arrays)));
+    }
+    let resource_type_id = *find_item(resource).ok_or(CommandError::InvalidInput(format!("{resource} is not found in Eve")))?;
+    let query = PlanetQuery { system_id: Some(system_id), resource_type_id: Some(resource_type_id), ..Default::default() };
+    let planet_id = query_planets(&query, usize::MAX)
+        .into_iter()
+        .find(|row| row.celestial_index == planet as i64)
+        .map(|row| row.planet_id)
+        .ok_or(CommandError::InvalidInput(format!("{system} {planet} has no {resource}")))?;
+
+    let layout = place_arrays(db, actor, tenant.corporation.id, outpost.id, planet_id, resource_type_id, count as i32).await?;
+    let lines: Vec<String> = layout
+        .iter()
+        .map(|placement| format!(
+            "{} {} {}: {}",
+            system_by_planet(placement.planet_id).map(|system| system.en_name.to_string()).unwrap_or_default(),
+            get_celestial(placement.planet_id).map(|celestial| celestial.celestial_index).unwrap_or_default(),
+            get_item(placement.resource_type_id).map(|item| item.en_name.to_string()).unwrap_or_default(),
+            placement.count,
+        ))
+        .collect();
+    Ok(match lines.is_empty() {
+        true => format!("**Recorded**: {outpost_name} has no arrays anchored"),
+        false => format!("**Recorded**: {outpost_name} has anchored\n{}", lines.join("\n")),
+    })
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("place_arrays").description("Record the arrays an outpost has anchored on a planet with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "outpost", "The outpost the arrays belong to")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "system", "The system of the planet")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "planet", "The planet's number in the system, e.g. 7 for Kimotoro VII")
+                .required(true)
+                .min_int_value(1),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "resource", "The resource harvested")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "count", "Arrays anchored, 0 to take them all down")
+                .required(true)
+                .min_int_value(0),
+        )
+}
@@ -0,0 +1,175 @@
+use std::collections::HashMap;
+
+use serenity::all::CommandInteraction;
+use serenity::all::CommandOptionType;
+use serenity::builder::*;
+use serenity::prelude::*;
+
+use material_lp::solve_schedule;
+use material_lp::objective::parse_decomposed_list;
+use material_lp::price::PriceSnapshot;
+use material_lp::schedule::{Period, Reanchoring, SchedulePlan};
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::*;
+use manager::entities::*;
+
+use crate::Handler;
+use crate::commands::{number_option, string_option};
+use crate::error::{log, CommandError};
+use crate::report::{schedule_table, Report};
+use crate::tenant::Tenant;
+
+/// Days in each period of a schedule.
+const WEEK: f64 = 7.;
+/// Every week adds a placement of each array to the schedule, this keeps a build solvable in a reply.
+const MAX_WEEKS: u64 = 12;
+
+struct Build {
+    outposts: Vec<outpost::Model>,
+    periods: Vec<Period>,
+}
+
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
+    let options = interaction.data.options();
+    let (Some(phases), Some(weeks)) = (
+        string_option(&options, "phases"),
+        number_option(&options, "weeks"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide the phases and the weeks each takes".to_string()));
+    };
+    let phases: Vec<&str> = phases.split(',').map(str::trim).filter(|phase| !phase.is_empty()).collect();
+    let reanchoring = Reanchoring {
+        isk_per_array: number_option(&options, "isk_per_move").unwrap_or(0.),
+        max_arrays: number_option(&options, "max_moves"),
+    };
+    let build = build(&phases, weeks as usize, &handle.db, tenant).await?;
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
+                format!("Planning {} over {} weeks...", phases.join(", "), build.periods.len()),
+            )),
+        )
+        .await?;
+    // The schedule is solved off the async runtime, it keeps the solver busy long enough to stall other commands.
+    let solved = tokio::task::spawn_blocking(move || solve_schedule(build.outposts, build.periods, reanchoring))
+        .await
+        .map_err(|error| CommandError::Solver(error.to_string()))
+        .and_then(|result| result.map_err(CommandError::Solver));
+    let report = match solved {
+        Ok(plan) => describe(&phases, &plan),
+        Err(error) => {
+            log(interaction, &error);
+            Report::from(format!("**Failed**: {error}"))
+        }
+    };
+    interaction.edit_response(ctx, report.edit()).await?;
+    for followup in report.followups() {
+        interaction.create_followup(ctx, followup).await?;
+    }
+    Ok(())
+}
+
+/// A week-long period for every week of every phase, each phase's requirements due by its last week.
+async fn build(phases: &[&str], weeks: usize, db: &DatabaseConnection, tenant: &Tenant) -> Result<Build, CommandError> {
+    if phases.is_empty() {
+        return Err(CommandError::InvalidInput("Please name the problems of each phase, e.g. Hull, Rigs".to_string()));
+    }
+    if (phases.len() * weeks) as u64 > MAX_WEEKS {
+        return Err(CommandError::InvalidInput(format!(
+            "{} phases of {weeks} weeks take {} weeks, plan at most {MAX_WEEKS} at once",
+            phases.len(),
+            phases.len() * weeks,
+        )));
+    }
+    let mut problem_ids = Vec::with_capacity(phases.len());
+    let mut periods = Vec::with_capacity(phases.len() * weeks);
+    let mut prices = None;
+    for phase in phases {
+        let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, phase, db)
+            .await?
+            .filter(|problem| problem.active)
+            .ok_or(CommandError::NotFound(phase.to_string()))?;
+        let constraint = std::str::from_utf8(&problem.constraint)
+            .map_err(|_| CommandError::InvalidInput(format!("The material list of {phase} is not text")))?;
+        let mut materials = parse_decomposed_list(constraint)
+            .map_err(|e| CommandError::InvalidInput(format!("The material list of {phase} could not be read: {e}")))?;
+        if problem.corp_prices {
+            if prices.is_none() {
+                let (book, entries) = PriceBook::find_with_entries(tenant.corporation.id, None, db)
+                    .await?
+                    .ok_or(CommandError::InvalidInput(format!("{phase} is valued at corp prices but none are set, add them with /set_price")))?;
+                prices = Some(PriceSnapshot::from_price_book(book, entries));
+            }
+            if let Some(prices) = &prices {
+                prices.revalue(&mut materials);
+            }
+        }
+
+        let due = Period::new(WEEK, materials);
+        for _ in 1..weeks {
+            // Earlier weeks of the phase harvest at its prices towards the requirements due at its end.
+            periods.push(Period { days: WEEK, minimum_output: HashMap::new(), value: due.value.clone() });
+        }
+        periods.push(due);
+        problem_ids.push(problem.id);
+    }
+
+    let outposts: Vec<outpost::Model> = Outpost::find_active_with_problems(tenant.corporation.id, db)
+        .await?
+        .into_iter()
+        .filter(|(_, serves)| serves.iter().any(|id| problem_ids.contains(id)))
+        .map(|(outpost, _)| outpost)
+        .collect();
+    if outposts.is_empty() {
+        return Err(CommandError::InvalidInput(format!("No active outpost serves {}, link one with /link_outpost", phases.join(", "))));
+    }
+    Ok(Build { outposts, periods })
+}
+
+fn describe(phases: &[&str], plan: &SchedulePlan) -> Report {
+    let moves: Vec<String> = plan.periods
+        .iter()
+        .enumerate()
+        .filter(|(_, period)| period.moved > 1e-6)
+        .map(|(index, period)| format!("week {}: {}", index + 1, period.moved.round()))
+        .collect();
+    let mut content = format!(
+        "To build {} over {} weeks for {} ISK net of re-anchoring, place the arrays as follows:",
+        phases.join(" then "),
+        plan.periods.len(),
+        plan.value.round(),
+    );
+    if !moves.is_empty() {
+        content.push_str(&format!("\n**Arrays moved**: {}", moves.join(", ")));
+    }
+    Report::table(&content, &schedule_table(plan), "schedule.csv")
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("plan_build").description("Plan the arrays week by week through the phases of a build with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "phases", "The problems of each phase in order, e.g. Hull, Rigs")
+                .required(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "weeks", format!("The weeks each phase takes, at most {MAX_WEEKS} for the whole build"))
+                .required(true)
+                .min_int_value(1)
+                .max_int_value(MAX_WEEKS),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "isk_per_move", "ISK lost for every array moved to another planet")
+                .min_number_value(0.),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "max_moves", "At most this many arrays move between two weeks")
+                .min_int_value(0),
+        )
+}
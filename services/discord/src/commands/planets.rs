@@ -0,0 +1,69 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use material_lp::data::{find_constellation, find_item, find_region, find_system, query_planets, PlanetQuery};
+
+use crate::commands::{number_option, string_option};
+use crate::error::CommandError;
+use crate::report::{planet_table, Report};
+
+/// Rows listed when the command does not say.
+const DEFAULT_LIMIT: usize = 25;
+
+pub fn run(options: &[ResolvedOption<'_>]) -> Result<Report, CommandError> {
+    let resolve = |option: &str, find: fn(&str) -> Option<&'static i64>| {
+        string_option(options, option)
+            .map(|name| find(name).copied().ok_or(CommandError::NotFound(name.to_string())))
+            .transpose()
+    };
+    let query = PlanetQuery {
+        resource_type_id: resolve("resource", find_item)?,
+        region_id: resolve("region", find_region)?,
+        constellation_id: resolve("constellation", find_constellation)?,
+        system_id: resolve("system", find_system)?,
+        min_output: number_option(options, "min_output"),
+        min_richness: number_option(options, "min_richness").map(|richness| richness as i64),
+    };
+    let limit = number_option(options, "limit").map_or(DEFAULT_LIMIT, |limit| limit as usize);
+
+    let rows = query_planets(&query, limit);
+    if rows.is_empty() {
+        return Ok(Report::from("No planet matches.".to_string()));
+    }
+    Ok(Report::table("", &planet_table(&rows), "planets.csv"))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("planets").description("Browse the resources of planets, highest output first, with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "resource", "Only this resource")
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "region", "Only planets in this region")
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "constellation", "Only planets in this constellation")
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "system", "Only planets in this system")
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "min_output", "Only resources yielding at least this many units per hour")
+                .min_number_value(0.),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "min_richness", "Only resources at least this rich")
+                .min_int_value(0),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "limit", "The number of rows listed, 25 by default")
+                .min_int_value(1)
+                .max_int_value(1000),
+        )
+}
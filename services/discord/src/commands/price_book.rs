@@ -0,0 +1,61 @@
+use prettytable::{Table, Row, Cell, format::FormatBuilder, row};
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use material_lp::data::get_item;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::PriceBook;
+
+use crate::commands::number_option;
+use crate::error::CommandError;
+use crate::report::Report;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant
+    ) -> Result<Report, CommandError> {
+    let version = number_option(options, "version").map(|version| version as i32);
+    let Some((book, entries)) = PriceBook::find_with_entries(tenant.corporation.id, version, db).await? else {
+        return Ok(Report::from(match version {
+            Some(version) => format!("The price book has no version {version}"),
+            None => "No prices are set yet, add them with /set_price".to_string(),
+        }));
+    };
+
+    let mut table = Table::new();
+    table.set_format(
+        FormatBuilder::new()
+            .column_separator('|')
+            .borders('|')
+            .padding(1, 1)
+            .build()
+    );
+    table.add_row(row!["Resource", "ISK per unit"]);
+    for entry in entries {
+        let resource = get_item(entry.resource_type_id)
+            .map(|item| item.en_name.to_string())
+            .unwrap_or_else(|| entry.resource_type_id.to_string());
+        table.add_row(Row::new(vec![
+            Cell::new(&resource),
+            Cell::new(&entry.price.to_string()),
+        ]));
+    }
+    let preamble = format!(
+        "Price book v{}, published by {} on {}",
+        book.version,
+        book.actor,
+        book.created_at.format("%Y-%m-%d %H:%M"),
+    );
+    Ok(Report::table(&preamble, &table, "price_book.csv"))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("price_book").description("Print the corporation's buyback prices with eve-anchor").add_option(
+        CreateCommandOption::new(CommandOptionType::Integer, "version", "An earlier version, the latest by default")
+            .min_int_value(1),
+    )
+}
@@ -0,0 +1,53 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::Problem;
+use manager::audit::Actor;
+use manager::service::set_problem_pricing;
+
+use crate::commands::string_option;
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (Some(problem_name), Some(prices)) = (
+        string_option(options, "problem"),
+        string_option(options, "prices"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide a problem and its prices".to_string()));
+    };
+
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, db)
+        .await?
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+    let corp_prices = prices == "corp";
+    set_problem_pricing(db, actor, tenant.corporation.id, problem.id, corp_prices).await?;
+    Ok(if corp_prices {
+        format!("**Updated**: {problem_name} is valued at the corporation's price book")
+    } else {
+        format!("**Updated**: {problem_name} is valued at its material list")
+    })
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("problem_prices").description("Choose the prices a problem's materials are valued at with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "problem", "The problem to price")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "prices", "Where the valuations come from")
+                .required(true)
+                .add_string_choice("Material list", "list")
+                .add_string_choice("Corp prices", "corp"),
+        )
+}
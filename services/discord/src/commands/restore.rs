@@ -0,0 +1,66 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use manager::audit::Actor;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::{Capsuleer, Outpost, Problem};
+use manager::service::{restore_capsuleer, restore_outpost, restore_problem};
+
+use crate::commands::string_option;
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (Some(kind), Some(name)) = (string_option(options, "kind"), string_option(options, "name")) else {
+        return Err(CommandError::InvalidInput("Please provide what to restore and its name".to_string()));
+    };
+    let corporation_id = tenant.corporation.id;
+    let not_found = || CommandError::NotFound(format!("A retired {kind} named {name}"));
+
+    match kind {
+        "capsuleer" => {
+            let capsuleer = Capsuleer::find_retired_by_corporation_and_name(corporation_id, name, db)
+                .await?
+                .ok_or_else(not_found)?;
+            restore_capsuleer(db, actor, corporation_id, capsuleer.id).await?;
+            Ok(format!("**Restored**: {name} with their outposts, link them to problems again"))
+        }
+        "outpost" => {
+            let outpost = Outpost::find_retired_by_corporation_and_name(corporation_id, name, db)
+                .await?
+                .ok_or_else(not_found)?;
+            restore_outpost(db, actor, corporation_id, outpost.id).await?;
+            Ok(format!("**Restored**: {name}, link it to a problem again"))
+        }
+        "problem" => {
+            let problem = Problem::find_retired_by_corporation_and_name(corporation_id, name, db)
+                .await?
+                .ok_or_else(not_found)?;
+            restore_problem(db, actor, corporation_id, problem.id).await?;
+            Ok(format!("**Restored**: {name}, add outposts to it again"))
+        }
+        _ => Err(CommandError::InvalidInput(format!("A {kind} cannot be restored"))),
+    }
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("restore").description("Bring back a retired capsuleer, deleted outpost or retired problem with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "kind", "What to restore")
+                .required(true)
+                .add_string_choice("Capsuleer", "capsuleer")
+                .add_string_choice("Outpost", "outpost")
+                .add_string_choice("Problem", "problem"),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "name", "Its name when it was retired")
+                .required(true),
+        )
+}
@@ -0,0 +1,40 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use manager::audit::Actor;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::Capsuleer;
+use manager::service::retire_capsuleer;
+
+use crate::commands::string_option;
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let Some(capsuleer_name) = string_option(options, "capsuleer") else {
+        return Err(CommandError::InvalidInput("Please provide a valid capsuleer".to_string()));
+    };
+
+    let capsuleer = Capsuleer::find_by_corporation_and_name(tenant.corporation.id, capsuleer_name, db)
+        .await?
+        .filter(|capsuleer| capsuleer.active)
+        .ok_or(CommandError::NotFound(capsuleer_name.to_string()))?;
+    retire_capsuleer(db, actor, tenant.corporation.id, capsuleer.id).await?;
+
+    Ok(format!("**Retired**: {capsuleer_name}, their outposts no longer harvest for the corporation"))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("retire_capsuleer").description("Retire a capsuleer from eve-anchor").add_option(
+        CreateCommandOption::new(CommandOptionType::String, "capsuleer", "The capsuleer to retire")
+            .required(true)
+            .set_autocomplete(true),
+    )
+}
@@ -0,0 +1,40 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use manager::audit::Actor;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::Problem;
+use manager::service::retire_problem;
+
+use crate::commands::string_option;
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let Some(problem_name) = string_option(options, "problem") else {
+        return Err(CommandError::InvalidInput("Please provide a valid problem".to_string()));
+    };
+
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, db)
+        .await?
+        .filter(|problem| problem.active)
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+    retire_problem(db, actor, tenant.corporation.id, problem.id).await?;
+
+    Ok(format!("**Retired**: {problem_name}, its outposts are free to serve other problems"))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("retire_problem").description("Retire a problem from eve-anchor, /restore brings it back").add_option(
+        CreateCommandOption::new(CommandOptionType::String, "problem", "The problem to retire")
+            .required(true)
+            .set_autocomplete(true),
+    )
+}
@@ -0,0 +1,57 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use material_lp::price::end_of_day;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::Problem;
+use manager::audit::Actor;
+use manager::service::schedule_problem;
+
+use crate::commands::{number_option, string_option};
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let (Some(problem_name), Some(priority)) = (
+        string_option(options, "problem"),
+        number_option(options, "priority"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide a problem and its priority".to_string()));
+    };
+    let deadline = string_option(options, "deadline")
+        .map(|date| end_of_day(date).ok_or(CommandError::InvalidInput(format!("{date} is not a date like 2025-01-24"))))
+        .transpose()?;
+
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, db)
+        .await?
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+    schedule_problem(db, actor, tenant.corporation.id, problem.id, priority as i32, deadline).await?;
+    Ok(match deadline {
+        Some(deadline) => format!("**Scheduled**: {problem_name} at priority {priority} by {}", deadline.format("%Y-%m-%d")),
+        None => format!("**Scheduled**: {problem_name} at priority {priority}"),
+    })
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("schedule_problem").description("Set how urgent a problem is when outposts are shared with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "problem", "The problem to schedule")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "priority", "Problems are served from priority 1 up")
+                .required(true)
+                .min_int_value(1),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "deadline", "The day the materials are needed by, e.g. 2025-01-24"),
+        )
+}
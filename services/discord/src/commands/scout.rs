@@ -0,0 +1,121 @@
+use serenity::all::CommandInteraction;
+use serenity::all::CommandOptionType;
+use serenity::builder::*;
+use serenity::prelude::*;
+
+use material_lp::data::find_region;
+use material_lp::objective::parse_decomposed_list;
+use material_lp::resource::Material;
+use material_lp::scout::{scout, Prospective, DEFAULT_ARRAYS, DEFAULT_PLANETS};
+use manager::entities::prelude::Problem;
+
+use crate::Handler;
+use crate::commands::{number_option, string_option};
+use crate::error::{log, CommandError};
+use crate::report::{scout_table, Report};
+use crate::tenant::Tenant;
+
+/// Constellations listed when the command does not say.
+const DEFAULT_TOP: usize = 10;
+
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
+    let options = interaction.data.options();
+    let (Some(problem_name), Some(outposts)) = (
+        string_option(&options, "problem"),
+        number_option(&options, "outposts"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide a problem and number of outposts".to_string()));
+    };
+    let prospective = Prospective {
+        outposts: outposts as i32,
+        planets: number_option(&options, "planets").map_or(DEFAULT_PLANETS, |planets| planets as i32),
+        arrays: number_option(&options, "arrays").map_or(DEFAULT_ARRAYS, |arrays| arrays as i32),
+        days: number_option(&options, "days").unwrap_or(7.),
+    };
+    let top = number_option(&options, "top").map_or(DEFAULT_TOP, |top| top as usize);
+    let region = string_option(&options, "region");
+    let region_id = region
+        .map(|region| find_region(region).copied().ok_or(CommandError::NotFound(region.to_string())))
+        .transpose()?;
+
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, &handle.db)
+        .await?
+        .filter(|problem| problem.active)
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+    let constraint = std::str::from_utf8(&problem.constraint)
+        .map_err(|_| CommandError::InvalidInput(format!("The material list of {problem_name} is not text")))?;
+    let materials: Vec<Material> = parse_decomposed_list(constraint)
+        .map_err(|e| CommandError::InvalidInput(format!("The material list of {problem_name} could not be read: {e}")))?;
+
+    // Every constellation is rated, which outlasts the time Discord allows for a reply,
+    // so the rating runs off the async runtime.
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
+                format!("Scouting {} for {} outposts harvesting {problem_name}...", region.unwrap_or("New Eden"), prospective.outposts),
+            )),
+        )
+        .await?;
+    let scouted = tokio::task::spawn_blocking(move || scout(&materials, &prospective, region_id, top))
+        .await
+        .map_err(|error| CommandError::Solver(error.to_string()));
+    let report = match scouted {
+        Ok(prospects) => {
+            let preamble = format!(
+                "The best constellations for {problem_name} by share of the materials they can harvest in {} days, then ISK per day:",
+                prospective.days,
+            );
+            Report::table(&preamble, &scout_table(&prospects), "scout.csv")
+        }
+        Err(error) => {
+            log(interaction, &error);
+            Report::from(format!("**Failed**: {error}"))
+        }
+    };
+    interaction.edit_response(ctx, report.edit()).await?;
+    for followup in report.followups() {
+        interaction.create_followup(ctx, followup).await?;
+    }
+    Ok(())
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("scout").description("Rank the constellations to anchor new outposts in for a problem with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "problem", "The problem whose materials are harvested")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "outposts", "The number of outposts to anchor")
+                .required(true)
+                .min_int_value(1),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "days", "The number of days to harvest, 7 by default")
+                .min_number_value(1.),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "planets", "Planets each outpost harvests")
+                .min_int_value(1),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "arrays", "Arrays on each planet")
+                .min_int_value(1),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "region", "Only constellations of this region")
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "top", "The number of constellations listed")
+                .min_int_value(1)
+                .max_int_value(25),
+        )
+}
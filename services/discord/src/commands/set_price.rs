@@ -0,0 +1,45 @@
+use serenity::model::application::ResolvedOption;
+use serenity::all::CommandOptionType;
+use serenity::all::CreateCommand;
+use serenity::all::CreateCommandOption;
+
+use material_lp::data::find_item;
+use manager::database::DatabaseConnection;
+use manager::audit::Actor;
+use manager::service::set_prices;
+
+use crate::commands::{number_option, string_option};
+use crate::error::CommandError;
+use crate::tenant::Tenant;
+
+pub async fn run(
+    options: &[ResolvedOption<'_>],
+    db: &DatabaseConnection,
+    tenant: &Tenant,
+    actor: Actor<'_>
+    ) -> Result<String, CommandError> {
+    let Some(resource) = string_option(options, "resource") else {
+        return Err(CommandError::InvalidInput("Please provide a resource".to_string()));
+    };
+    let resource_type_id = *find_item(resource).ok_or(CommandError::InvalidInput(format!("{resource} is not found in Eve")))?;
+    let price = number_option(options, "price");
+
+    let (book, _) = set_prices(db, actor, tenant.corporation.id, &[(resource_type_id, price)]).await?;
+    Ok(match price {
+        Some(price) => format!("**Price book v{}**: {resource} at {price} ISK", book.version),
+        None => format!("**Price book v{}**: {resource} removed", book.version),
+    })
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("set_price").description("Set the corporation's buyback price of a resource with eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "resource", "The resource to price")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "price", "ISK per unit, leave out to remove the resource from the price book")
+                .min_number_value(0.),
+        )
+}
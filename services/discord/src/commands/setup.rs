@@ -0,0 +1,63 @@
+use serenity::model::application::ResolvedOption;
+use serenity::model::application::ResolvedValue;
+use serenity::model::Permissions;
+use serenity::all::CommandOptionType;
+use serenity::all::CommandInteraction;
+use serenity::builder::*;
+
+use manager::audit::Actor;
+use manager::database::DatabaseConnection;
+use manager::service::setup_guild;
+
+use crate::error::CommandError;
+
+pub async fn run(
+    interaction: &CommandInteraction,
+    db: &DatabaseConnection
+    ) -> Result<String, CommandError> {
+    let options = interaction.data.options();
+    let (alliance_name, corporation_name) = match (options.first(), options.get(1)) {
+        (
+            Some(ResolvedOption { value: ResolvedValue::String(alliance_name), .. }),
+            Some(ResolvedOption { value: ResolvedValue::String(corporation_name), .. }),
+        ) => (*alliance_name, *corporation_name),
+        _ => return Err(CommandError::InvalidInput("Please provide an alliance and corporation".to_string())),
+    };
+    let claim = options
+        .iter()
+        .find(|option| option.name == "claim")
+        .and_then(|option| match option.value {
+            ResolvedValue::String(claim) => Some(claim),
+            _ => None,
+        });
+    let Some(guild_id) = interaction.guild_id else {
+        return Err(CommandError::InvalidInput("Setup must be run from a server".to_string()));
+    };
+
+    let actor = interaction.user.tag();
+    setup_guild(db, Actor::Discord(&actor), guild_id.get() as i64, alliance_name, corporation_name, claim).await?;
+
+    Ok(format!("**Setup**: this server now plans for {corporation_name} in {alliance_name}"))
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("setup").description("Bind this server to a corporation with eve-anchor")
+        .default_member_permissions(Permissions::ADMINISTRATOR)
+        .dm_permission(false)
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "alliance", "The alliance the corporation belongs to")
+                .required(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "corporation", "The corporation planning in this server")
+                .required(true),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                "claim",
+                "A claim token from the API, needed for a corporation or alliance registered before",
+            )
+                .required(false),
+        )
+}
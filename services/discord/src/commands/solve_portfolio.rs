@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use serenity::all::CommandInteraction;
+use serenity::all::CommandOptionType;
+use serenity::builder::*;
+use serenity::prelude::*;
+use sea_orm::prelude::DateTimeUtc;
+
+use material_lp::{solve_portfolio, FUEL_DEMAND};
+use material_lp::portfolio::{Demand, PortfolioPlan};
+use material_lp::objective::parse_decomposed_list;
+use material_lp::price::PriceSnapshot;
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::*;
+use manager::entities::*;
+
+use crate::Handler;
+use crate::commands::number_option;
+use crate::error::{log, CommandError};
+use crate::report::{portfolio_table, Report};
+use crate::tenant::Tenant;
+
+struct Portfolio {
+    /// Active outposts with the names of the problems they serve.
+    outposts: Vec<(outpost::Model, Vec<String>)>,
+    /// Outpost names by id, with the capsuleer's name where two outposts share one.
+    labels: HashMap<i32, String>,
+    demands: Vec<Demand>,
+    /// Problem names from the most urgent, fuel first.
+    order: Vec<String>,
+}
+
+pub async fn run(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
+    let options = interaction.data.options();
+    let Some(days) = number_option(&options, "days") else {
+        return Err(CommandError::InvalidInput("Please provide the number of days".to_string()));
+    };
+    let portfolio = portfolio(days, &handle.db, tenant).await?;
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
+                format!(
+                    "Sharing {} outposts between {} problems...",
+                    portfolio.outposts.len(),
+                    portfolio.demands.len(),
+                ),
+            )),
+        )
+        .await?;
+    let order = portfolio.order;
+    let labels = portfolio.labels;
+    // Every demand solves the outposts again, so the portfolio is solved off the async runtime.
+    let solved = tokio::task::spawn_blocking(move || solve_portfolio(portfolio.outposts, portfolio.demands))
+        .await
+        .map_err(|error| CommandError::Solver(error.to_string()))
+        .and_then(|result| result.map_err(CommandError::Solver));
+    let report = match solved {
+        Ok(plan) => describe(&plan, &order, &labels),
+        Err(error) => {
+            log(interaction, &error);
+            Report::from(format!("**Failed**: {error}"))
+        }
+    };
+    interaction.edit_response(ctx, report.edit()).await?;
+    for followup in report.followups() {
+        interaction.create_followup(ctx, followup).await?;
+    }
+    Ok(())
+}
+
+/// Every active problem of the corporation, harvested for until its deadline or over the given days.
+async fn portfolio(days: f64, db: &DatabaseConnection, tenant: &Tenant) -> Result<Portfolio, CommandError> {
+    let problems = Problem::find_active_by_corporation(tenant.corporation.id, db).await?;
+    if problems.is_empty() {
+        return Err(CommandError::InvalidInput("There are no active problems, add one with /new_problem".to_string()));
+    }
+    let names: HashMap<i32, String> = problems.iter().map(|problem| (problem.id, problem.name.clone())).collect();
+    let outposts: Vec<(outpost::Model, Vec<String>)> = Outpost::find_active_with_problems(tenant.corporation.id, db)
+        .await?
+        .into_iter()
+        .map(|(outpost, problem_ids)| {
+            let serves = problem_ids.iter().filter_map(|id| names.get(id).cloned()).collect();
+            (outpost, serves)
+        })
+        .collect();
+    let capsuleers: HashMap<i32, String> = Capsuleer::find_by_corporation(tenant.corporation.id, db)
+        .await?
+        .into_iter()
+        .map(|capsuleer| (capsuleer.id, capsuleer.name))
+        .collect();
+    let labels = outposts
+        .iter()
+        .map(|(outpost, _)| {
+            let shared = outposts.iter().filter(|(other, _)| other.name == outpost.name).count() > 1;
+            let label = match capsuleers.get(&outpost.capsuleer_id) {
+                Some(capsuleer) if shared => format!("{} ({capsuleer})", outpost.name),
+                _ => outpost.name.clone(),
+            };
+            (outpost.id, label)
+        })
+        .collect();
+
+    let now = DateTimeUtc::from(std::time::SystemTime::now());
+    let mut prices = None;
+    let mut demands = Vec::with_capacity(problems.len());
+    for problem in problems {
+        let days = match problem.deadline {
+            Some(deadline) if deadline <= now => {
+                return Err(CommandError::InvalidInput(format!(
+                    "The deadline of {} passed on {}, move it with /schedule_problem",
+                    problem.name,
+                    deadline.format("%Y-%m-%d"),
+                )));
+            }
+            Some(deadline) => (deadline - now).num_seconds() as f64 / 86400.,
+            None => days,
+        };
+        let constraint = std::str::from_utf8(&problem.constraint)
+            .map_err(|_| CommandError::InvalidInput(format!("The material list of {} is not text", problem.name)))?;
+        let mut materials = parse_decomposed_list(constraint)
+            .map_err(|e| CommandError::InvalidInput(format!("The material list of {} could not be read: {e}", problem.name)))?;
+        if problem.corp_prices {
+            if prices.is_none() {
+                let (book, entries) = PriceBook::find_with_entries(tenant.corporation.id, None, db)
+                    .await?
+                    .ok_or(CommandError::InvalidInput(format!("{} is valued at corp prices but none are set, add them with /set_price", problem.name)))?;
+                prices = Some(PriceSnapshot::from_price_book(book, entries));
+            }
+            if let Some(prices) = &prices {
+                prices.revalue(&mut materials);
+            }
+        }
+        demands.push(Demand::new(problem.name, problem.priority, days, materials));
+    }
+
+    demands.sort_by(|a, b| a.priority.cmp(&b.priority).then(a.days.total_cmp(&b.days)));
+    let order = std::iter::once(FUEL_DEMAND.to_string())
+        .chain(demands.iter().map(|demand| demand.name.clone()))
+        .collect();
+    Ok(Portfolio { outposts, labels, demands, order })
+}
+
+fn describe(plan: &PortfolioPlan, order: &[String], labels: &HashMap<i32, String>) -> Report {
+    let mut content = format!(
+        "To secure the problems from the most urgent and maximize the value of the rest, worth {} ISK, place the arrays as follows:",
+        plan.value.round(),
+    );
+    if !plan.unmet.is_empty() {
+        content.push_str(&format!("\n**Unmet**: {} only get what is left of the outposts", plan.unmet.join(", ")));
+    }
+    Report::table(&content, &portfolio_table(plan, order, labels), "portfolio.csv")
+}
+
+pub fn register() -> CreateCommand {
+    CreateCommand::new("solve_portfolio").description("Share the outposts between every active problem using eve-anchor")
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "days", "The number of days to harvest for problems without a deadline")
+                .required(true)
+                .min_number_value(1.),
+        )
+}
@@ -1,10 +1,20 @@
+use prettytable::Table;
 use serenity::all::CommandInteraction;
+use serenity::all::CommandOptionType;
+use serenity::all::ResolvedValue;
 use serenity::builder::*;
 use serenity::prelude::*;
-use serenity::utils::CreateQuickModal;
+use sea_orm::{ColumnTrait, EntityTrait, QueryFilter};
+use sea_orm::prelude::DateTimeUtc;
 
-use material_lp::solve_for_constellation;
+use material_lp::{current_layout, plan_key, solve_for_constellation, Destination};
+use material_lp::cache::Cache;
+use material_lp::problem::{Move, Stability};
+use material_lp::sensitivity::Sensitivity;
+use material_lp::summary::{summarize, Summary};
 use material_lp::objective::parse_decomposed_list;
+use material_lp::price::{end_of_day, PastedList, PriceSnapshot, PriceSource};
+use material_lp::resource::Material;
 use material_lp::data::get_constellation;
 use material_lp::data::find_constellation_by_system;
 use manager::database::DatabaseConnection;
@@ -12,84 +22,293 @@ use manager::entities::prelude::*;
 use manager::entities::*;
 
 use crate::Handler;
-use crate::report::solution_table;
+use crate::commands::{number_option, string_option};
+use crate::error::{log, respond, CommandError};
+use crate::report::{csv, moves_embed, sensitivity_embed, solution_embeds, solution_table, Report};
+use crate::tenant::Tenant;
+
+struct Plan {
+    /// Plans are cached across servers, so the corporation is part of their key.
+    corporation_id: i32,
+    outposts: Vec<outpost::Model>,
+    materials: Vec<Material>,
+    days: f64,
+    destination: Option<Destination>,
+    constellation_name: String,
+    /// When the prices the materials are valued at were taken, if not the list's own.
+    priced_at: Option<String>,
+    /// The arrays anchored today, when the plan should keep close to them.
+    stability: Option<Stability>,
+    /// Whether to price the constraints too, solving again for each of them.
+    sensitivity: bool,
+}
+
+struct Solution {
+    summary: Summary,
+    sensitivity: Option<Sensitivity>,
+    /// Outpost names with the constellation they harvest.
+    outposts: Vec<(String, String)>,
+    /// The system the harvest is hauled to.
+    deliver_to: Option<String>,
+    priced_at: Option<String>,
+    /// Changes to the arrays anchored today.
+    moves: Option<Vec<Move>>,
+    table: Table,
+}
 
 pub async fn run(
     ctx: &Context,
     interaction: &CommandInteraction,
-    handle: &Handler
-    ) -> Result<(), serenity::Error> {
-    let modal = CreateQuickModal::new("Solve Problem")
-        .timeout(std::time::Duration::from_secs(600))
-        .short_field("Problem Name")
-        .short_field("Outpost Name")
-        .short_field("Number of Days");
-    let response = interaction.quick_modal(ctx, modal).await?.unwrap();
-    let inputs = response.inputs;
-    let (problem_name, outpost_name, days) = (&inputs[0], &inputs[1], &inputs[2]);
-
-    let db = &handle.db as &DatabaseConnection;
-    let outpost = Outpost::find_by_name(outpost_name, &db)
-        .await
-        .unwrap()
-        .unwrap();
-    let problem_outposts: Vec<(problem::Model, Option<outpost::Model>)> = Problem::find_outposts_by_name(problem_name, &db)
-            .await
-            .unwrap();
-
-    let constraint = std::str::from_utf8(&problem_outposts[0].0.constraint)
-        .expect("Failed to convert constraint to string");
-    let materials = parse_decomposed_list(constraint)
-        .expect("Failed to parse constraint");
-
-    let outposts: Vec<outpost::Model> = problem_outposts.iter().filter_map(|(_, outpost)| outpost.clone()).collect();
-    let key = format!("{}-{}-{}", outposts.len(), materials.len(), days);
-    if !&handle.cache.get(&key).is_some() {
-        response
-            .interaction
-            .create_response(
-                ctx,
-                CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                    format!("Calculating solution for {problem_name} in {outpost_name} for {days} days..."),
-                )),
-            )
-            .await?;
+    handle: &Handler,
+    tenant: &Tenant
+    ) -> Result<(), CommandError> {
+    let options = interaction.data.options();
+    let (Some(problem_name), Some(outpost_name), Some(days)) = (
+        string_option(&options, "problem"),
+        string_option(&options, "outpost"),
+        number_option(&options, "days"),
+    ) else {
+        return Err(CommandError::InvalidInput("Please provide a problem, outpost and number of days".to_string()));
+    };
+
+    let destination = string_option(&options, "deliver_to").map(|system| Destination {
+        system: system.to_string(),
+        max_jumps: number_option(&options, "max_jumps").map(|jumps| jumps as usize),
+        isk_per_unit_jump: number_option(&options, "isk_per_jump").unwrap_or(0.),
+    });
+    let prices_as_of = string_option(&options, "prices_as_of")
+        .map(|date| end_of_day(date).ok_or(CommandError::InvalidInput(format!("{date} is not a date like 2025-01-24"))))
+        .transpose()?;
+    let isk_per_move = number_option(&options, "stability");
+    let sensitivity = options.iter().any(|option| option.name == "sensitivity" && matches!(option.value, ResolvedValue::Boolean(true)));
+    let mut plan = plan(problem_name, outpost_name, days, destination, prices_as_of, &handle.db, tenant).await?;
+    plan.sensitivity = sensitivity;
+    if let Some(isk_per_move) = isk_per_move {
+        plan.stability = Some(stability(problem_name, &plan.outposts, isk_per_move, &handle.db).await?);
     }
-    if let Ok(result) = solve_for_constellation(outposts, materials, days.parse::<f64>().unwrap(), &handle.cache) {
-        let constellation_id = find_constellation_by_system(&outpost.system)
-            .expect("Failed to find constellation by system");
-        let constellation = get_constellation(*constellation_id);
-        let constellation_name = constellation.unwrap().en_name.to_string();
-        let solution = solution_table(constellation_name.to_string(), result);
-        response
-            .interaction
-            .create_response(
-                ctx,
-                CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                    format!(
-                        "To maximize total value for {} meeting the {} material requirements within {} days harvest the following:\n{}",
-                        outpost_name,
-                        problem_name,
-                        days,
-                        solution,
-                    ),
-                )),
-            )
-            .await?;
-        return Ok(());
-    } else {
-        interaction
-            .create_response(
-                ctx,
-                CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
-                    "Please provide a valid problem or run the /problem command",
-                )),
-            )
-            .await?;
+
+    let key = plan_key(plan.corporation_id, &plan.outposts, &plan.materials, plan.days, plan.destination.as_ref(), plan.stability.as_ref(), plan.sensitivity);
+    if handle.cache.get(&key).is_some() {
+        let result = solve(plan, &handle.cache)
+            .map(|solution| describe(problem_name, outpost_name, days, solution));
+        return respond(ctx, interaction, None, result).await;
+    }
+
+    interaction
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(CreateInteractionResponseMessage::new().content(
+                format!("Calculating solution for {problem_name} in {outpost_name} for {days} days..."),
+            )),
+        )
+        .await?;
+    // The calculating message is already public, so a failure replaces it rather than replying ephemerally.
+    let report = match solve_blocking(plan, &handle.cache).await {
+        Ok(solution) => describe(problem_name, outpost_name, days, solution),
+        Err(error) => {
+            log(interaction, &error);
+            Report::from(format!("**Failed**: {error}"))
+        }
+    };
+    interaction.edit_response(ctx, report.edit()).await?;
+    for followup in report.followups() {
+        interaction.create_followup(ctx, followup).await?;
     }
     Ok(())
 }
 
+async fn plan(
+    problem_name: &str,
+    outpost_name: &str,
+    days: f64,
+    destination: Option<Destination>,
+    prices_as_of: Option<DateTimeUtc>,
+    db: &DatabaseConnection,
+    tenant: &Tenant
+    ) -> Result<Plan, CommandError> {
+    let outpost = Outpost::find_active_by_corporation_and_name(tenant.corporation.id, outpost_name, db)
+        .await?
+        .ok_or(CommandError::NotFound(outpost_name.to_string()))?;
+    let problem = Problem::find_by_corporation_and_name(tenant.corporation.id, problem_name, db)
+        .await?
+        .filter(|problem| problem.active)
+        .ok_or(CommandError::NotFound(problem_name.to_string()))?;
+    let outposts = Outpost::find()
+        .filter(outpost::Column::ProblemId.eq(problem.id))
+        .filter(outpost::Column::Active.eq(true))
+        .all(db)
+        .await?;
+
+    let constraint = std::str::from_utf8(&problem.constraint)
+        .map_err(|_| CommandError::InvalidInput(format!("The material list of {problem_name} is not text")))?;
+    let mut materials = parse_decomposed_list(constraint)
+        .map_err(|e| CommandError::InvalidInput(format!("The material list of {problem_name} could not be read: {e}")))?;
+    let priced_at = match prices_as_of {
+        Some(at) => {
+            let snapshot = PriceSnapshot::as_of(db, tenant.corporation.id, at)
+                .await?
+                .ok_or(CommandError::InvalidInput(format!("No prices were recorded by {}", at.format("%Y-%m-%d"))))?;
+            let unpriced = snapshot.revalue(&mut materials);
+            Some(format!("{} prices from {}{}", snapshot.source, snapshot.taken_at.format("%Y-%m-%d %H:%M"), unpriced_note(&unpriced)))
+        }
+        None if problem.corp_prices => {
+            let (book, entries) = PriceBook::find_with_entries(tenant.corporation.id, None, db)
+                .await?
+                .ok_or(CommandError::InvalidInput(format!("{problem_name} is valued at corp prices but none are set, add them with /set_price")))?;
+            let snapshot = PriceSnapshot::from_price_book(book, entries);
+            let unpriced = snapshot.revalue(&mut materials);
+            let priced_at = format!("{} prices{}", snapshot.source, unpriced_note(&unpriced));
+            record_prices(snapshot, db, tenant).await?;
+            Some(priced_at)
+        }
+        None => {
+            let list = PastedList { name: problem_name.to_string(), materials: materials.clone() };
+            let snapshot = list.snapshot().await.map_err(|e| CommandError::InvalidInput(e.to_string()))?;
+            record_prices(snapshot, db, tenant).await?;
+            None
+        }
+    };
+
+    let constellation_name = constellation_name(&outpost.system)
+        .ok_or(CommandError::InvalidInput(format!("{} is not found in Eve", outpost.system)))?;
+
+    Ok(Plan {
+        corporation_id: tenant.corporation.id,
+        outposts,
+        materials,
+        days,
+        destination,
+        constellation_name,
+        priced_at,
+        stability: None,
+        sensitivity: false,
+    })
+}
+
+/// The arrays the outposts have anchored today, recorded with /place_arrays.
+async fn stability(problem_name: &str, outposts: &[outpost::Model], isk_per_move: f64, db: &DatabaseConnection) -> Result<Stability, CommandError> {
+    let outpost_ids = outposts.iter().map(|outpost| outpost.id).collect();
+    let placements = ArrayPlacement::find_by_outposts(outpost_ids, db).await?;
+    if placements.is_empty() {
+        return Err(CommandError::InvalidInput(format!("No arrays of {problem_name} are recorded, add them with /place_arrays")));
+    }
+    Ok(current_layout(&placements, isk_per_move))
+}
+
+/// Materials the prices did not quote, left at the valuation pasted with the problem.
+fn unpriced_note(unpriced: &[Box<str>]) -> String {
+    if unpriced.is_empty() {
+        return String::new();
+    }
+    format!(" ({} had no price and kept the list's valuation)", unpriced.join(", "))
+}
+
+/// Keep the prices a solve used when they changed since they were last recorded. Snapshots are
+/// corp-wide, so the materials the list does not quote keep the prices recorded before.
+async fn record_prices(mut snapshot: PriceSnapshot, db: &DatabaseConnection, tenant: &Tenant) -> Result<(), CommandError> {
+    let latest = PriceSnapshot::as_of(db, tenant.corporation.id, snapshot.taken_at).await?;
+    if let Some(latest) = &latest {
+        snapshot.merge(latest);
+    }
+    if latest.is_none_or(|latest| latest.prices != snapshot.prices) {
+        snapshot.record(db, tenant.corporation.id).await?;
+    }
+    Ok(())
+}
+
+/// Solve off the async runtime, a plan missing from the cache keeps the solver busy long enough to stall other commands.
+async fn solve_blocking(plan: Plan, cache: &Cache) -> Result<Solution, CommandError> {
+    let cache = cache.clone();
+    tokio::task::spawn_blocking(move || solve(plan, &cache))
+        .await
+        .map_err(|error| CommandError::Solver(error.to_string()))?
+}
+
+/// Run the solver and summarize the harvest for every outpost on the problem.
+fn solve(plan: Plan, cache: &Cache) -> Result<Solution, CommandError> {
+    let outposts: Vec<(String, String)> = plan.outposts
+        .iter()
+        .map(|outpost| (outpost.name.clone(), constellation_name(&outpost.system).unwrap_or_default()))
+        .collect();
+    let count = plan.outposts.len();
+    let deliver_to = plan.destination.as_ref().map(|destination| destination.system.clone());
+    let priced_at = plan.priced_at.clone();
+    let result = solve_for_constellation(plan.corporation_id, plan.outposts, plan.materials.clone(), plan.days, plan.destination.as_ref(), plan.stability.as_ref(), plan.sensitivity, cache)
+        .map_err(CommandError::Solver)?;
+    let moves = plan.stability.as_ref().map(|stability| stability.moves(&result.production));
+    let mut summary = summarize(&result.production, &plan.materials, plan.days, count);
+    // The outpost the command was run for leads.
+    summary.sections.sort_by_key(|section| section.key != plan.constellation_name);
+    Ok(Solution { summary, sensitivity: result.sensitivity, outposts, deliver_to, priced_at, moves, table: solution_table(result.production) })
+}
+
+fn describe(problem_name: &str, outpost_name: &str, days: f64, solution: Solution) -> Report {
+    let content = format!(
+        "To maximize total value for {} meeting the {} material requirements within {} days{}{}{} harvest the following:",
+        outpost_name,
+        problem_name,
+        days,
+        solution.deliver_to.as_ref().map(|system| format!(" delivered to {system}")).unwrap_or_default(),
+        solution.priced_at.as_ref().map(|prices| format!(" at {prices}")).unwrap_or_default(),
+        if solution.moves.is_some() { " keeping close to today's arrays" } else { "" },
+    );
+    let mut embeds = solution_embeds(&solution.summary, &solution.outposts);
+    if let Some(sensitivity) = &solution.sensitivity {
+        embeds.push(sensitivity_embed(sensitivity));
+    }
+    if let Some(moves) = &solution.moves {
+        embeds.push(moves_embed(moves));
+    }
+    Report::embeds(
+        content,
+        embeds,
+        csv(&solution.table, "solution.csv"),
+    )
+}
+
+fn constellation_name(system: &str) -> Option<String> {
+    find_constellation_by_system(system)
+        .and_then(|constellation_id| get_constellation(*constellation_id))
+        .map(|constellation| constellation.en_name.to_string())
+}
+
 pub fn register() -> CreateCommand {
     CreateCommand::new("solve_problem").description("Solve the problem with related outpost using eve-anchor")
-}
\ No newline at end of file
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "problem", "The problem to solve")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "outpost", "The outpost whose constellation is harvested")
+                .required(true)
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "days", "The number of days to harvest")
+                .required(true)
+                .min_number_value(1.),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "deliver_to", "The system the harvest is hauled to")
+                .set_autocomplete(true),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Integer, "max_jumps", "Leave out planets further than this from the delivery system")
+                .min_int_value(0),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "isk_per_jump", "ISK each unit loses for every jump it is hauled")
+                .min_number_value(0.),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::String, "prices_as_of", "Value the materials at the prices recorded by this day, e.g. 2025-01-24"),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Number, "stability", "ISK lost for every array anchored elsewhere than recorded with /place_arrays")
+                .min_number_value(0.),
+        )
+        .add_option(
+            CreateCommandOption::new(CommandOptionType::Boolean, "sensitivity", "Also price the constraints, slower as it solves the whole problem again for each one"),
+        )
+}
@@ -0,0 +1,139 @@
+// src/error.rs
+
+use std::fmt;
+use std::str::FromStr;
+
+use serenity::all::{CommandInteraction, ModalInteraction};
+use serenity::builder::*;
+use serenity::prelude::*;
+use sea_orm::DbErr;
+
+use crate::report::Report;
+
+/// Why a command could not be completed, the `Display` text is shown to the member who ran it.
+#[derive(Debug)]
+pub enum CommandError {
+    Database(DbErr),
+    Discord(Box<serenity::Error>),
+    Timeout,
+    InvalidInput(String),
+    NotFound(String),
+    Solver(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::Database(_) => write!(f, "eve-anchor could not reach its database, try again later"),
+            CommandError::Discord(_) => write!(f, "Discord rejected the reply, try again later"),
+            CommandError::Timeout => write!(f, "The form timed out, run the command again"),
+            CommandError::InvalidInput(reason) => write!(f, "{reason}"),
+            CommandError::NotFound(name) => write!(f, "{name} is not registered with eve-anchor"),
+            CommandError::Solver(reason) => write!(f, "No solution was found: {reason}"),
+        }
+    }
+}
+
+impl std::error::Error for CommandError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CommandError::Database(error) => Some(error),
+            CommandError::Discord(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<DbErr> for CommandError {
+    fn from(error: DbErr) -> Self {
+        CommandError::Database(error)
+    }
+}
+
+impl From<manager::Error> for CommandError {
+    fn from(error: manager::Error) -> Self {
+        match error {
+            manager::Error::NotFound { key, .. } => CommandError::NotFound(key),
+            manager::Error::Duplicate { key, .. } => CommandError::InvalidInput(format!("{key} already registered with eve-anchor")),
+            manager::Error::Ambiguous { entity, key } => CommandError::InvalidInput(
+                format!("More than one {entity} is named {key}, rename one to tell them apart"),
+            ),
+            manager::Error::Inactive { entity, key } => CommandError::InvalidInput(format!("The {entity} {key} is retired")),
+            manager::Error::Unsupported { entity, key } => CommandError::InvalidInput(format!("This {entity} {key} is not supported")),
+            manager::Error::Forbidden { entity, key } => CommandError::InvalidInput(
+                format!("The {entity} {key} is bound to another server or needs a claim token issued from the API"),
+            ),
+            manager::Error::Connection(error) | manager::Error::Database(error) => CommandError::Database(error),
+        }
+    }
+}
+
+impl From<serenity::Error> for CommandError {
+    fn from(error: serenity::Error) -> Self {
+        CommandError::Discord(Box::new(error))
+    }
+}
+
+impl CommandError {
+    pub fn response(&self) -> CreateInteractionResponse {
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(format!("**Failed**: {self}"))
+                .ephemeral(true),
+        )
+    }
+}
+
+/// Parse a value typed into a modal field, naming the field when it is not understood.
+pub fn parse<T: FromStr>(field: &str, value: &str) -> Result<T, CommandError> {
+    value
+        .trim()
+        .parse::<T>()
+        .map_err(|_| CommandError::InvalidInput(format!("{field} must be a number, not \"{value}\"")))
+}
+
+pub fn log(interaction: &CommandInteraction, error: &CommandError) {
+    tracing::error!(
+        command = %interaction.data.name,
+        user = %interaction.user.tag(),
+        guild = ?interaction.guild_id.map(|guild_id| guild_id.get()),
+        error = ?error,
+        "command failed"
+    );
+}
+
+/// Reply with the report or an ephemeral reason, on the modal submission when the command opened one
+/// since the command interaction itself was already answered with the modal. Pages past the first follow up.
+pub async fn respond(
+    ctx: &Context,
+    interaction: &CommandInteraction,
+    modal: Option<&ModalInteraction>,
+    result: Result<Report, CommandError>
+    ) -> Result<(), CommandError> {
+    let report = match result {
+        Ok(report) => report,
+        Err(error) => {
+            log(interaction, &error);
+            return match modal {
+                Some(modal) => modal.create_response(ctx, error.response()).await,
+                None => interaction.create_response(ctx, error.response()).await,
+            }
+            .map_err(CommandError::from);
+        }
+    };
+    match modal {
+        Some(modal) => {
+            modal.create_response(ctx, report.response()).await?;
+            for followup in report.followups() {
+                modal.create_followup(ctx, followup).await?;
+            }
+        }
+        None => {
+            interaction.create_response(ctx, report.response()).await?;
+            for followup in report.followups() {
+                interaction.create_followup(ctx, followup).await?;
+            }
+        }
+    }
+    Ok(())
+}
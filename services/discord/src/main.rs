@@ -1,156 +1,228 @@
 // src/main.rs
 
+mod autocomplete;
 mod commands;
+mod error;
 mod report;
+mod tenant;
 
 use dotenv::dotenv;
+use tracing_subscriber::EnvFilter;
 
 use serenity::async_trait;
-use serenity::builder::{CreateInteractionResponse, CreateInteractionResponseMessage};
-use serenity::model::application::Interaction;
+use serenity::model::application::{Command, CommandInteraction, Interaction};
 use serenity::model::gateway::Ready;
-use serenity::model::id::GuildId;
 use serenity::prelude::*;
 
 use material_lp::cache::Cache;
 use material_lp::data;
 use manager::database::DatabaseConnection;
-use manager::database::DatabaseManager;
-use manager::entities::prelude::Alliance;
-use manager::entities::prelude::Corporation;
-use manager::entities::alliance;
-use manager::entities::corporation;
-use manager::new_alliance;
-use manager::new_corporation;
+use manager::database::{DatabaseManager, MigrationPolicy};
+use manager::audit::Actor;
+
+use error::{respond, CommandError};
+use report::Report;
+use tenant::Tenant;
 
 #[derive(Debug, Eq, PartialEq, Hash)]
 pub struct CacheKey(String, String, i64);
 
 struct Handler {
-    alliance: alliance::Model,
-    corporation: corporation::Model,
     db: DatabaseConnection,
     cache: Cache,
 }
 
+impl Handler {
+    async fn dispatch(&self, ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+        if command.data.name == "setup" {
+            let content = commands::setup::run(command, &self.db).await?;
+            return respond(ctx, command, None, Ok(content.into())).await;
+        }
+
+        let tenant = match command.guild_id {
+            Some(guild_id) => Tenant::resolve(guild_id, &self.db).await?,
+            None => None,
+        }
+        .ok_or(CommandError::InvalidInput(
+            "This server is not bound to a corporation, ask an administrator to run /setup".to_string(),
+        ))?;
+
+        let actor = command.user.tag();
+        let actor = Actor::Discord(&actor);
+        let content = match command.data.name.as_str() {
+            "new_member" => Some(
+                commands::new_member::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "new_capsuleer" => {
+                commands::new_capsuleer::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "new_outpost" => {
+                commands::new_outpost::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "new_problem" => {
+                commands::new_problem::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "delete_outpost" => Some(
+                commands::delete_outpost::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "edit_outpost" => {
+                commands::edit_outpost::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "edit_problem" => {
+                commands::edit_problem::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "edit_skills" => {
+                commands::edit_skills::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "retire_capsuleer" => Some(
+                commands::retire_capsuleer::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "retire_problem" => Some(
+                commands::retire_problem::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "restore" => Some(
+                commands::restore::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "add_member_outpost_to_problem" => Some(
+                commands::add_member_outpost_to_problem::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "solve_problem" => {
+                commands::solve_problem::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "solve_portfolio" => {
+                commands::solve_portfolio::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "plan_build" => {
+                commands::plan_build::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "scout" => {
+                commands::scout::run(ctx, command, self, &tenant).await?;
+                None
+            },
+            "list_outpost" => Some(
+                commands::list_outpost::run(&self.db, &tenant).await?
+            ),
+            "list_problem" => Some(
+                commands::list_problem::run(&self.db, &tenant).await?
+            ),
+            "audit" => Some(
+                commands::audit::run(&command.data.options(), &self.db, &tenant).await?
+            ),
+            "planets" => Some(
+                commands::planets::run(&command.data.options())?
+            ),
+            "set_price" => Some(
+                commands::set_price::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "price_book" => Some(
+                commands::price_book::run(&command.data.options(), &self.db, &tenant).await?
+            ),
+            "problem_prices" => Some(
+                commands::problem_prices::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "link_outpost" => Some(
+                commands::link_outpost::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "schedule_problem" => Some(
+                commands::schedule_problem::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "place_arrays" => Some(
+                commands::place_arrays::run(&command.data.options(), &self.db, &tenant, actor).await?.into()
+            ),
+            "list_problem_material" => Some(
+                commands::list_problem_material::run(&command.data.options(), &self.db, &tenant).await?
+            ),
+            _ => Some(Report::from("not implemented.".to_string())),
+        };
+
+        match content {
+            Some(content) => respond(ctx, command, None, Ok(content)).await,
+            None => Ok(()),
+        }
+    }
+}
+
 #[async_trait]
 impl EventHandler for Handler {
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
-        if let Interaction::Command(command) = interaction {
-            let content = match command.data.name.as_str() {
-                "new_member" => {
-                    commands::new_member::run(&ctx, &command, &self.db).await.unwrap();
-                    None
-                },
-                "new_capsuleer" => {
-                    commands::new_capsuleer::run(&ctx, &command, &self).await.unwrap();
-                    None
-                },
-                "new_outpost" => {
-                    commands::new_outpost::run(&ctx, &command, &self).await.unwrap();
-                    None
-                },
-                "new_problem" => {
-                    commands::new_problem::run(&ctx, &command, &self).await.unwrap();
-                    None
-                },
-                "delete_outpost" => {
-                    commands::delete_outpost::run(&ctx, &command, &self).await.unwrap();
-                    None
-                },
-                "add_member_outpost_to_problem" => {
-                    commands::add_member_outpost_to_problem::run(&ctx, &command, &self.db).await.unwrap();
-                    None
-                },
-                "solve_problem" => {
-                    commands::solve_problem::run(&ctx, &command, &self).await.unwrap();
-                    None
-                },
-                "list_outpost" => Some(
-                    commands::list_outpost::run(&self.db).await
-                ),
-                "list_problem" => Some(
-                    commands::list_problem::run(&self.db).await
-                ),
-                "list_problem_material" => Some(
-                    commands::list_problem_material::run(&command.data.options(), &self.db).await
-                ),
-                _ => Some("not implemented.".to_string()),
-            };
-
-            if let Some(content) = content {
-                let data = CreateInteractionResponseMessage::new().content(content);
-                let builder = CreateInteractionResponse::Message(data);
-                if let Err(why) = command.create_response(&ctx.http, builder).await {
-                    println!("Cannot respond to slash command: {why}");
+        match interaction {
+            Interaction::Command(command) => {
+                if let Err(error) = self.dispatch(&ctx, &command).await {
+                    if let Err(why) = respond(&ctx, &command, None, Err(error)).await {
+                        tracing::warn!(command = %command.data.name, error = ?why, "cannot respond to slash command");
+                    }
+                }
+            },
+            Interaction::Autocomplete(command) => {
+                if let Err(error) = autocomplete::run(&ctx, &command, &self.db).await {
+                    tracing::warn!(command = %command.data.name, error = ?error, "cannot suggest choices");
                 }
-            }
+            },
+            _ => {},
         }
     }
 
     async fn ready(&self, ctx: Context, ready: Ready) {
-        println!("{} is connected!", ready.user.name);
-
-        let guild_id = GuildId::new(
-            std::env::var("GUILD_ID")
-                .expect("Expected GUILD_ID in the .env file")
-                .parse()
-                .expect("GUILD_ID must be a valid u64"),
-        );
-
-        let _ = guild_id
-            .set_commands(&ctx.http, vec![
-                commands::new_member::register(),
-                commands::new_capsuleer::register(),
-                commands::new_outpost::register(),
-                commands::new_problem::register(),
-                commands::solve_problem::register(),
-                commands::list_outpost::register(),
-                commands::list_problem::register(),
-                commands::list_problem_material::register(),
-                commands::add_member_outpost_to_problem::register(),
-                commands::delete_outpost::register(),
-            ])
-            .await;
-    }
-}
-
-async fn validate(alliance: &str, corporation: &str, db: &DatabaseConnection) {
-    if let Some(_) = Corporation::find_by_name(corporation, db)
-        .await
-        .unwrap() {
-            return
-    } else if let Some(alliance) = Alliance::find_by_name(alliance, db)
-        .await
-        .unwrap() {
-        let _ = new_corporation(&db, corporation, alliance.id)
-            .await
-            .expect("Failed to add corporation to database");
-    } else {
-        let alliance = new_alliance(&db, alliance)
-            .await
-            .expect("Failed to add alliance to database");
-        let _ = new_corporation(&db, corporation, alliance.last_insert_id)
-            .await
-            .expect("Failed to add corporation to database");
+        tracing::info!(user = %ready.user.name, "connected");
+
+        let _ = Command::set_global_commands(&ctx.http, vec![
+            commands::setup::register(),
+            commands::new_member::register(),
+            commands::new_capsuleer::register(),
+            commands::new_outpost::register(),
+            commands::new_problem::register(),
+            commands::solve_problem::register(),
+            commands::list_outpost::register(),
+            commands::list_problem::register(),
+            commands::list_problem_material::register(),
+            commands::add_member_outpost_to_problem::register(),
+            commands::delete_outpost::register(),
+            commands::edit_outpost::register(),
+            commands::edit_problem::register(),
+            commands::edit_skills::register(),
+            commands::retire_capsuleer::register(),
+            commands::retire_problem::register(),
+            commands::restore::register(),
+            commands::audit::register(),
+            commands::scout::register(),
+            commands::planets::register(),
+            commands::set_price::register(),
+            commands::price_book::register(),
+            commands::problem_prices::register(),
+            commands::link_outpost::register(),
+            commands::schedule_problem::register(),
+            commands::solve_portfolio::register(),
+            commands::plan_build::register(),
+            commands::place_arrays::register(),
+        ])
+        .await;
     }
 }
 
 #[tokio::main]
 async fn main() {
     dotenv().ok();
+    tracing_subscriber::fmt()
+        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .init();
     let token = std::env::var("ABOT_TOKEN")
         .expect("Expected ABOT_TOKEN in the .env file");
     let config = manager::environment::EnvironmentManager::load_config("local")
         .await
         .expect("Failed to load configuration");
-    let db = DatabaseManager::revision(&config)
+    let db = DatabaseManager::start(&config, MigrationPolicy::from_env())
         .await
         .expect("Failed to connect to database");
-    validate("NRFD", "REEF", &db).await;
     let bot = Handler {
-        alliance: Alliance::find_by_name("NRFD", &db).await.unwrap().unwrap(),
-        corporation: Corporation::find_by_name("REEF", &db).await.unwrap().unwrap(),
         db,
         cache: Cache::new(std::time::Duration::from_secs(300)),
     };
@@ -158,8 +230,10 @@ async fn main() {
     // Load lazy_static data
     let _ = &*data::CELESTIALS;
     let _ = &*data::CONSTELLATIONS;
+    let _ = &*data::REGIONS;
     let _ = &*data::ITEMS;
     let _ = &*data::SYSTEMS;
+    let _ = &*data::STARGATES;
     let _ = &*data::PLANETS;
     
 
@@ -171,7 +245,7 @@ async fn main() {
         .expect("Error creating client");
 
     if let Err(why) = client.start().await {
-        println!("Client error: {why:?}");
+        tracing::error!(error = ?why, "client error");
     }
 }
 
@@ -1,7 +1,184 @@
 use prettytable::{Table, Row, Cell, format::Alignment, format::FormatBuilder,row};
+use serenity::builder::*;
+use serenity::model::Colour;
 use std::cmp::Ordering;
-use material_lp::data::{get_celestial, system_by_planet, get_item};
+use std::collections::HashMap;
+use material_lp::data::{get_celestial, get_region, system_by_planet, get_item, PlanetRow};
 use material_lp::resource::{Material, CelestialResource}; 
+use material_lp::portfolio::{Assignment, PortfolioPlan};
+use material_lp::problem::Move;
+use material_lp::schedule::SchedulePlan;
+use material_lp::scout::Prospect;
+use material_lp::sensitivity::Sensitivity;
+use material_lp::summary::{by_region, Progress, Section, Summary};
+
+/// Discord rejects messages longer than this many characters.
+const MESSAGE_LIMIT: usize = 2000;
+/// Discord allows at most this many embeds, holding this many characters in total, on one message.
+const EMBEDS_PER_MESSAGE: usize = 10;
+const EMBED_LIMIT: usize = 6000;
+const FIELD_LIMIT: usize = 1024;
+const CODE_BLOCK: &str = "```";
+const PROGRESS_CELLS: usize = 10;
+/// Lines listed for each kind of marginal value.
+const MARGINAL_LINES: usize = 5;
+
+/// One message of a report.
+#[derive(Default)]
+pub struct Page {
+    pub content: String,
+    pub embeds: Vec<CreateEmbed>,
+}
+
+/// A reply split into messages that each fit Discord's limit, the full table attached when it spans several.
+pub struct Report {
+    pub pages: Vec<Page>,
+    pub attachment: Option<CreateAttachment>,
+}
+
+impl From<String> for Report {
+    fn from(content: String) -> Self {
+        Report { pages: vec![Page { content, embeds: vec![] }], attachment: None }
+    }
+}
+
+/// Embed text kept apart from the builder so its length can be counted against Discord's limits.
+pub struct Embed {
+    pub title: String,
+    pub description: String,
+    pub fields: Vec<(String, String)>,
+    pub colour: Colour,
+}
+
+impl Embed {
+    fn len(&self) -> usize {
+        self.title.chars().count()
+            + self.description.chars().count()
+            + self.fields.iter().map(|(name, value)| name.chars().count() + value.chars().count()).sum::<usize>()
+    }
+
+    fn build(self) -> CreateEmbed {
+        let embed = CreateEmbed::new().title(self.title).description(self.description).colour(self.colour);
+        self.fields.into_iter().fold(embed, |embed, (name, value)| embed.field(name, value, false))
+    }
+}
+
+impl Report {
+    /// Render the table after the preamble, one code block per page, attaching it as `filename` CSV when long.
+    pub fn table(preamble: &str, table: &Table, filename: &str) -> Report {
+        let pages: Vec<Page> = paginate(preamble, &table.to_string())
+            .into_iter()
+            .map(|content| Page { content, embeds: vec![] })
+            .collect();
+        let attachment = if pages.len() > 1 { csv(table, filename) } else { None };
+        Report { pages, attachment }
+    }
+
+    /// The content followed by the embeds, spread over as many messages as Discord's embed limits need.
+    pub fn embeds(content: String, embeds: Vec<Embed>, attachment: Option<CreateAttachment>) -> Report {
+        let mut pages = vec![Page { content, embeds: vec![] }];
+        let mut length = 0;
+        for embed in embeds {
+            let page = pages.last_mut().expect("a report has a first page");
+            if !page.embeds.is_empty() && (page.embeds.len() == EMBEDS_PER_MESSAGE || length + embed.len() > EMBED_LIMIT) {
+                pages.push(Page::default());
+                length = 0;
+            }
+            length += embed.len();
+            pages.last_mut().expect("a report has a page").embeds.push(embed.build());
+        }
+        Report { pages, attachment }
+    }
+
+    fn attachment_on(&self, index: usize) -> Option<CreateAttachment> {
+        match index + 1 == self.pages.len() {
+            true => self.attachment.clone(),
+            false => None,
+        }
+    }
+
+    /// The first page as the reply, with the attachment when there is nothing to follow it.
+    pub fn response(&self) -> CreateInteractionResponse {
+        let page = &self.pages[0];
+        let mut message = CreateInteractionResponseMessage::new().embeds(page.embeds.clone());
+        if !page.content.is_empty() {
+            message = message.content(page.content.clone());
+        }
+        if let Some(attachment) = self.attachment_on(0) {
+            message = message.add_file(attachment);
+        }
+        CreateInteractionResponse::Message(message)
+    }
+
+    /// The first page replacing a reply already sent, such as a progress message.
+    pub fn edit(&self) -> EditInteractionResponse {
+        let page = &self.pages[0];
+        let mut edit = EditInteractionResponse::new().content(page.content.clone()).embeds(page.embeds.clone());
+        if let Some(attachment) = self.attachment_on(0) {
+            edit = edit.new_attachment(attachment);
+        }
+        edit
+    }
+
+    /// The remaining pages, the last one carrying the attachment.
+    pub fn followups(&self) -> Vec<CreateInteractionResponseFollowup> {
+        self.pages
+            .iter()
+            .enumerate()
+            .skip(1)
+            .map(|(index, page)| {
+                let mut followup = CreateInteractionResponseFollowup::new().embeds(page.embeds.clone());
+                if !page.content.is_empty() {
+                    followup = followup.content(page.content.clone());
+                }
+                match self.attachment_on(index) {
+                    Some(attachment) => followup.add_file(attachment),
+                    None => followup,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The table as a CSV attachment.
+pub fn csv(table: &Table, filename: &str) -> Option<CreateAttachment> {
+    table
+        .to_csv(Vec::new())
+        .ok()
+        .and_then(|writer| writer.into_inner().ok())
+        .map(|csv| CreateAttachment::bytes(csv, filename))
+}
+
+/// Split a rendered table into code blocks on row boundaries, repeating the header row on every page.
+fn paginate(preamble: &str, rendered: &str) -> Vec<String> {
+    let mut lines = rendered.lines();
+    let header = lines.next().unwrap_or_default();
+    let open = |preamble: &str| {
+        let prefix = if preamble.is_empty() { String::new() } else { format!("{preamble}\n") };
+        format!("{prefix}{CODE_BLOCK}\n{header}\n")
+    };
+    // Room for the closing fence.
+    let budget = MESSAGE_LIMIT - CODE_BLOCK.len();
+
+    let mut pages = Vec::new();
+    let mut page = open(preamble);
+    let mut rows = 0;
+    for line in lines {
+        if rows > 0 && page.chars().count() + line.chars().count() + 1 > budget {
+            page.push_str(CODE_BLOCK);
+            pages.push(page);
+            page = open("");
+            rows = 0;
+        }
+        let room = budget.saturating_sub(page.chars().count() + 1);
+        page.extend(line.chars().take(room));
+        page.push('\n');
+        rows += 1;
+    }
+    page.push_str(CODE_BLOCK);
+    pages.push(page);
+    pages
+}
 
 pub struct SolutionTable {
     pub celestial: String,
@@ -9,24 +186,20 @@ pub struct SolutionTable {
     pub arrays: f64,
 }
 
-pub fn solution_table(key: String, values: Vec<(CelestialResource, f64)>) -> String {
+pub fn solution_table(values: Vec<(CelestialResource, f64)>) -> Table {
     let mut solution_table: Vec<SolutionTable> = Vec::new();
 
     for (celestial_resource, value) in values.iter() {
         if round_to_2_decimal_places(*value) == 0.0 {
             continue;
         }
-        if *key != celestial_resource.key {
-            continue;
-        }
         let system = system_by_planet(celestial_resource.planet_id)
             .unwrap()
             .en_name
             .clone();
         let planet = get_celestial(celestial_resource.planet_id)
             .unwrap()
-            .celestial_index
-            .clone();
+            .celestial_index;
         let resource = get_item(celestial_resource.resource_type_id)
             .unwrap()
             .en_name
@@ -77,15 +250,10 @@ pub fn solution_table(key: String, values: Vec<(CelestialResource, f64)>) -> Str
         ]));
     }
 
-    let table_string = table.to_string();
-    let formatted_output = match table_string.get(..1999) {
-        Some(substring) => format!("```\n{}\n```", substring),
-        None => format!("```\n{}\n```", table_string),
-    };
-    formatted_output
+    table
 }
 
-pub fn material_table(requirements: Vec<Material>) -> String {
+pub fn material_table(requirements: Vec<Material>) -> Table {
     let mut table = Table::new();
     table.set_format(FormatBuilder::new().padding(1, 1).build());
 
@@ -100,12 +268,291 @@ pub fn material_table(requirements: Vec<Material>) -> String {
             Cell::new_align(&format_value(material.valuation), Alignment::RIGHT),
         ]));
     }
-    let table_string = table.to_string();
-    let formatted_output = match table_string.get(..1999) {
-        Some(substring) => format!("```\n{}\n```", substring),
-        None => format!("```\n{}\n```", table_string),
+    table
+}
+
+/// Planet resources as the query ordered them, each planet labelled as the game does.
+pub fn planet_table(rows: &[PlanetRow]) -> Table {
+    let mut table = Table::new();
+    table.set_format(FormatBuilder::new().padding(1, 1).build());
+    table.add_row(row![b => "Celestial", "Region", "Constellation", "Resource", "Output", "Richness"]);
+    for planet in rows {
+        table.add_row(Row::new(vec![
+            Cell::new_align(&format!("{} {}", planet.system, planet.celestial_index), Alignment::LEFT),
+            Cell::new_align(&planet.region, Alignment::LEFT),
+            Cell::new_align(&planet.constellation, Alignment::LEFT),
+            Cell::new_align(&planet.resource, Alignment::LEFT),
+            Cell::new_align(&round_to_2_decimal_places(planet.init_output).to_string(), Alignment::RIGHT),
+            Cell::new_align(&planet.richness_index.to_string(), Alignment::RIGHT),
+        ]));
+    }
+    table
+}
+
+/// Prospective constellations in rank order, with the system holding most of their best planets.
+pub fn scout_table(prospects: &[Prospect]) -> Table {
+    let mut table = Table::new();
+    table.set_format(FormatBuilder::new().padding(1, 1).build());
+    table.add_row(row![b => "Constellation", "System", "Coverage", "ISK/day", "Missing"]);
+    for prospect in prospects {
+        let missing: Vec<String> = prospect.evaluation.missing.iter().map(|resource_type_id| item_name(*resource_type_id)).collect();
+        table.add_row(Row::new(vec![
+            Cell::new_align(&prospect.constellation, Alignment::LEFT),
+            Cell::new_align(&prospect.system, Alignment::LEFT),
+            Cell::new_align(&format!("{:.0}%", prospect.evaluation.coverage * 100.), Alignment::RIGHT),
+            Cell::new_align(&format_value(prospect.evaluation.isk_per_day), Alignment::RIGHT),
+            Cell::new_align(&missing.join(", "), Alignment::LEFT),
+        ]));
+    }
+    table
+}
+
+/// Arrays of every outpost by the problem they serve, most urgent problem first.
+pub fn portfolio_table(plan: &PortfolioPlan, order: &[String], labels: &HashMap<i32, String>) -> Table {
+    let label = |assignment: &Assignment| labels.get(&assignment.site_id).cloned().unwrap_or_else(|| assignment.site.clone());
+    let mut assignments: Vec<&Assignment> = plan.assignments.iter().collect();
+    assignments.sort_by_key(|assignment| (
+        order.iter().position(|name| *name == assignment.demand),
+        label(assignment),
+        assignment.site_id,
+        assignment.resource.planet_id,
+    ));
+    let mut table = Table::new();
+    table.set_format(FormatBuilder::new().padding(1, 1).build());
+    table.add_row(row![b => "Problem", "Outpost", "Planet", "Resource", "Arrays"]);
+    for assignment in assignments {
+        table.add_row(Row::new(vec![
+            Cell::new_align(&assignment.demand, Alignment::LEFT),
+            Cell::new_align(&label(assignment), Alignment::LEFT),
+            Cell::new_align(&planet_label(assignment.resource.planet_id), Alignment::LEFT),
+            Cell::new_align(&item_name(assignment.resource.resource_type_id), Alignment::LEFT),
+            Cell::new_align(&round_to_2_decimal_places(assignment.arrays).to_string(), Alignment::RIGHT),
+        ]));
+    }
+    table
+}
+
+/// The arrays of every week of a schedule, with how many were moved to place them.
+pub fn schedule_table(plan: &SchedulePlan) -> Table {
+    let mut table = Table::new();
+    table.set_format(FormatBuilder::new().padding(1, 1).build());
+    table.add_row(row![b => "Week", "Constellation", "Planet", "Resource", "Arrays"]);
+    for (index, period) in plan.periods.iter().enumerate() {
+        for (resource, arrays) in &period.production {
+            if round_to_2_decimal_places(*arrays) == 0.0 {
+                continue;
+            }
+            table.add_row(Row::new(vec![
+                Cell::new_align(&(index + 1).to_string(), Alignment::RIGHT),
+                Cell::new_align(&resource.key, Alignment::LEFT),
+                Cell::new_align(&planet_label(resource.planet_id), Alignment::LEFT),
+                Cell::new_align(&item_name(resource.resource_type_id), Alignment::LEFT),
+                Cell::new_align(&round_to_2_decimal_places(*arrays).to_string(), Alignment::RIGHT),
+            ]));
+        }
+    }
+    table
+}
+
+/// A summary embed for the whole plan, then one embed per constellation naming the outposts harvesting it.
+/// The solver pools the arrays of every outpost in a constellation, so its figures are not split between them.
+pub fn solution_embeds(summary: &Summary, outposts: &[(String, String)]) -> Vec<Embed> {
+    let met = summary.materials.iter().filter(|progress| progress.met()).count();
+    let colour = if met == summary.materials.len() { Colour::DARK_GREEN } else { Colour::ORANGE };
+
+    let mut embeds = vec![Embed {
+        title: "Plan".to_string(),
+        description: format!(
+            "**ISK per day**: {}\n**Fuel**: {} Liquid Ozone\n**Materials met**: {met} of {}, {} outstanding",
+            format_value(summary.isk_per_day),
+            format_value(summary.fuel),
+            summary.materials.len(),
+            summary.materials.len() - met,
+        ),
+        fields: fields("Materials", summary.materials.iter().map(progress_line))
+            .into_iter()
+            .chain(fields("Regions", by_region(summary, |planet_id| get_celestial(planet_id).map(|celestial| celestial.region_id))
+                .iter()
+                .map(|region| format!(
+                    "{}: {} ISK/day, {} arrays in {}",
+                    get_region(region.region_id)
+                        .map(|found| found.en_name.to_string())
+                        .unwrap_or_else(|| region.region_id.to_string()),
+                    format_value(region.isk_per_day),
+                    round_to_2_decimal_places(region.arrays),
+                    region.sections.join(", "),
+                ))))
+            .collect(),
+        colour,
+    }];
+
+    for section in &summary.sections {
+        let names: Vec<&str> = outposts
+            .iter()
+            .filter(|(_, key)| *key == section.key)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        embeds.push(Embed {
+            title: section.key.clone(),
+            description: format!(
+                "**Outposts**: {}\n**ISK per day**: {}\n**Arrays**: {}",
+                match names.len() {
+                    0 => "-".to_string(),
+                    1 => names[0].to_string(),
+                    _ => format!("{}, the arrays are shared between them", names.join(", ")),
+                },
+                format_value(section.isk_per_day),
+                round_to_2_decimal_places(section.arrays),
+            ),
+            fields: harvest_field(section),
+            colour,
+        });
+    }
+    embeds
+}
+
+/// What one more array, a larger material requirement or an unused resource is worth to the plan.
+pub fn sensitivity_embed(sensitivity: &Sensitivity) -> Embed {
+    let marginal = |value: Option<f64>, unit: &str| match value {
+        Some(value) => format!("{} {unit}", format_isk(value)),
+        None => "not possible".to_string(),
     };
-    formatted_output
+    let arrays = sensitivity.constellations
+        .iter()
+        .take(MARGINAL_LINES)
+        .map(|constellation| format!("{}: {}", constellation.key, marginal(constellation.isk_per_day, "ISK/day")))
+        .chain(sensitivity.planets
+            .iter()
+            .take(MARGINAL_LINES)
+            .map(|planet| format!("{}: {}", planet_label(planet.key), marginal(planet.isk_per_day, "ISK/day"))));
+    let materials = sensitivity.materials
+        .iter()
+        .filter(|material| material.isk != Some(0.))
+        .take(MARGINAL_LINES)
+        .map(|material| format!("{}: {}", item_name(material.resource_type_id), marginal(material.isk.map(|isk| -isk), "ISK")));
+    let unused = sensitivity.reduced_costs
+        .iter()
+        .take(MARGINAL_LINES)
+        .map(|resource| format!(
+            "{} {}: {}",
+            planet_label(resource.planet_id),
+            item_name(resource.resource_type_id),
+            marginal(resource.isk_per_day, "ISK/day"),
+        ));
+
+    let mut marginal_fields = fields("One more array", arrays);
+    marginal_fields.extend(fields("10% more of a material", materials));
+    marginal_fields.extend(fields("One array on an unused resource", unused));
+    Embed {
+        title: "Where to anchor next".to_string(),
+        description: format!("Change in the plan's {} ISK per day, or ISK over the whole period for materials", format_value(sensitivity.isk_per_day)),
+        fields: marginal_fields,
+        colour: Colour::BLUE,
+    }
+}
+
+/// What the plan changes on the arrays anchored today, arrays taken down first.
+pub fn moves_embed(moves: &[Move]) -> Embed {
+    let moved: f64 = moves.iter().map(|change| (change.to - change.from).max(0.)).sum();
+    let line = |change: &Move| format!(
+        "{} {}: {} → {}",
+        planet_label(change.planet_id),
+        item_name(change.resource_type_id),
+        round_to_2_decimal_places(change.from),
+        round_to_2_decimal_places(change.to),
+    );
+    let mut change_fields = fields("Take down", moves.iter().filter(|change| change.to < change.from).map(line));
+    change_fields.extend(fields("Anchor", moves.iter().filter(|change| change.to > change.from).map(line)));
+    Embed {
+        title: "Changes to today's layout".to_string(),
+        description: if moves.is_empty() {
+            "Keep every array where it is".to_string()
+        } else {
+            format!("{} arrays anchored elsewhere", round_to_2_decimal_places(moved))
+        },
+        fields: change_fields,
+        colour: if moves.is_empty() { Colour::DARK_GREEN } else { Colour::ORANGE },
+    }
+}
+
+fn planet_label(planet_id: i64) -> String {
+    format!(
+        "{} {}",
+        system_by_planet(planet_id)
+            .map(|system| system.en_name.to_string())
+            .unwrap_or_default(),
+        get_celestial(planet_id)
+            .map(|celestial| celestial.celestial_index.to_string())
+            .unwrap_or_default(),
+    )
+}
+
+fn item_name(resource_type_id: i64) -> String {
+    get_item(resource_type_id)
+        .map(|item| item.en_name.to_string())
+        .unwrap_or_else(|| resource_type_id.to_string())
+}
+
+/// Lines packed into as few fields as fit, continued fields keep the name blank.
+fn fields(name: &str, lines: impl Iterator<Item = String>) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = vec![];
+    for line in lines {
+        match fields.last_mut() {
+            Some((_, value)) if value.chars().count() + line.chars().count() < FIELD_LIMIT => {
+                value.push('\n');
+                value.push_str(&line);
+            }
+            _ => {
+                let name = if fields.is_empty() { name.to_string() } else { "\u{200b}".to_string() };
+                fields.push((name, line));
+            }
+        }
+    }
+    fields
+}
+
+/// The harvests as a code block in a single field, the rest counted when they do not fit.
+fn harvest_field(section: &Section) -> Vec<(String, String)> {
+    let mut lines: Vec<String> = vec![];
+    let mut length = 2 * CODE_BLOCK.len() + 2;
+    for (index, harvest) in section.harvests.iter().enumerate() {
+        let line = format!(
+            "{} {}: {} arrays",
+            system_by_planet(harvest.planet_id)
+                .map(|system| system.en_name.to_string())
+                .unwrap_or_default(),
+            get_celestial(harvest.planet_id)
+                .map(|celestial| celestial.celestial_index.to_string())
+                .unwrap_or_default(),
+            round_to_2_decimal_places(harvest.arrays),
+        );
+        let line = match get_item(harvest.resource_type_id) {
+            Some(item) => format!("{line} of {}", item.en_name),
+            None => line,
+        };
+        // Leave room for the note on the harvests left out.
+        if length + line.chars().count() + 1 > FIELD_LIMIT - 32 {
+            lines.push(format!("… and {} more, see the attachment", section.harvests.len() - index));
+            break;
+        }
+        length += line.chars().count() + 1;
+        lines.push(line);
+    }
+    vec![("Harvest".to_string(), format!("{CODE_BLOCK}\n{}\n{CODE_BLOCK}", lines.join("\n")))]
+}
+
+fn progress_line(progress: &Progress) -> String {
+    let filled = (progress.ratio() * PROGRESS_CELLS as f64).round() as usize;
+    format!(
+        "{} `{}{}` {:.0}% {} of {} {}",
+        if progress.met() { "✅" } else { "⚠️" },
+        "█".repeat(filled),
+        "░".repeat(PROGRESS_CELLS - filled),
+        progress.ratio() * 100.,
+        format_value(progress.produced.floor()),
+        format_value(progress.required),
+        progress.name,
+    )
 }
 
 fn format_value(value: f64) -> String {
@@ -124,6 +571,12 @@ fn format_value(value: f64) -> String {
     }
 }
 
+/// A signed, rounded ISK amount.
+fn format_isk(value: f64) -> String {
+    let sign = if value < -0.5 { "-" } else { "+" };
+    format!("{sign}{}", format_value(value.abs().round()))
+}
+
 fn round_to_2_decimal_places(value: f64) -> f64 {
     let multiplier = 100.0; // 10^2
     (value * multiplier).round() / multiplier
@@ -0,0 +1,30 @@
+// src/tenant.rs
+
+use serenity::model::id::GuildId;
+use sea_orm::EntityTrait;
+
+use manager::database::DatabaseConnection;
+use manager::entities::prelude::*;
+use manager::entities::alliance;
+use manager::entities::corporation;
+
+/// The corporation and alliance a guild was bound to with `/setup`.
+pub struct Tenant {
+    pub alliance: alliance::Model,
+    pub corporation: corporation::Model,
+}
+
+impl Tenant {
+    pub async fn resolve(guild_id: GuildId, db: &DatabaseConnection) -> Result<Option<Tenant>, manager::Error> {
+        let guild = match Guild::find_by_guild_id(guild_id.get() as i64, db).await? {
+            Some(guild) => guild,
+            None => return Ok(None),
+        };
+        let corporation = Corporation::find_by_id(guild.corporation_id).one(db).await?;
+        let alliance = Alliance::find_by_id(guild.alliance_id).one(db).await?;
+
+        Ok(corporation
+            .zip(alliance)
+            .map(|(corporation, alliance)| Tenant { alliance, corporation }))
+    }
+}